@@ -0,0 +1,591 @@
+//! Command-line interface of the launcher binary, for shell aliases, desktop
+//! shortcuts and onboarding scripts. Subcommands run without opening the GUI.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use crate::models::{CreateProjectInput, Project, ProjectConfig, ProjectExport};
+use crate::services::dependency_checker::{DependencyError, DependencyStatus};
+use crate::services::*;
+use crate::services::instance::ForwardReply;
+use crate::services::session::LaunchSession;
+
+const USAGE: &str = "\
+用法: claude-code-launcher <命令> [参数]
+
+命令:
+  list [--json]                              列出所有项目
+  launch <名称|ID> [--new-window]            启动项目 (默认在当前终端中运行，Windows 上总是打开新窗口)
+  print-command <名称|ID> [--shell bash|pwsh|cmd|fish] [--with-secrets]
+                                             输出启动命令，可用于 shell 别名
+  add <名称> <目录> [--mode claude|custom] [--model 模型] [--base-url 地址]
+      [--token 令牌] [--proxy 代理] [--no-skip-permissions]
+                                             新建项目
+  remove <名称|ID>                           删除项目
+  export [文件] [--with-secrets]             导出项目 (默认输出到标准输出，不含令牌和代理密码)
+  import <文件>                              导入项目 (跳过同名项目)
+  doctor                                     检查运行环境
+  help                                       显示本帮助
+
+不带命令运行时打开图形界面。";
+
+const SUBCOMMANDS: [&str; 9] = [
+    "list", "launch", "print-command", "add", "remove", "export", "import", "doctor", "help",
+];
+
+/// Positional arguments and `--flag [value]` options of one subcommand
+struct Args {
+    positional: Vec<String>,
+    options: HashMap<String, Option<String>>,
+}
+
+impl Args {
+    /// `value_options` take the next argument as their value; all other options are switches
+    fn parse(raw: &[String], value_options: &[&str]) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut options = HashMap::new();
+        let mut iter = raw.iter();
+        while let Some(arg) = iter.next() {
+            let Some(name) = arg.strip_prefix("--") else {
+                positional.push(arg.clone());
+                continue;
+            };
+            let (name, inline) = match name.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (name, None),
+            };
+            let value = if value_options.contains(&name) {
+                match inline {
+                    Some(value) => Some(value),
+                    None => Some(iter.next().ok_or(format!("--{} 缺少参数值", name))?.clone()),
+                }
+            } else {
+                None
+            };
+            options.insert(name.to_string(), value);
+        }
+        Ok(Self { positional, options })
+    }
+
+    fn flag(&self, name: &str) -> bool {
+        self.options.contains_key(name)
+    }
+
+    fn value(&self, name: &str) -> Option<&str> {
+        self.options.get(name).and_then(|v| v.as_deref())
+    }
+
+    fn required(&self, index: usize, what: &str) -> Result<&str, String> {
+        self.positional
+            .get(index)
+            .map(String::as_str)
+            .ok_or(format!("缺少参数: {}", what))
+    }
+}
+
+/// GUI apps on Windows have no console; reuse the one of the shell that started us
+#[cfg(windows)]
+fn attach_console() {
+    use windows::Win32::System::Console::{AttachConsole, ATTACH_PARENT_PROCESS};
+
+    unsafe {
+        let _ = AttachConsole(ATTACH_PARENT_PROCESS);
+    }
+}
+
+/// Output of a subcommand and the directory its relative paths are resolved against.
+/// Forwarded invocations run inside the GUI process, so they capture instead of printing.
+pub struct Console {
+    cwd: PathBuf,
+    capture: bool,
+    stdout: String,
+    stderr: String,
+}
+
+impl Console {
+    fn local() -> Self {
+        Self {
+            cwd: std::env::current_dir().unwrap_or_default(),
+            capture: false,
+            stdout: String::new(),
+            stderr: String::new(),
+        }
+    }
+
+    fn out(&mut self, line: impl AsRef<str>) {
+        if self.capture {
+            self.stdout.push_str(line.as_ref());
+            self.stdout.push('\n');
+        } else {
+            println!("{}", line.as_ref());
+        }
+    }
+
+    fn err(&mut self, line: impl AsRef<str>) {
+        if self.capture {
+            self.stderr.push_str(line.as_ref());
+            self.stderr.push('\n');
+        } else {
+            eprintln!("{}", line.as_ref());
+        }
+    }
+
+    fn path(&self, path: &str) -> PathBuf {
+        self.cwd.join(path)
+    }
+}
+
+/// Subcommands that change config.json go through the running GUI, if any, so that there
+/// is a single writer and its window and tray pick up the change. `launch` does as a whole
+/// when it opens a new terminal window; in the current terminal only its bookkeeping does.
+fn forwarded(args: &[String]) -> bool {
+    match args.first().map(String::as_str) {
+        Some("add" | "remove" | "import") => true,
+        Some("launch") => opens_new_window(args),
+        _ => false,
+    }
+}
+
+/// The release build on Windows is a GUI-subsystem binary: the shell that started it does
+/// not wait for it and keeps reading the console, so `claude` cannot share that terminal
+fn opens_new_window(args: &[String]) -> bool {
+    cfg!(windows) || args.iter().any(|arg| arg == "--new-window")
+}
+
+/// No arguments, deep-link URLs and anything else that is not a subcommand
+/// (e.g. `-psn_…` from old macOS Finder) open the GUI
+pub fn is_gui_invocation(args: &[String]) -> bool {
+    let Some(command) = args.first() else {
+        return true;
+    };
+    let is_help = matches!(command.as_str(), "-h" | "--help");
+    !is_help && !SUBCOMMANDS.contains(&command.as_str()) && (command.starts_with('-') || command.contains("://"))
+}
+
+/// Run a subcommand if `args` (without the program name) start with one.
+/// Returns the process exit code, or None to start the GUI instead.
+pub fn run(args: &[String]) -> Option<i32> {
+    if is_gui_invocation(args) {
+        return None;
+    }
+
+    #[cfg(windows)]
+    attach_console();
+
+    if forwarded(args) {
+        if let Ok(reply) = SingleInstance::forward(args) {
+            print!("{}", reply.stdout);
+            eprint!("{}", reply.stderr);
+            return Some(reply.exit_code);
+        }
+    }
+
+    Some(execute(&mut Console::local(), args))
+}
+
+/// Run a subcommand forwarded by a second invocation, with its output captured for the reply
+pub fn run_forwarded(args: &[String], cwd: &str) -> ForwardReply {
+    let mut console = Console {
+        cwd: PathBuf::from(cwd),
+        capture: true,
+        stdout: String::new(),
+        stderr: String::new(),
+    };
+    let exit_code = execute(&mut console, args);
+    ForwardReply {
+        exit_code,
+        stdout: console.stdout,
+        stderr: console.stderr,
+    }
+}
+
+fn execute(console: &mut Console, args: &[String]) -> i32 {
+    let Some(command) = args.first() else {
+        console.out(USAGE);
+        return 0;
+    };
+    if matches!(command.as_str(), "-h" | "--help" | "help") {
+        console.out(USAGE);
+        return 0;
+    }
+
+    let rest = &args[1..];
+    let result = match command.as_str() {
+        "list" => list(console, rest),
+        "launch" => launch(console, rest),
+        "print-command" => print_command(console, rest),
+        "add" => add(console, rest),
+        "remove" => remove(console, rest),
+        "export" => export(console, rest),
+        "import" => import(console, rest),
+        "doctor" => doctor(console),
+        // Bookkeeping of an attached `launch` in another process, only accepted when forwarded
+        BEGIN_LAUNCH if console.capture => begin_launch(console, rest),
+        ABORT_LAUNCH if console.capture => abort_launch(rest),
+        _ => Err(format!("未知命令: {}\n\n{}", command, USAGE)),
+    };
+
+    match result {
+        Ok(code) => code,
+        Err(e) => {
+            console.err(format!("错误: {}", e));
+            2
+        }
+    }
+}
+
+fn list(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let args = Args::parse(raw, &[])?;
+    let projects = ConfigStorage::get_projects()?;
+    if args.flag("json") {
+        let json = serde_json::to_string_pretty(&projects_for_output(projects))
+            .map_err(|e| format!("无法序列化项目列表: {}", e))?;
+        console.out(json);
+        return Ok(0);
+    }
+
+    for project in projects {
+        console.out(format!(
+            "{}{}\t{}\t{}\t{}",
+            if project.is_pinned { "* " } else { "  " },
+            project.name,
+            project.id,
+            project.config.mode,
+            project.working_directory
+        ));
+    }
+    Ok(0)
+}
+
+/// Projects as printed by `list --json`: secrets never leave the launcher this way
+fn projects_for_output(projects: Vec<Project>) -> Vec<Project> {
+    projects
+        .into_iter()
+        .map(|mut project| {
+            project.config.token.clear();
+            project.config.proxy_password.clear();
+            project
+        })
+        .collect()
+}
+
+fn launch(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let args = Args::parse(raw, &[])?;
+    let project = ConfigStorage::find_project(args.required(0, "项目名称或 ID")?)?;
+    let config = Launcher::project_config_map(&project, true);
+    let hooks = LaunchHooks::from_config(&project.config);
+
+    if opens_new_window(raw) {
+        let session = start_session(&project)?;
+        let result = Launcher::launch_with_config_and_dir(config, Some(project.working_directory.clone()), &hooks, Some(&session.id));
+        if result.is_err() {
+            Sessions::abort(&session.id);
+        }
+        result?;
+        console.out(format!("已在新窗口中启动 {}", project.name));
+        return Ok(0);
+    }
+
+    let session_id = through_instance(&[BEGIN_LAUNCH, &project.id], || Ok(start_session(&project)?.id))?;
+    let session_id = session_id.trim();
+
+    match Launcher::run_attached(&config, &project.working_directory, &hooks, Some(session_id)) {
+        Ok(code) => Ok(code),
+        Err(e) => {
+            let _ = through_instance(&[ABORT_LAUNCH, session_id], || {
+                Sessions::abort(session_id);
+                Ok(String::new())
+            });
+            Err(e)
+        }
+    }
+}
+
+/// Hidden subcommands an attached `launch` forwards, so sessions.json and config.json
+/// are only written by the running GUI
+const BEGIN_LAUNCH: &str = "begin-launch";
+const ABORT_LAUNCH: &str = "abort-launch";
+
+/// Run `args` in the running instance and return its stdout, or run `local` when there is none
+fn through_instance(args: &[&str], local: impl FnOnce() -> Result<String, String>) -> Result<String, String> {
+    let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+    match SingleInstance::forward(&args) {
+        Ok(reply) if reply.exit_code == 0 => Ok(reply.stdout),
+        Ok(reply) => Err(reply.stderr.trim().trim_start_matches("错误: ").to_string()),
+        Err(_) => local(),
+    }
+}
+
+fn start_session(project: &Project) -> Result<LaunchSession, String> {
+    let session = Sessions::begin(Some(&project.id), Some(&project.name), Some(&project.working_directory))?;
+    let _ = ConfigStorage::update_project_launched(&project.id);
+    Ok(session)
+}
+
+/// Register the session of an attached launch and print its id
+fn begin_launch(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let project = ConfigStorage::find_project(raw.first().ok_or("缺少参数: 项目 ID")?)?;
+    console.out(start_session(&project)?.id);
+    Ok(0)
+}
+
+fn abort_launch(raw: &[String]) -> Result<i32, String> {
+    Sessions::abort(raw.first().ok_or("缺少参数: 会话 ID")?);
+    Ok(0)
+}
+
+fn print_command(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let args = Args::parse(raw, &["shell"])?;
+    let project = ConfigStorage::find_project(args.required(0, "项目名称或 ID")?)?;
+    let config = Launcher::project_config_map(&project, args.flag("with-secrets"));
+    let hooks = LaunchHooks::from_config(&project.config);
+    let dir = Some(project.working_directory.clone());
+
+    let default_shell = if cfg!(windows) { "pwsh" } else { "bash" };
+    let command = match args.value("shell").unwrap_or(default_shell) {
+        "bash" | "zsh" | "sh" => Launcher::generate_bash_command_with_dir(&config, dir, &hooks),
+        "pwsh" | "powershell" => Launcher::generate_powershell_command_with_dir(&config, dir, &hooks),
+        "cmd" => Launcher::generate_cmd_command_with_dir(&config, dir, &hooks),
+        "fish" => Launcher::generate_fish_command_with_dir(&config, dir, &hooks),
+        other => return Err(format!("不支持的 shell: {} (可选 bash、pwsh、cmd、fish)", other)),
+    };
+    console.out(command);
+    Ok(0)
+}
+
+fn add(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let args = Args::parse(raw, &["mode", "model", "base-url", "token", "proxy"])?;
+    let name = args.required(0, "项目名称")?.trim().to_string();
+    let dir = args.required(1, "工作目录")?;
+    if name.is_empty() {
+        return Err("请输入项目名称".to_string());
+    }
+
+    let working_directory = ConfigStorage::canonical_directory(&console.path(dir).to_string_lossy())?;
+
+    let mut config = ProjectConfig::default();
+    if let Some(mode) = args.value("mode") {
+        if mode != "claude" && mode != "custom" {
+            return Err(format!("未知模式: {} (可选 claude、custom)", mode));
+        }
+        config.mode = mode.to_string();
+    }
+    if let Some(model) = args.value("model") {
+        config.model = model.to_string();
+    }
+    if let Some(base_url) = args.value("base-url") {
+        config.base_url = base_url.to_string();
+    }
+    if let Some(token) = args.value("token") {
+        config.token = token.to_string();
+    }
+    if let Some(proxy) = args.value("proxy") {
+        config.proxy = proxy.to_string();
+    }
+    config.skip_permissions = !args.flag("no-skip-permissions");
+
+    let project = ConfigStorage::create_project(CreateProjectInput {
+        name,
+        working_directory,
+        config,
+    })?;
+    console.out(format!("已创建项目 {} ({})", project.name, project.id));
+    Ok(0)
+}
+
+fn remove(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let args = Args::parse(raw, &[])?;
+    let project = ConfigStorage::find_project(args.required(0, "项目名称或 ID")?)?;
+    ConfigStorage::delete_project(&project.id)?;
+    console.out(format!("已删除项目 {}", project.name));
+    Ok(0)
+}
+
+fn export(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let args = Args::parse(raw, &[])?;
+    let export = ConfigStorage::export_projects(args.flag("with-secrets"))?;
+    let json = serde_json::to_string_pretty(&export).map_err(|e| format!("无法序列化项目: {}", e))?;
+
+    match args.positional.first() {
+        Some(file) => {
+            std::fs::write(console.path(file), json).map_err(|e| format!("无法写入导出文件: {}", e))?;
+            console.err(format!("已导出 {} 个项目到 {}", export.projects.len(), file));
+            if args.flag("with-secrets") {
+                console.err("注意: 导出文件中包含明文令牌和代理密码");
+            }
+        }
+        None => console.out(json),
+    }
+    Ok(0)
+}
+
+fn import(console: &mut Console, raw: &[String]) -> Result<i32, String> {
+    let args = Args::parse(raw, &[])?;
+    let file = args.required(0, "导出文件")?;
+    let content = std::fs::read_to_string(console.path(file)).map_err(|e| format!("无法读取导入文件: {}", e))?;
+    let export: ProjectExport =
+        serde_json::from_str(&content).map_err(|e| format!("导入文件格式错误: {}", e))?;
+
+    let (created, skipped) = ConfigStorage::import_projects(export)?;
+    for project in &created {
+        console.out(format!("已导入 {}", project.name));
+        if !Path::new(&project.working_directory).is_dir() {
+            console.out(format!("  警告: 工作目录不存在: {}", project.working_directory));
+        }
+    }
+    for name in &skipped {
+        console.out(format!("已跳过 {} (已存在同名项目)", name));
+    }
+    Ok(0)
+}
+
+fn describe_error(error: &DependencyError) -> String {
+    match error {
+        DependencyError::NotFound { command } => format!("未找到 {}", command),
+        DependencyError::CommandFailed { command, exit_code, stderr } => {
+            format!("{} 执行失败 (退出码 {:?}): {}", command, exit_code, stderr.trim())
+        }
+        DependencyError::SpawnFailed { command, message } => format!("无法运行 {}: {}", command, message),
+        DependencyError::VersionUnparseable { raw_output } => format!("无法识别版本号: {}", raw_output.trim()),
+        DependencyError::BelowMinimum { version, minimum } => format!("版本 {} 低于最低要求 {}", version, minimum),
+    }
+}
+
+/// One line of `doctor` output; returns whether the check passed
+fn report(console: &mut Console, ok: bool, label: &str, detail: impl AsRef<str>) -> bool {
+    console.out(format!("[{}] {}: {}", if ok { " OK " } else { "FAIL" }, label, detail.as_ref()));
+    ok
+}
+
+fn report_dependency(console: &mut Console, label: &str, status: &DependencyStatus) -> bool {
+    let ok = status.installed && status.meets_requirement;
+    let detail = match (&status.version, &status.error) {
+        (_, Some(error)) => describe_error(error),
+        (Some(version), None) => match &status.path {
+            Some(path) => format!("{} ({})", version, path),
+            None => version.clone(),
+        },
+        (None, None) => "未安装".to_string(),
+    };
+    report(console, ok, label, detail)
+}
+
+fn doctor(console: &mut Console) -> Result<i32, String> {
+    let mut ok = true;
+
+    ok &= report_dependency(console, "Node.js", &DependencyChecker::check_nodejs());
+    ok &= report_dependency(console, "Claude Code", &DependencyChecker::check_claude());
+    #[cfg(windows)]
+    {
+        ok &= report_dependency(console, "Git Bash", &DependencyChecker::check_gitbash());
+    }
+
+    let registry = NpmRegistry::resolve();
+    report(console, true, "npm 镜像", format!("{} ({:?})", registry.url, registry.source));
+
+    let detected = SystemProxy::detect();
+    if detected.is_empty() {
+        report(console, true, "系统代理", "未设置");
+    }
+    for proxy in detected {
+        let mut parts = Vec::new();
+        if let Some(http) = proxy.http_proxy {
+            parts.push(format!("HTTP {}", http));
+        }
+        if let Some(https) = proxy.https_proxy {
+            parts.push(format!("HTTPS {}", https));
+        }
+        if let Some(pac) = proxy.pac_url {
+            parts.push(format!("PAC {}", pac));
+        }
+        report(console, true, &format!("系统代理 ({:?})", proxy.source), parts.join(", "));
+    }
+
+    let settings = ConfigStorage::get_launcher_settings()?;
+    if !settings.ca_bundle.path.is_empty() {
+        ok &= match CaBundle::inspect(&settings.ca_bundle.path) {
+            Ok(info) => {
+                let expired = info.certificates.iter().filter(|c| c.expired).count();
+                report(console, 
+                    expired == 0,
+                    "全局根证书",
+                    format!("{} 个证书，{} 个已过期 ({})", info.certificates.len(), expired, info.path),
+                )
+            }
+            Err(e) => report(console, false, "全局根证书", e),
+        };
+    }
+
+    let projects = ConfigStorage::get_projects()?;
+    report(console, true, "项目", format!("{} 个", projects.len()));
+    for project in &projects {
+        if !Path::new(&project.working_directory).is_dir() {
+            ok &= report(console, false, &format!("项目 {}", project.name), format!("工作目录不存在: {}", project.working_directory));
+        }
+        if !project.config.ca_bundle.path.is_empty() {
+            if let Err(e) = CaBundle::inspect(&project.config.ca_bundle.path) {
+                ok &= report(console, false, &format!("项目 {} 根证书", project.name), e);
+            }
+        }
+    }
+
+    report(console, true, "日志目录", Logger::log_dir().to_string_lossy());
+    Ok(if ok { 0 } else { 1 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_positional_switches_and_values() {
+        let args = Args::parse(
+            &strings(&["my app", "./dir", "--mode", "custom", "--model=qwen", "--no-skip-permissions"]),
+            &["mode", "model"],
+        )
+        .unwrap();
+        assert_eq!(args.positional, ["my app", "./dir"]);
+        assert_eq!(args.value("mode"), Some("custom"));
+        assert_eq!(args.value("model"), Some("qwen"));
+        assert!(args.flag("no-skip-permissions"));
+        assert_eq!(args.value("no-skip-permissions"), None);
+        assert!(!args.flag("token"));
+    }
+
+    #[test]
+    fn parse_value_option_takes_next_argument_verbatim() {
+        let args = Args::parse(&strings(&["--token", "--not-a-flag"]), &["token"]).unwrap();
+        assert_eq!(args.value("token"), Some("--not-a-flag"));
+        assert!(args.positional.is_empty());
+    }
+
+    #[test]
+    fn parse_missing_value_is_an_error() {
+        assert_eq!(Args::parse(&strings(&["x", "--shell"]), &["shell"]).err().unwrap(), "--shell 缺少参数值");
+        let args = Args::parse(&strings(&[]), &[]).unwrap();
+        assert_eq!(args.required(0, "项目名称").unwrap_err(), "缺少参数: 项目名称");
+    }
+
+    #[test]
+    fn gui_invocations() {
+        assert!(is_gui_invocation(&[]));
+        assert!(is_gui_invocation(&strings(&["claude-launcher://launch?project=x"])));
+        assert!(is_gui_invocation(&strings(&["-psn_0_12345"])));
+        assert!(!is_gui_invocation(&strings(&["--help"])));
+        assert!(!is_gui_invocation(&strings(&["list"])));
+        assert!(!is_gui_invocation(&strings(&["lsit"])));
+    }
+
+    #[test]
+    fn config_changes_are_forwarded() {
+        assert!(forwarded(&strings(&["add", "x", "."])));
+        assert!(forwarded(&strings(&["launch", "x", "--new-window"])));
+        assert_eq!(forwarded(&strings(&["launch", "x"])), cfg!(windows));
+        assert!(!forwarded(&strings(&["list"])));
+        assert!(!forwarded(&strings(&[BEGIN_LAUNCH, "x"])));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::services::*;
-use crate::models::{Project, ProjectConfig, CreateProjectInput, UpdateProjectInput, ProjectOrderItem, PinnedOrderItem};
+use crate::models::{Project, ProjectConfig, ProjectSnapshot, CreateProjectInput, UpdateProjectInput, ProjectOrderItem, PinnedOrderItem, LauncherSettings, LauncherSettingsSnapshot};
 use std::collections::HashMap;
 
 #[tauri::command]
@@ -13,13 +13,13 @@ pub async fn check_claude() -> Result<dependency_checker::DependencyStatus, Stri
 }
 
 #[tauri::command]
-pub async fn check_nodejs_with_update() -> Result<dependency_checker::DependencyStatus, String> {
-    Ok(DependencyChecker::check_nodejs_with_update().await)
+pub async fn check_nodejs_with_update(force: Option<bool>) -> Result<dependency_checker::DependencyStatus, String> {
+    Ok(DependencyChecker::check_nodejs_with_update(force.unwrap_or(false)).await)
 }
 
 #[tauri::command]
-pub async fn check_claude_with_update() -> Result<dependency_checker::DependencyStatus, String> {
-    Ok(DependencyChecker::check_claude_with_update().await)
+pub async fn check_claude_with_update(force: Option<bool>) -> Result<dependency_checker::DependencyStatus, String> {
+    Ok(DependencyChecker::check_claude_with_update(force.unwrap_or(false)).await)
 }
 
 #[tauri::command]
@@ -28,8 +28,13 @@ pub async fn check_gitbash() -> Result<dependency_checker::DependencyStatus, Str
 }
 
 #[tauri::command]
-pub async fn check_gitbash_with_update() -> Result<dependency_checker::DependencyStatus, String> {
-    Ok(DependencyChecker::check_gitbash_with_update().await)
+pub async fn check_gitbash_with_update(force: Option<bool>) -> Result<dependency_checker::DependencyStatus, String> {
+    Ok(DependencyChecker::check_gitbash_with_update(force.unwrap_or(false)).await)
+}
+
+#[tauri::command]
+pub fn clear_update_cache() -> Result<(), String> {
+    UpdateCache::clear()
 }
 
 #[tauri::command]
@@ -38,39 +43,284 @@ pub fn refresh_system_path() {
     DependencyChecker::refresh_system_path();
 }
 
+/// Forward install job progress to the frontend as Tauri events
+fn emit_install_event(app_handle: &tauri::AppHandle, event: install_job::InstallJobEvent) {
+    use tauri::Emitter;
+
+    let name = match event {
+        install_job::InstallJobEvent::Output { .. } => "install-output",
+        install_job::InstallJobEvent::Finished { .. } => "install-finished",
+    };
+    let _ = app_handle.emit(name, event);
+}
+
+fn start_install_job(app_handle: tauri::AppHandle, recipe: install_job::InstallRecipe) -> Result<install_job::InstallJobInfo, String> {
+    InstallJobs::start(recipe, move |event| emit_install_event(&app_handle, event))
+}
+
+#[tauri::command]
+pub async fn install_nodejs(app_handle: tauri::AppHandle) -> Result<install_job::InstallJobInfo, String> {
+    start_install_job(app_handle, Installer::nodejs_install_recipe()?)
+}
+
+#[tauri::command]
+pub async fn update_nodejs(app_handle: tauri::AppHandle) -> Result<install_job::InstallJobInfo, String> {
+    start_install_job(app_handle, Installer::nodejs_update_recipe()?)
+}
+
+#[tauri::command]
+pub async fn install_claude(app_handle: tauri::AppHandle, method: Option<dependency_checker::InstallMethod>) -> Result<install_job::InstallJobInfo, String> {
+    start_claude_version_job(app_handle, Installer::claude_install_recipe(method)?)
+}
+
+/// Like `start_install_job`, but remembers the replaced version for rollback once the job succeeds
+fn start_claude_version_job(app_handle: tauri::AppHandle, recipe: install_job::InstallRecipe) -> Result<install_job::InstallJobInfo, String> {
+    let before = DependencyChecker::check_claude().version;
+    InstallJobs::start(recipe, move |event| {
+        if let install_job::InstallJobEvent::Finished { ref job, ref status } = event {
+            if job.state == install_job::InstallJobState::Succeeded && before.is_some() && status.version != before {
+                let _ = ConfigStorage::set_claude_previous_version(before.clone());
+            }
+            if job.state == install_job::InstallJobState::Succeeded
+                && status.install_method == Some(dependency_checker::InstallMethod::Native)
+            {
+                if let Err(e) = ClaudeNative::ensure_on_path() {
+                    Logger::warn("install", format!("无法将 Claude Code 原生安装目录加入 PATH: {}", e));
+                }
+            }
+        }
+        emit_install_event(&app_handle, event);
+    })
+}
+
+#[tauri::command]
+pub async fn update_claude(app_handle: tauri::AppHandle) -> Result<install_job::InstallJobInfo, String> {
+    start_claude_version_job(app_handle, Installer::claude_update_recipe()?)
+}
+
+#[tauri::command]
+pub async fn list_claude_versions() -> Result<npm_registry::PackageVersions, String> {
+    let registry = NpmRegistry::resolve();
+    NpmRegistry::fetch_versions(&registry, npm_registry::CLAUDE_PACKAGE).await
+}
+
+#[tauri::command]
+pub async fn install_claude_version(app_handle: tauri::AppHandle, version: String) -> Result<install_job::InstallJobInfo, String> {
+    start_claude_version_job(app_handle, Installer::claude_version_recipe(&version)?)
+}
+
+#[tauri::command]
+pub fn inspect_offline_package(path: String, version: Option<String>) -> Result<offline_bundle::VerifiedPackage, String> {
+    OfflineBundle::resolve(&path, version.as_deref())
+}
+
+#[tauri::command]
+pub async fn install_claude_offline(app_handle: tauri::AppHandle, path: String, version: Option<String>) -> Result<install_job::InstallJobInfo, String> {
+    let package = OfflineBundle::resolve(&path, version.as_deref())?;
+    start_claude_version_job(app_handle, Installer::claude_offline_recipe(&package)?)
+}
+
+#[tauri::command]
+pub async fn prepare_offline_bundle(dest_dir: String, version: Option<String>) -> Result<offline_bundle::OfflineManifest, String> {
+    OfflineBundle::prepare(&dest_dir, version.as_deref()).await
+}
+
+#[tauri::command]
+pub async fn select_offline_package(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = app_handle
+        .dialog()
+        .file()
+        .set_title("选择 Claude Code 离线安装包")
+        .add_filter("npm 安装包", &["tgz"])
+        .blocking_pick_file();
+
+    Ok(result.map(|p| p.to_string()))
+}
+
+#[tauri::command]
+pub fn get_claude_previous_version() -> Result<Option<String>, String> {
+    ConfigStorage::get_claude_previous_version()
+}
+
+#[tauri::command]
+pub async fn rollback_claude(app_handle: tauri::AppHandle) -> Result<install_job::InstallJobInfo, String> {
+    let previous = ConfigStorage::get_claude_previous_version()?
+        .ok_or("没有可回滚的 Claude Code 版本")?;
+    start_claude_version_job(app_handle, Installer::claude_version_recipe(&previous)?)
+}
+
 #[tauri::command]
-pub async fn install_nodejs() -> Result<(), String> {
-    Installer::install_nodejs()
+pub fn get_claude_installations() -> claude_native::ClaudeInstallations {
+    ClaudeNative::installations()
 }
 
 #[tauri::command]
-pub async fn update_nodejs() -> Result<(), String> {
-    Installer::update_nodejs()
+pub async fn migrate_claude_to_native(app_handle: tauri::AppHandle) -> Result<install_job::InstallJobInfo, String> {
+    start_claude_version_job(app_handle, Installer::claude_migrate_recipe()?)
+}
+
+#[tauri::command]
+pub async fn install_gitbash(app_handle: tauri::AppHandle) -> Result<install_job::InstallJobInfo, String> {
+    start_install_job(app_handle, Installer::gitbash_install_recipe()?)
+}
+
+#[tauri::command]
+pub async fn update_gitbash(app_handle: tauri::AppHandle) -> Result<install_job::InstallJobInfo, String> {
+    start_install_job(app_handle, Installer::gitbash_update_recipe()?)
+}
+
+#[tauri::command]
+pub fn get_npm_prefix_status() -> npm_prefix::NpmPrefixStatus {
+    NpmPrefix::status()
+}
+
+#[tauri::command]
+pub fn configure_npm_user_prefix() -> Result<npm_prefix::NpmPrefixStatus, String> {
+    NpmPrefix::configure_user_prefix()
+}
+
+#[tauri::command]
+pub async fn cleanup(options: uninstaller::CleanupOptions) -> Result<uninstaller::CleanupReport, String> {
+    tokio::task::spawn_blocking(move || Uninstaller::run(&options))
+        .await
+        .map_err(|e| format!("清理任务失败: {}", e))?
+}
+
+#[tauri::command]
+pub fn cancel_install_job(job_id: String) -> Result<(), String> {
+    InstallJobs::cancel(&job_id)
+}
+
+#[tauri::command]
+pub fn list_install_jobs() -> Vec<install_job::InstallJobInfo> {
+    InstallJobs::list()
+}
+
+/// Linux has no external terminal integration, so launches there always use the in-app terminal
+fn use_in_app_terminal() -> bool {
+    cfg!(target_os = "linux")
+        || ConfigStorage::get_launcher_settings()
+            .map(|settings| settings.in_app_terminal)
+            .unwrap_or(false)
+}
+
+fn emit_pty_event(app_handle: &tauri::AppHandle, event: pty::PtyEvent) {
+    use tauri::Emitter;
+
+    let name = match event {
+        pty::PtyEvent::Output { .. } => "terminal-output",
+        pty::PtyEvent::Exit { .. } => "terminal-exit",
+    };
+    let _ = app_handle.emit(name, event);
+}
+
+/// Default size until the terminal view reports its real one
+const DEFAULT_TERMINAL_COLS: u16 = 120;
+const DEFAULT_TERMINAL_ROWS: u16 = 30;
+
+/// Start `claude` in a new in-app terminal tab and tell the frontend to show it
+fn open_terminal(
+    app_handle: tauri::AppHandle,
+    config: &HashMap<String, String>,
+    hooks: &LaunchHooks,
+    title: String,
+    project_id: Option<String>,
+    working_dir: Option<String>,
+    session_id: Option<String>,
+) -> Result<pty::TerminalInfo, String> {
+    use tauri::Emitter;
+
+    let (env, args) = Launcher::claude_invocation(config);
+    let shell = Launcher::hooked_shell(config, hooks, session_id.as_deref());
+    let options = pty::PtySpawnOptions {
+        title,
+        project_id,
+        session_id,
+        working_dir,
+        env,
+        args,
+        shell,
+        cols: DEFAULT_TERMINAL_COLS,
+        rows: DEFAULT_TERMINAL_ROWS,
+    };
+
+    let events = app_handle.clone();
+    let info = PtyTerminals::spawn(options, move |event| emit_pty_event(&events, event))?;
+    let _ = app_handle.emit("terminal-opened", &info);
+    Ok(info)
+}
+
+#[tauri::command]
+pub fn launch_claude_code(app_handle: tauri::AppHandle, config: HashMap<String, String>) -> Result<session::LaunchSession, String> {
+    let session = Sessions::begin(None, None, None)?;
+    let result = if use_in_app_terminal() {
+        open_terminal(app_handle, &config, &LaunchHooks::default(), "Claude Code".to_string(), None, None, Some(session.id.clone())).map(|_| ())
+    } else {
+        Launcher::launch_with_config(config, Some(&session.id))
+    };
+    if let Err(e) = result {
+        Sessions::abort(&session.id);
+        return Err(e);
+    }
+    Ok(session)
 }
 
 #[tauri::command]
-pub async fn install_claude() -> Result<(), String> {
-    Installer::install_claude()
+pub fn write_terminal(terminal_id: String, data: String) -> Result<(), String> {
+    PtyTerminals::write(&terminal_id, &data)
 }
 
 #[tauri::command]
-pub async fn update_claude() -> Result<(), String> {
-    Installer::update_claude()
+pub fn resize_terminal(terminal_id: String, cols: u16, rows: u16) -> Result<(), String> {
+    PtyTerminals::resize(&terminal_id, cols, rows)
 }
 
 #[tauri::command]
-pub async fn install_gitbash() -> Result<(), String> {
-    Installer::install_gitbash()
+pub fn get_terminal_snapshot(terminal_id: String) -> Result<pty::TerminalSnapshot, String> {
+    PtyTerminals::snapshot(&terminal_id)
 }
 
 #[tauri::command]
-pub async fn update_gitbash() -> Result<(), String> {
-    Installer::update_gitbash()
+pub fn close_terminal(terminal_id: String) -> Result<(), String> {
+    PtyTerminals::close(&terminal_id)
 }
 
 #[tauri::command]
-pub fn launch_claude_code(config: HashMap<String, String>) -> Result<(), String> {
-    Launcher::launch_with_config(config)
+pub fn list_terminals() -> Vec<pty::TerminalInfo> {
+    PtyTerminals::list()
+}
+
+#[tauri::command]
+pub fn list_sessions(project_id: Option<String>) -> Result<Vec<session::LaunchSession>, String> {
+    Sessions::list(project_id.as_deref())
+}
+
+#[tauri::command]
+pub fn get_session(id: String) -> Result<session::LaunchSession, String> {
+    Sessions::get(&id)
+}
+
+#[tauri::command]
+pub fn get_session_hook_output(id: String) -> Result<String, String> {
+    Sessions::hook_output(&id)
+}
+
+#[tauri::command]
+pub fn get_project_session_stats() -> Result<HashMap<String, session::ProjectSessionStats>, String> {
+    Sessions::project_stats()
+}
+
+/// Launcher log entries (already redacted) for attaching to bug reports
+#[tauri::command]
+pub fn get_logs(filter: Option<LogFilter>) -> Result<Vec<LogEntry>, String> {
+    Logger::read(&filter.unwrap_or_default())
+}
+
+#[tauri::command]
+pub fn get_log_directory() -> String {
+    Logger::log_dir().to_string_lossy().to_string()
 }
 
 #[tauri::command]
@@ -101,13 +351,18 @@ pub fn get_platform() -> String {
 }
 
 #[tauri::command]
-pub fn save_to_settings(config: HashMap<String, String>) -> Result<(), String> {
-    SettingsManager::save_config(config)
+pub fn get_settings_file_version() -> Result<String, String> {
+    SettingsManager::file_version()
 }
 
 #[tauri::command]
-pub fn reset_settings() -> Result<(), String> {
-    SettingsManager::reset_config()
+pub fn save_to_settings(config: HashMap<String, String>, version: Option<String>) -> Result<(), String> {
+    SettingsManager::save_config(config, version.as_deref())
+}
+
+#[tauri::command]
+pub fn reset_settings(version: Option<String>) -> Result<(), String> {
+    SettingsManager::reset_config(version.as_deref())
 }
 
 #[tauri::command]
@@ -137,6 +392,11 @@ pub fn get_project(id: String) -> Result<Project, String> {
     ConfigStorage::get_project(&id)
 }
 
+#[tauri::command]
+pub fn get_project_snapshot(id: String) -> Result<ProjectSnapshot, String> {
+    ConfigStorage::get_project_snapshot(&id)
+}
+
 #[tauri::command]
 pub fn create_project(name: String, working_directory: String, config: ProjectConfig) -> Result<Project, String> {
     let input = CreateProjectInput {
@@ -148,12 +408,13 @@ pub fn create_project(name: String, working_directory: String, config: ProjectCo
 }
 
 #[tauri::command]
-pub fn update_project(id: String, name: Option<String>, working_directory: Option<String>, config: Option<ProjectConfig>, is_pinned: Option<bool>) -> Result<Project, String> {
+pub fn update_project(id: String, name: Option<String>, working_directory: Option<String>, config: Option<ProjectConfig>, is_pinned: Option<bool>, version: Option<String>) -> Result<Project, String> {
     let updates = UpdateProjectInput {
         name,
         working_directory,
         config,
         is_pinned,
+        version,
     };
     ConfigStorage::update_project(&id, updates)
 }
@@ -164,40 +425,93 @@ pub fn delete_project(id: String) -> Result<(), String> {
 }
 
 #[tauri::command]
-pub fn launch_project(id: String) -> Result<(), String> {
+pub fn launch_project(app_handle: tauri::AppHandle, id: String) -> Result<session::LaunchSession, String> {
     let project = ConfigStorage::get_project(&id)?;
 
-    // Build config from project
-    let mut config: HashMap<String, String> = HashMap::new();
+    // Build config from project; proxy credentials are only filled in here
+    let config = Launcher::project_config_map(&project, true);
+    let hooks = LaunchHooks::from_config(&project.config);
 
-    if project.config.mode == "claude" {
-        if !project.config.proxy.is_empty() {
-            config.insert("HTTP_PROXY".to_string(), project.config.proxy.clone());
-            config.insert("HTTPS_PROXY".to_string(), project.config.proxy.clone());
-        }
+    // Launch with working directory
+    let session = Sessions::begin(Some(&project.id), Some(&project.name), Some(&project.working_directory))?;
+    let result = if use_in_app_terminal() {
+        open_terminal(
+            app_handle,
+            &config,
+            &hooks,
+            project.name.clone(),
+            Some(project.id.clone()),
+            Some(project.working_directory.clone()),
+            Some(session.id.clone()),
+        )
+        .map(|_| ())
     } else {
-        if !project.config.model.is_empty() {
-            config.insert("ANTHROPIC_MODEL".to_string(), project.config.model.clone());
-        }
-        if !project.config.base_url.is_empty() {
-            config.insert("ANTHROPIC_BASE_URL".to_string(), project.config.base_url.clone());
-        }
-        if !project.config.token.is_empty() {
-            config.insert("ANTHROPIC_AUTH_TOKEN".to_string(), project.config.token.clone());
-        }
+        Launcher::launch_with_config_and_dir(config, Some(project.working_directory.clone()), &hooks, Some(&session.id))
+    };
+    if let Err(e) = result {
+        Sessions::abort(&session.id);
+        return Err(e);
     }
 
-    if project.config.skip_permissions {
-        config.insert("SKIP_PERMISSIONS".to_string(), "true".to_string());
+    // Update last launched timestamp
+    let _ = ConfigStorage::update_project_launched(&id);
+
+    Ok(session)
+}
+
+#[tauri::command]
+pub fn create_project_shortcut(id: String) -> Result<Project, String> {
+    ConfigStorage::create_project_shortcut(&id)
+}
+
+#[tauri::command]
+pub fn remove_project_shortcut(id: String) -> Result<Project, String> {
+    ConfigStorage::remove_project_shortcut(&id)
+}
+
+/// Store a link opened from outside the app and bring the window up to confirm it
+pub fn receive_deep_link(app_handle: &tauri::AppHandle, url: &str) {
+    use tauri::{Emitter, Manager};
+
+    let request = DeepLinks::receive(url);
+    let _ = app_handle.emit("deep-link", &request);
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
     }
+}
 
-    // Launch with working directory
-    Launcher::launch_with_config_and_dir(config, Some(project.working_directory.clone()))?;
+#[tauri::command]
+pub fn get_pending_deep_links() -> Result<Vec<deep_link::DeepLinkRequest>, String> {
+    DeepLinks::pending()
+}
 
-    // Update last launched timestamp
-    let _ = ConfigStorage::update_project_launched(&id);
+#[tauri::command]
+pub fn confirm_deep_link(app_handle: tauri::AppHandle, id: String) -> Result<session::LaunchSession, String> {
+    let project_id = match DeepLinks::take(&id)? {
+        deep_link::DeepLinkAction::Launch { project_id, .. } => project_id,
+        deep_link::DeepLinkAction::Open { project_id: Some(project_id), .. } => project_id,
+        deep_link::DeepLinkAction::Open { path, project_id: None, .. } => {
+            // No project for this directory yet: create one named after it
+            let name = std::path::Path::new(&path)
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.clone());
+            let input = CreateProjectInput {
+                name,
+                working_directory: path,
+                config: ProjectConfig::default(),
+            };
+            ConfigStorage::create_project(input)?.id
+        }
+    };
+    launch_project(app_handle, project_id)
+}
 
-    Ok(())
+#[tauri::command]
+pub fn dismiss_deep_link(id: String) -> Result<(), String> {
+    DeepLinks::dismiss(&id)
 }
 
 #[tauri::command]
@@ -216,49 +530,58 @@ pub async fn select_directory(app_handle: tauri::AppHandle) -> Result<Option<Str
 #[tauri::command]
 pub fn generate_project_powershell_command(id: String) -> Result<String, String> {
     let project = ConfigStorage::get_project(&id)?;
-    let config = build_config_map(&project);
-    Ok(Launcher::generate_powershell_command_with_dir(&config, Some(project.working_directory)))
+    let config = Launcher::project_config_map(&project, false);
+    Ok(Launcher::generate_powershell_command_with_dir(&config, Some(project.working_directory), &LaunchHooks::from_config(&project.config)))
 }
 
 #[tauri::command]
 pub fn generate_project_cmd_command(id: String) -> Result<String, String> {
     let project = ConfigStorage::get_project(&id)?;
-    let config = build_config_map(&project);
-    Ok(Launcher::generate_cmd_command_with_dir(&config, Some(project.working_directory)))
+    let config = Launcher::project_config_map(&project, false);
+    Ok(Launcher::generate_cmd_command_with_dir(&config, Some(project.working_directory), &LaunchHooks::from_config(&project.config)))
 }
 
 #[tauri::command]
 pub fn generate_project_bash_command(id: String) -> Result<String, String> {
     let project = ConfigStorage::get_project(&id)?;
-    let config = build_config_map(&project);
-    Ok(Launcher::generate_bash_command_with_dir(&config, Some(project.working_directory)))
+    let config = Launcher::project_config_map(&project, false);
+    Ok(Launcher::generate_bash_command_with_dir(&config, Some(project.working_directory), &LaunchHooks::from_config(&project.config)))
 }
 
-fn build_config_map(project: &Project) -> HashMap<String, String> {
-    let mut config: HashMap<String, String> = HashMap::new();
+#[tauri::command]
+pub fn validate_proxy_url(url: String) -> Result<proxy::ProxyInfo, String> {
+    Proxy::validate(&url)
+}
 
-    if project.config.mode == "claude" {
-        if !project.config.proxy.is_empty() {
-            config.insert("HTTP_PROXY".to_string(), project.config.proxy.clone());
-            config.insert("HTTPS_PROXY".to_string(), project.config.proxy.clone());
-        }
-    } else {
-        if !project.config.model.is_empty() {
-            config.insert("ANTHROPIC_MODEL".to_string(), project.config.model.clone());
-        }
-        if !project.config.base_url.is_empty() {
-            config.insert("ANTHROPIC_BASE_URL".to_string(), project.config.base_url.clone());
-        }
-        if !project.config.token.is_empty() {
-            config.insert("ANTHROPIC_AUTH_TOKEN".to_string(), project.config.token.clone());
-        }
-    }
+#[tauri::command]
+pub fn inspect_ca_bundle(path: String) -> Result<ca_bundle::CaBundleInfo, String> {
+    CaBundle::inspect(&path)
+}
 
-    if project.config.skip_permissions {
-        config.insert("SKIP_PERMISSIONS".to_string(), "true".to_string());
-    }
+#[tauri::command]
+pub async fn select_ca_bundle_file(app_handle: tauri::AppHandle) -> Result<Option<String>, String> {
+    use tauri_plugin_dialog::DialogExt;
+
+    let result = app_handle
+        .dialog()
+        .file()
+        .set_title("选择 CA 证书文件")
+        .add_filter("PEM 证书", &["pem", "crt", "cer"])
+        .blocking_pick_file();
 
-    config
+    Ok(result.map(|p| p.to_string()))
+}
+
+/// Proxy settings found in the environment and OS network settings, for filling a project's proxy
+#[tauri::command]
+pub fn detect_system_proxy() -> Vec<system_proxy::DetectedProxy> {
+    SystemProxy::detect()
+}
+
+/// Takes the unsaved form config so the user can test before saving
+#[tauri::command]
+pub async fn test_proxy_connectivity(config: ProjectConfig) -> Result<proxy::ConnectivityResult, String> {
+    Proxy::test_connectivity(&config).await
 }
 
 #[tauri::command]
@@ -283,6 +606,26 @@ pub fn toggle_project_pinned(id: String, is_pinned: bool) -> Result<Project, Str
     ConfigStorage::toggle_project_pinned(&id, is_pinned)
 }
 
+#[tauri::command]
+pub fn get_launcher_settings() -> Result<LauncherSettings, String> {
+    ConfigStorage::get_launcher_settings()
+}
+
+#[tauri::command]
+pub fn get_launcher_settings_snapshot() -> Result<LauncherSettingsSnapshot, String> {
+    ConfigStorage::get_launcher_settings_snapshot()
+}
+
+#[tauri::command]
+pub fn save_launcher_settings(settings: LauncherSettings, version: Option<String>) -> Result<(), String> {
+    ConfigStorage::save_launcher_settings(settings, version.as_deref())
+}
+
+#[tauri::command]
+pub fn get_npm_registry() -> npm_registry::RegistryConfig {
+    NpmRegistry::resolve()
+}
+
 #[tauri::command]
 pub fn get_onboarding_status() -> Result<bool, String> {
     ConfigStorage::get_onboarding_status()
@@ -1,19 +1,126 @@
 mod services;
 mod commands;
 mod models;
+mod tray;
+pub mod cli;
+
+use services::instance::{ForwardReply, ForwardedArgs};
+use services::{ConfigStorage, FileWatch, Logger, SettingsManager, SingleInstance};
+
+/// A second invocation of the launcher, received by the running one
+fn handle_forwarded(app: &tauri::AppHandle, request: ForwardedArgs) -> ForwardReply {
+    if !cli::is_gui_invocation(&request.args) {
+        return cli::run_forwarded(&request.args, &request.cwd);
+    }
+
+    let links: Vec<&String> = request.args.iter().filter(|arg| arg.contains("://")).collect();
+    if links.is_empty() {
+        tray::show_main_window(app);
+    }
+    for link in links {
+        commands::receive_deep_link(app, link);
+    }
+    ForwardReply::default()
+}
+
+/// Tell the window (and the tray) when config.json or ~/.claude/settings.json is changed
+/// from outside the launcher, so they reload instead of showing and saving stale data
+fn watch_config_files(app: &tauri::AppHandle) {
+    use tauri::Emitter;
+
+    let (Ok(config_path), Ok(settings_path)) = (ConfigStorage::get_config_path(), SettingsManager::get_settings_path()) else {
+        return;
+    };
+    let handle = app.clone();
+    let watched_config = config_path.clone();
+    FileWatch::watch(vec![config_path, settings_path], move |path| {
+        let file = if path == watched_config {
+            ConfigStorage::notify_changed();
+            "config"
+        } else {
+            "settings"
+        };
+        let _ = handle.emit("config-changed", serde_json::json!({ "file": file }));
+    });
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Only one window may work on config.json: hand our arguments to a running launcher instead
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    // Without the lock the window still opens, only without forwarding; the user is told why
+    let claim_error = match SingleInstance::claim(&args) {
+        Ok(true) => None,
+        Ok(false) => return,
+        Err(e) => {
+            Logger::error("instance", format!("无法确认是否已有启动器在运行: {}", e));
+            Some(e)
+        }
+    };
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_clipboard_manager::init())
         .plugin(tauri_plugin_process::init())
-        .setup(|app| {
+        .plugin(tauri_plugin_deep_link::init())
+        .setup(move |app| {
+            use tauri_plugin_deep_link::DeepLinkExt;
+
             #[cfg(desktop)]
             app.handle().plugin(tauri_plugin_updater::Builder::new().build())?;
+            #[cfg(desktop)]
+            tray::init(app.handle())?;
+
+            watch_config_files(app.handle());
+
+            if let Some(e) = claim_error {
+                use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+                // Listening too would take forwarded invocations away from an instance that may still be running
+                app.dialog()
+                    .message(format!(
+                        "{}\n\n启动器将继续运行，但命令行和外部链接不会转交给此窗口；若已有启动器窗口，请关闭其中一个以免同时修改配置。",
+                        e
+                    ))
+                    .title("单实例检查失败")
+                    .kind(MessageDialogKind::Warning)
+                    .show(|_| {});
+            } else {
+                let handle = app.handle().clone();
+                if let Err(e) = SingleInstance::listen(move |request| handle_forwarded(&handle, request)) {
+                    Logger::error("instance", format!("无法启动单实例监听: {}", e));
+                }
+            }
+
+            // macOS registers the scheme from the bundle; elsewhere register it at runtime
+            // so links also work for portable and development builds
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                Logger::error("deep_link", format!("无法注册链接协议: {}", e));
+            }
+
+            let handle = app.handle().clone();
+            if let Ok(Some(urls)) = app.deep_link().get_current() {
+                for url in urls {
+                    commands::receive_deep_link(&handle, url.as_str());
+                }
+            }
+            app.deep_link().on_open_url(move |event| {
+                for url in event.urls() {
+                    commands::receive_deep_link(&handle, url.as_str());
+                }
+            });
             Ok(())
         })
+        .on_window_event(|window, event| {
+            if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                if window.label() == "main" && tray::hide_on_close() {
+                    api.prevent_close();
+                    let _ = window.hide();
+                }
+            }
+        })
         .invoke_handler(tauri::generate_handler![
             commands::check_nodejs,
             commands::check_claude,
@@ -21,18 +128,46 @@ pub fn run() {
             commands::check_nodejs_with_update,
             commands::check_claude_with_update,
             commands::check_gitbash_with_update,
+            commands::clear_update_cache,
             commands::refresh_system_path,
             commands::install_nodejs,
             commands::update_nodejs,
             commands::install_claude,
             commands::update_claude,
+            commands::list_claude_versions,
+            commands::install_claude_version,
+            commands::get_claude_previous_version,
+            commands::rollback_claude,
+            commands::get_claude_installations,
+            commands::migrate_claude_to_native,
+            commands::inspect_offline_package,
+            commands::install_claude_offline,
+            commands::prepare_offline_bundle,
+            commands::select_offline_package,
             commands::install_gitbash,
             commands::update_gitbash,
+            commands::get_npm_prefix_status,
+            commands::configure_npm_user_prefix,
+            commands::cleanup,
+            commands::cancel_install_job,
+            commands::list_install_jobs,
             commands::launch_claude_code,
+            commands::list_sessions,
+            commands::get_session,
+            commands::get_session_hook_output,
+            commands::get_project_session_stats,
+            commands::write_terminal,
+            commands::resize_terminal,
+            commands::get_terminal_snapshot,
+            commands::close_terminal,
+            commands::list_terminals,
+            commands::get_logs,
+            commands::get_log_directory,
             commands::generate_powershell_command,
             commands::generate_cmd_command,
             commands::generate_bash_command,
             commands::get_platform,
+            commands::get_settings_file_version,
             commands::save_to_settings,
             commands::reset_settings,
             commands::open_settings_file,
@@ -41,6 +176,7 @@ pub fn run() {
             // Project management commands
             commands::get_projects,
             commands::get_project,
+            commands::get_project_snapshot,
             commands::create_project,
             commands::update_project,
             commands::delete_project,
@@ -50,13 +186,34 @@ pub fn run() {
             commands::generate_project_cmd_command,
             commands::generate_project_bash_command,
             commands::get_home_directory,
+            commands::validate_proxy_url,
+            commands::test_proxy_connectivity,
+            commands::detect_system_proxy,
+            commands::inspect_ca_bundle,
+            commands::select_ca_bundle_file,
             commands::update_projects_order,
             commands::update_pinned_order,
             commands::toggle_project_pinned,
+            commands::create_project_shortcut,
+            commands::remove_project_shortcut,
+            // Deep link commands
+            commands::get_pending_deep_links,
+            commands::confirm_deep_link,
+            commands::dismiss_deep_link,
+            // Launcher settings commands
+            commands::get_launcher_settings,
+            commands::get_launcher_settings_snapshot,
+            commands::save_launcher_settings,
+            commands::get_npm_registry,
             // Onboarding commands
             commands::get_onboarding_status,
             commands::set_onboarding_completed,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while running tauri application")
+        .run(|_app, event| {
+            if let tauri::RunEvent::Exit = event {
+                SingleInstance::release();
+            }
+        });
 }
@@ -2,5 +2,10 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 fn main() {
+    // Subcommands (`list`, `launch`, ...) run without opening the GUI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(code) = claude_code_launcher_tauri_lib::cli::run(&args) {
+        std::process::exit(code);
+    }
     claude_code_launcher_tauri_lib::run()
 }
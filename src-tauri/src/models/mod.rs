@@ -1,3 +1,5 @@
 pub mod project;
+pub mod settings;
 
 pub use project::*;
+pub use settings::*;
@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::settings::CaBundleConfig;
+
 fn default_skip_permissions() -> bool {
     true
 }
@@ -8,13 +10,38 @@ fn default_mode() -> String {
     "claude".to_string()
 }
 
+fn default_hook_timeout() -> u64 {
+    60
+}
+
+/// A shell command run before launch or after Claude Code exits, in the project's
+/// working directory and with the same environment as Claude Code
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaunchHook {
+    pub command: String,
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_secs: u64,               // The hook is killed after this many seconds
+    #[serde(default)]
+    pub abort_on_failure: bool,          // Pre-launch: do not start Claude Code; post-exit: skip the remaining hooks
+}
+
 /// Project-specific configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProjectConfig {
     #[serde(default = "default_mode")]
     pub mode: String,                    // "claude" or "custom"
     #[serde(default)]
-    pub proxy: String,                   // HTTP/HTTPS proxy for Claude mode
+    pub proxy: String,                   // HTTP proxy (also used for HTTPS unless `https_proxy` is set), without credentials
+    #[serde(default)]
+    pub https_proxy: String,             // HTTPS proxy; empty means same as `proxy`
+    #[serde(default)]
+    pub no_proxy: String,                // Comma-separated NO_PROXY list
+    #[serde(default)]
+    pub inherit_system_proxy: bool,      // Fill unset proxy values from the system settings
+    #[serde(default)]
+    pub proxy_username: String,          // Proxy credentials, injected into the URL only at launch
+    #[serde(default)]
+    pub proxy_password: String,          // Kept in the OS keychain; empty in config.json
     #[serde(default)]
     pub model: String,                   // Model name for custom mode
     #[serde(default)]
@@ -23,6 +50,12 @@ pub struct ProjectConfig {
     pub token: String,                   // API token (Base64 encoded in storage)
     #[serde(default = "default_skip_permissions")]
     pub skip_permissions: bool,          // Skip permissions flag
+    #[serde(default)]
+    pub ca_bundle: CaBundleConfig,       // Overrides the global CA bundle when its path is set
+    #[serde(default)]
+    pub pre_launch_hooks: Vec<LaunchHook>,  // Run in order before `claude`
+    #[serde(default)]
+    pub post_exit_hooks: Vec<LaunchHook>,   // Run in order after `claude` exits
 }
 
 impl Default for ProjectConfig {
@@ -30,10 +63,18 @@ impl Default for ProjectConfig {
         Self {
             mode: "claude".to_string(),
             proxy: String::new(),
+            https_proxy: String::new(),
+            no_proxy: String::new(),
+            inherit_system_proxy: false,
+            proxy_username: String::new(),
+            proxy_password: String::new(),
             model: "qwen3-coder-480b-a35b".to_string(),
             base_url: "http://litellm.uattest.weoa.com".to_string(),
             token: String::new(),
             skip_permissions: true,
+            ca_bundle: CaBundleConfig::default(),
+            pre_launch_hooks: Vec::new(),
+            post_exit_hooks: Vec::new(),
         }
     }
 }
@@ -56,6 +97,8 @@ pub struct Project {
     pub pinned_at: Option<u64>,          // Timestamp when pinned (for sorting pinned projects)
     #[serde(default)]
     pub sort_order: u32,                 // Sort order for non-pinned projects (lower = earlier)
+    #[serde(default)]
+    pub shortcut_path: Option<String>,   // Desktop shortcut / launcher entry created for this project
 }
 
 impl Project {
@@ -77,6 +120,7 @@ impl Project {
             is_pinned: false,
             pinned_at: None,
             sort_order: 0,
+            shortcut_path: None,
         }
     }
 
@@ -98,6 +142,7 @@ impl Project {
             is_pinned: false,
             pinned_at: None,
             sort_order,
+            shortcut_path: None,
         }
     }
 
@@ -130,6 +175,15 @@ pub struct UpdateProjectInput {
     pub working_directory: Option<String>,
     pub config: Option<ProjectConfig>,
     pub is_pinned: Option<bool>,
+    #[serde(default)]
+    pub version: Option<String>,         // ProjectSnapshot::version the edit is based on; the update is refused if the project changed since
+}
+
+/// A project as loaded into the edit form, with the version its save is checked against
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectSnapshot {
+    pub project: Project,
+    pub version: String,
 }
 
 /// Input for updating project order (batch)
@@ -146,6 +200,27 @@ pub struct PinnedOrderItem {
     pub pinned_at: u64,
 }
 
+/// Current format of project export files
+pub const PROJECT_EXPORT_VERSION: u32 = 1;
+
+/// One project in an export file; IDs and timestamps are not carried over
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedProject {
+    pub name: String,
+    pub working_directory: String,
+    pub config: ProjectConfig,
+    #[serde(default)]
+    pub is_pinned: bool,
+}
+
+/// Projects moved between machines with `export`/`import`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectExport {
+    pub format_version: u32,
+    pub exported_at: u64,                // Unix timestamp
+    pub projects: Vec<ExportedProject>,
+}
+
 /// Generate a simple UUID v4
 fn uuid_v4() -> String {
     use std::time::{SystemTime, UNIX_EPOCH};
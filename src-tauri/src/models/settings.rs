@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+
+fn default_update_check_ttl_secs() -> u64 {
+    6 * 60 * 60
+}
+
+/// A PEM bundle of extra CA certificates, e.g. for a TLS-inspecting proxy
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CaBundleConfig {
+    #[serde(default)]
+    pub path: String,                    // PEM file; empty = not set
+    #[serde(default)]
+    pub set_ssl_cert_file: bool,         // Also export SSL_CERT_FILE (replaces the default trust store, so only for complete bundles)
+}
+
+/// Launcher-wide settings that are not tied to a single project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LauncherSettings {
+    #[serde(default)]
+    pub npm_registry: String,            // Custom npm registry URL (empty = auto-detect from env/.npmrc)
+    #[serde(default = "default_update_check_ttl_secs")]
+    pub update_check_ttl_secs: u64,      // How long a cached latest-version lookup stays fresh
+    #[serde(default)]
+    pub offline_mode: bool,              // Skip all network lookups and use cached results only
+    #[serde(default)]
+    pub claude_pinned_version: Option<String>, // Hold Claude Code at this version instead of tracking latest
+    #[serde(default)]
+    pub in_app_terminal: bool,           // Run claude in a launcher tab instead of an external terminal window
+    #[serde(default)]
+    pub ca_bundle: CaBundleConfig,       // Global CA bundle; also used for the launcher's own HTTPS requests
+    #[serde(default)]
+    pub hide_to_tray_on_close: bool,     // Closing the window hides it to the tray instead of quitting
+}
+
+impl Default for LauncherSettings {
+    fn default() -> Self {
+        Self {
+            npm_registry: String::new(),
+            update_check_ttl_secs: default_update_check_ttl_secs(),
+            offline_mode: false,
+            claude_pinned_version: None,
+            in_app_terminal: false,
+            ca_bundle: CaBundleConfig::default(),
+            hide_to_tray_on_close: false,
+        }
+    }
+}
+
+/// Launcher settings as loaded into the UI, with the version their save is checked against
+#[derive(Debug, Clone, Serialize)]
+pub struct LauncherSettingsSnapshot {
+    pub settings: LauncherSettings,
+    pub version: String,
+}
@@ -0,0 +1,119 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use x509_parser::pem::Pem;
+
+use crate::models::{CaBundleConfig, ProjectConfig};
+use super::config_storage::ConfigStorage;
+
+/// One certificate from a CA bundle, for display
+#[derive(Debug, Clone, Serialize)]
+pub struct CertificateInfo {
+    pub subject: String,
+    pub issuer: String,
+    pub not_before: String,              // RFC 3339
+    pub not_after: String,               // RFC 3339
+    pub expired: bool,
+    pub is_ca: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CaBundleInfo {
+    pub path: String,
+    pub certificates: Vec<CertificateInfo>,
+}
+
+pub struct CaBundle;
+
+impl CaBundle {
+    fn format_time(timestamp: i64) -> String {
+        chrono::DateTime::from_timestamp(timestamp, 0)
+            .map(|t| t.to_rfc3339_opts(chrono::SecondsFormat::Secs, true))
+            .unwrap_or_default()
+    }
+
+    /// DER blobs of every CERTIFICATE block in a PEM file
+    fn read_der(path: &Path) -> Result<Vec<Vec<u8>>, String> {
+        let data = std::fs::read(path).map_err(|e| format!("无法读取证书文件: {}", e))?;
+        let mut certs = Vec::new();
+        for pem in Pem::iter_from_buffer(&data) {
+            let pem = pem.map_err(|e| format!("证书文件不是有效的 PEM 格式: {}", e))?;
+            if pem.label == "CERTIFICATE" || pem.label == "TRUSTED CERTIFICATE" {
+                certs.push(pem.contents);
+            }
+        }
+        if certs.is_empty() {
+            return Err("证书文件中没有找到 CERTIFICATE 块".to_string());
+        }
+        Ok(certs)
+    }
+
+    /// Parse a PEM bundle and list its certificates; fails if any certificate is malformed
+    pub fn inspect(path: &str) -> Result<CaBundleInfo, String> {
+        let path = path.trim();
+        if path.is_empty() {
+            return Err("请选择证书文件".to_string());
+        }
+
+        let now = chrono::Utc::now().timestamp();
+        let mut certificates = Vec::new();
+        for (index, der) in Self::read_der(Path::new(path))?.iter().enumerate() {
+            let (_, cert) = x509_parser::parse_x509_certificate(der)
+                .map_err(|e| format!("第 {} 个证书无法解析: {}", index + 1, e))?;
+            let validity = cert.validity();
+            certificates.push(CertificateInfo {
+                subject: cert.subject().to_string(),
+                issuer: cert.issuer().to_string(),
+                not_before: Self::format_time(validity.not_before.timestamp()),
+                not_after: Self::format_time(validity.not_after.timestamp()),
+                expired: validity.not_after.timestamp() < now,
+                is_ca: cert.is_ca(),
+            });
+        }
+
+        Ok(CaBundleInfo {
+            path: path.to_string(),
+            certificates,
+        })
+    }
+
+    /// The project's bundle if it sets one, otherwise the global bundle
+    pub fn effective(project: Option<&ProjectConfig>) -> Option<CaBundleConfig> {
+        if let Some(config) = project {
+            if !config.ca_bundle.path.trim().is_empty() {
+                return Some(config.ca_bundle.clone());
+            }
+        }
+        ConfigStorage::get_launcher_settings()
+            .ok()
+            .map(|settings| settings.ca_bundle)
+            .filter(|bundle| !bundle.path.trim().is_empty())
+    }
+
+    /// Export the bundle to a launch config as NODE_EXTRA_CA_CERTS (and SSL_CERT_FILE if enabled)
+    pub fn apply(bundle: &CaBundleConfig, config: &mut HashMap<String, String>) {
+        let path = bundle.path.trim();
+        if path.is_empty() {
+            return;
+        }
+        config.insert("NODE_EXTRA_CA_CERTS".to_string(), path.to_string());
+        if bundle.set_ssl_cert_file {
+            config.insert("SSL_CERT_FILE".to_string(), path.to_string());
+        }
+    }
+
+    /// Trust the bundle's certificates in a `reqwest` client in addition to the built-in roots
+    pub fn configure_client(
+        mut builder: reqwest::ClientBuilder,
+        bundle: Option<&CaBundleConfig>,
+    ) -> Result<reqwest::ClientBuilder, String> {
+        let Some(bundle) = bundle.filter(|b| !b.path.trim().is_empty()) else {
+            return Ok(builder);
+        };
+        for der in Self::read_der(Path::new(bundle.path.trim()))? {
+            let cert = reqwest::Certificate::from_der(&der).map_err(|e| format!("无法加载证书: {}", e))?;
+            builder = builder.add_root_certificate(cert);
+        }
+        Ok(builder)
+    }
+}
@@ -0,0 +1,113 @@
+use serde::Serialize;
+use std::path::PathBuf;
+
+use super::dependency_checker::{DependencyChecker, InstallMethod};
+use super::environment::Environment;
+use super::npm_prefix::NpmPrefix;
+use super::npm_registry::NpmRegistry;
+
+/// Standalone installer scripts published by Anthropic
+pub const INSTALL_SH_URL: &str = "https://claude.ai/install.sh";
+pub const INSTALL_PS1_URL: &str = "https://claude.ai/install.ps1";
+
+/// Release bucket the native installer and `claude update` read from; `<url>/latest` is a bare version
+const RELEASES_URL: &str = "https://storage.googleapis.com/claude-code-dist-86c565f3-f756-42ad-8dfa-d59b1c096819/claude-code-releases";
+
+/// Every Claude Code installation found on this machine, not just the one that wins on PATH
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaudeInstallations {
+    pub active_method: Option<InstallMethod>, // Method of the `claude` that PATH resolves to
+    pub active_path: Option<String>,
+    pub npm_path: Option<String>,        // Global npm package shim, if installed
+    pub native_path: Option<String>,     // Native installer binary, if installed
+    pub can_migrate: bool,               // npm install present and no native one yet
+}
+
+pub struct ClaudeNative;
+
+impl ClaudeNative {
+    /// `~/.local/bin` on every platform (`%USERPROFILE%\.local\bin` on Windows)
+    pub fn bin_dir() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| home.join(".local").join("bin"))
+    }
+
+    pub fn binary_path() -> Option<PathBuf> {
+        #[cfg(windows)]
+        let name = "claude.exe";
+        #[cfg(not(windows))]
+        let name = "claude";
+
+        Self::bin_dir()
+            .map(|dir| dir.join(name))
+            .filter(|path| path.is_file())
+    }
+
+    /// The npm shim lives in the bin dir of the global prefix
+    fn npm_binary_path() -> Option<PathBuf> {
+        #[cfg(windows)]
+        let name = "claude.cmd";
+        #[cfg(not(windows))]
+        let name = "claude";
+
+        NpmPrefix::current_prefix()
+            .map(|prefix| NpmPrefix::bin_dir(&prefix).join(name))
+            .filter(|path| path.is_file())
+    }
+
+    pub fn installations() -> ClaudeInstallations {
+        let active_path = DependencyChecker::resolve_binary("claude");
+        let npm_path = Self::npm_binary_path();
+        let native_path = Self::binary_path();
+
+        ClaudeInstallations {
+            active_method: active_path.as_deref().map(DependencyChecker::detect_install_method),
+            active_path: active_path.map(|p| p.to_string_lossy().to_string()),
+            can_migrate: npm_path.is_some() && native_path.is_none(),
+            npm_path: npm_path.map(|p| p.to_string_lossy().to_string()),
+            native_path: native_path.map(|p| p.to_string_lossy().to_string()),
+        }
+    }
+
+    /// Persist `~/.local/bin` on the user PATH so terminals opened later find the native `claude`
+    pub fn ensure_on_path() -> Result<(), String> {
+        let Some(bin_dir) = Self::bin_dir().filter(|dir| dir.is_dir()) else {
+            return Ok(());
+        };
+        if Environment::user_path_contains(&bin_dir) {
+            return Ok(());
+        }
+        // Most Linux distributions already add ~/.local/bin from ~/.profile
+        #[cfg(not(windows))]
+        if std::env::var_os("PATH").is_some_and(|path| std::env::split_paths(&path).any(|p| p == bin_dir)) {
+            return Ok(());
+        }
+        Environment::add_to_user_path(&bin_dir)
+    }
+
+    /// Latest version on the native release channel, which can lag or lead the npm `latest` tag
+    pub async fn fetch_latest_version() -> Result<String, String> {
+        let client = NpmRegistry::http_client()?;
+        let response = client
+            .get(format!("{}/latest", RELEASES_URL))
+            .send()
+            .await
+            .map_err(|e| format!("无法连接 Claude Code 发布服务器: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Claude Code 发布服务器返回错误状态 {}", response.status()));
+        }
+
+        let version = response
+            .text()
+            .await
+            .map_err(|e| format!("无法读取 Claude Code 发布服务器响应: {}", e))?
+            .trim()
+            .to_string();
+
+        if NpmRegistry::is_valid_version(&version) {
+            Ok(version)
+        } else {
+            Err(format!("Claude Code 发布服务器返回了无效的版本号: {}", version))
+        }
+    }
+}
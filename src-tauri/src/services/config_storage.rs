@@ -1,8 +1,24 @@
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
 use base64::{Engine as _, engine::general_purpose};
-use crate::models::{Project, ProjectConfig, CreateProjectInput, UpdateProjectInput, ProjectOrderItem, PinnedOrderItem};
+use crate::models::{Project, ProjectConfig, ProjectSnapshot, CreateProjectInput, UpdateProjectInput, ProjectOrderItem, PinnedOrderItem, LauncherSettings, LauncherSettingsSnapshot, CaBundleConfig, LaunchHook, ExportedProject, ProjectExport, PROJECT_EXPORT_VERSION};
+use super::npm_registry::NpmRegistry;
+use super::proxy::Proxy;
+use super::ca_bundle::CaBundle;
+use super::hooks::MAX_HOOK_TIMEOUT_SECS;
+use super::logger::Logger;
+use super::shortcut::Shortcuts;
+use super::file_watch::FileWatch;
+use super::keychain::Keychain;
+
+type ChangeListener = Arc<dyn Fn() + Send + Sync>;
+
+fn change_listeners() -> &'static Mutex<Vec<ChangeListener>> {
+    static LISTENERS: OnceLock<Mutex<Vec<ChangeListener>>> = OnceLock::new();
+    LISTENERS.get_or_init(|| Mutex::new(Vec::new()))
+}
 
 fn default_skip_permissions() -> bool {
     true
@@ -18,6 +34,8 @@ pub struct AppConfig {
     pub token: String,
     #[serde(default = "default_skip_permissions")]
     pub skip_permissions: bool,
+    #[serde(default)]
+    pub file_version: String,           // Version of the default project when loaded; saving is refused if it changed since
 }
 
 impl Default for AppConfig {
@@ -29,6 +47,7 @@ impl Default for AppConfig {
             base_url: "http://litellm.uattest.weoa.com".to_string(),
             token: String::new(),
             skip_permissions: true,
+            file_version: String::new(),
         }
     }
 }
@@ -40,6 +59,12 @@ pub struct AppConfigV2 {
     pub projects: Vec<Project>,
     #[serde(default)]
     pub has_seen_onboarding: bool,
+    #[serde(default)]
+    pub settings: LauncherSettings,
+    #[serde(default)]
+    pub claude_previous_version: Option<String>, // Version replaced by the last Claude Code update, for rollback
+    #[serde(skip)]
+    pub file_version: String,                    // FileWatch version of config.json when loaded; saving is refused if it changed since
 }
 
 impl Default for AppConfigV2 {
@@ -48,6 +73,9 @@ impl Default for AppConfigV2 {
             version: 2,
             projects: vec![Project::default_project()],
             has_seen_onboarding: false,
+            settings: LauncherSettings::default(),
+            claude_previous_version: None,
+            file_version: String::new(),
         }
     }
 }
@@ -62,7 +90,7 @@ struct RawConfig {
 pub struct ConfigStorage;
 
 impl ConfigStorage {
-    fn get_config_path() -> Result<PathBuf, String> {
+    pub fn get_config_path() -> Result<PathBuf, String> {
         let config_dir = dirs::config_dir()
             .ok_or("无法获取配置目录")?
             .join("ClaudeCodeLauncher");
@@ -81,14 +109,23 @@ impl ConfigStorage {
             .map(|p| p.to_string_lossy().to_string())
             .unwrap_or_else(|| "~".to_string());
 
-        let project_config = ProjectConfig {
+        let mut project_config = ProjectConfig {
             mode: v1_config.mode,
             proxy: v1_config.proxy,
+            https_proxy: String::new(),
+            no_proxy: String::new(),
+            inherit_system_proxy: false,
+            proxy_username: String::new(),
+            proxy_password: String::new(),
             model: v1_config.model,
             base_url: v1_config.base_url,
             token: v1_config.token, // Already decoded at this point
             skip_permissions: v1_config.skip_permissions,
+            ca_bundle: CaBundleConfig::default(),
+            pre_launch_hooks: Vec::new(),
+            post_exit_hooks: Vec::new(),
         };
+        Self::split_proxy_credentials(&mut project_config);
 
         let default_project = Project::new(
             "默认项目".to_string(),
@@ -101,27 +138,123 @@ impl ConfigStorage {
             version: 2,
             projects: vec![default_project],
             has_seen_onboarding: false,
+            settings: LauncherSettings::default(),
+            claude_previous_version: None,
+            file_version: String::new(),
         }
     }
 
-    /// Encode token in project config for storage
+    /// Encode the token for storage and move the proxy password into the OS keychain
     fn encode_project_token(project: &mut Project) {
         if !project.config.token.is_empty() {
             project.config.token = general_purpose::STANDARD.encode(&project.config.token);
         }
+        let password = std::mem::take(&mut project.config.proxy_password);
+        let account = Keychain::proxy_password_account(&project.id);
+        // Only a changed password reaches the store, so saves that do not touch it never depend on it
+        if Keychain::get(&account).unwrap_or_default() == password {
+            return;
+        }
+        if let Err(e) = Keychain::set(&account, &password) {
+            // Without a reachable store, keep the password where older versions kept it rather than refusing every save
+            Logger::warn(
+                "keychain",
+                format!("{}；项目 {} 的代理密码仍以 Base64 形式保存在 config.json 中", e, project.name),
+            );
+            project.config.proxy_password = general_purpose::STANDARD.encode(&password);
+        }
+    }
+
+    fn decode_secret(value: &mut String) {
+        if !value.is_empty() {
+            if let Ok(decoded) = general_purpose::STANDARD.decode(&*value) {
+                if let Ok(decoded_str) = String::from_utf8(decoded) {
+                    *value = decoded_str;
+                }
+            }
+        }
     }
 
-    /// Decode token in project config after loading
+    /// Decode the token and fetch the proxy password from the OS keychain after loading
     fn decode_project_token(project: &mut Project) {
-        if !project.config.token.is_empty() {
-            if let Ok(decoded) = general_purpose::STANDARD.decode(&project.config.token) {
-                if let Ok(token_str) = String::from_utf8(decoded) {
-                    project.config.token = token_str;
+        Self::decode_secret(&mut project.config.token);
+        if project.config.proxy_password.is_empty() {
+            project.config.proxy_password = Keychain::get(&Keychain::proxy_password_account(&project.id)).unwrap_or_default();
+        } else {
+            // Older versions kept it base64-encoded in config.json; the next save moves it to the keychain
+            Self::decode_secret(&mut project.config.proxy_password);
+        }
+        // Configs from older versions kept credentials inside the proxy URL
+        Self::split_proxy_credentials(&mut project.config);
+    }
+
+    /// Move `user:password@` out of the proxy URLs into the dedicated credential fields
+    fn split_proxy_credentials(config: &mut ProjectConfig) {
+        for url in [&mut config.proxy, &mut config.https_proxy] {
+            if !url.contains('@') {
+                continue;
+            }
+            if let Ok(info) = Proxy::validate(url) {
+                if !info.username.is_empty() || !info.password.is_empty() {
+                    *url = info.url;
+                    config.proxy_username = info.username;
+                    config.proxy_password = info.password;
                 }
             }
         }
     }
 
+    /// Validate the proxy URLs and NO_PROXY list and normalize inline credentials before a project is saved
+    fn prepare_project_config(mut config: ProjectConfig) -> Result<ProjectConfig, String> {
+        // Both proxies share one set of credentials
+        for url in [&mut config.proxy, &mut config.https_proxy] {
+            *url = url.trim().to_string();
+            if url.is_empty() {
+                continue;
+            }
+            let info = Proxy::validate(url)?;
+            // Credentials typed into the URL win over stale ones in the dedicated fields
+            if !info.username.is_empty() || !info.password.is_empty() {
+                config.proxy_username = info.username;
+                config.proxy_password = info.password;
+            }
+            *url = info.url;
+        }
+        config.no_proxy = Proxy::normalize_no_proxy(&config.no_proxy)?;
+        Self::prepare_ca_bundle(&mut config.ca_bundle)?;
+        Self::prepare_hooks(&mut config.pre_launch_hooks)?;
+        Self::prepare_hooks(&mut config.post_exit_hooks)?;
+        Ok(config)
+    }
+
+    /// Drop blank hooks; the launch scripts run each hook as a single line
+    fn prepare_hooks(hooks: &mut Vec<LaunchHook>) -> Result<(), String> {
+        hooks.retain_mut(|hook| {
+            hook.command = hook.command.trim().to_string();
+            !hook.command.is_empty()
+        });
+        for hook in hooks.iter() {
+            if hook.command.contains('\n') || hook.command.contains('\r') {
+                return Err(format!("钩子命令不能包含换行: {}", hook.command));
+            }
+            if hook.timeout_secs == 0 || hook.timeout_secs > MAX_HOOK_TIMEOUT_SECS {
+                return Err(format!("钩子超时时间应在 1 到 {} 秒之间: {}", MAX_HOOK_TIMEOUT_SECS, hook.command));
+            }
+        }
+        Ok(())
+    }
+
+    /// A configured CA bundle must exist and parse, or every launch would fail TLS
+    fn prepare_ca_bundle(bundle: &mut CaBundleConfig) -> Result<(), String> {
+        bundle.path = bundle.path.trim().to_string();
+        if bundle.path.is_empty() {
+            bundle.set_ssl_cert_file = false;
+        } else {
+            CaBundle::inspect(&bundle.path)?;
+        }
+        Ok(())
+    }
+
     /// Load v2 config, migrating from v1 if necessary
     pub fn load_config_v2() -> Result<AppConfigV2, String> {
         let config_path = Self::get_config_path()?;
@@ -132,6 +265,7 @@ impl ConfigStorage {
 
         let content = fs::read_to_string(&config_path)
             .map_err(|e| format!("无法读取配置文件: {}", e))?;
+        let file_version = FileWatch::version_of(Some(content.as_bytes()));
 
         // Detect version
         let raw: RawConfig = serde_json::from_str(&content)
@@ -146,6 +280,7 @@ impl ConfigStorage {
             for project in &mut config.projects {
                 Self::decode_project_token(project);
             }
+            config.file_version = file_version;
 
             Ok(config)
         } else {
@@ -162,10 +297,12 @@ impl ConfigStorage {
                 }
             }
 
-            let config = Self::migrate_v1_to_v2(v1_config);
+            let mut config = Self::migrate_v1_to_v2(v1_config);
+            config.file_version = file_version;
 
             // Save migrated config
             Self::save_config_v2(&config)?;
+            config.file_version = FileWatch::version(&config_path);
 
             Ok(config)
         }
@@ -184,12 +321,29 @@ impl ConfigStorage {
         let json_string = serde_json::to_string_pretty(&config_to_save)
             .map_err(|e| format!("无法序列化配置: {}", e))?;
 
-        fs::write(&config_path, json_string)
-            .map_err(|e| format!("无法写入配置文件: {}", e))?;
+        // Refuse to overwrite edits made since `config` was loaded
+        FileWatch::write(&config_path, json_string.as_bytes(), &config.file_version)?;
 
+        Self::notify_changed();
         Ok(())
     }
 
+    /// Run `listener` after every change of the config file, e.g. to refresh the tray menu
+    pub fn on_change(listener: impl Fn() + Send + Sync + 'static) {
+        if let Ok(mut listeners) = change_listeners().lock() {
+            listeners.push(Arc::new(listener));
+        }
+    }
+
+    /// Also called by the file watcher when the file was changed by someone else
+    pub fn notify_changed() {
+        // Listeners may read the config again, so call them without holding the lock
+        let listeners = change_listeners().lock().map(|l| l.clone()).unwrap_or_default();
+        for listener in listeners {
+            listener();
+        }
+    }
+
     /// Get all projects
     pub fn get_projects() -> Result<Vec<Project>, String> {
         let config = Self::load_config_v2()?;
@@ -205,6 +359,121 @@ impl ConfigStorage {
             .ok_or_else(|| format!("项目不存在: {}", id))
     }
 
+    /// Version of the fields the edit form writes; launch bookkeeping and reordering do not change it
+    fn project_version(project: &Project) -> String {
+        let edited = serde_json::json!({
+            "name": project.name,
+            "working_directory": project.working_directory,
+            "config": project.config,
+            "is_pinned": project.is_pinned,
+        });
+        FileWatch::version_of(Some(edited.to_string().as_bytes()))
+    }
+
+    /// A project together with the version `update_project` checks the edit against
+    pub fn get_project_snapshot(id: &str) -> Result<ProjectSnapshot, String> {
+        let project = Self::get_project(id)?;
+        Ok(ProjectSnapshot { version: Self::project_version(&project), project })
+    }
+
+    /// Find a project by ID, or else by its name (case-insensitive)
+    pub fn find_project(name_or_id: &str) -> Result<Project, String> {
+        let name_or_id = name_or_id.trim();
+        let projects = Self::get_projects()?;
+        if let Some(project) = projects.iter().find(|p| p.id == name_or_id) {
+            return Ok(project.clone());
+        }
+
+        let matches: Vec<&Project> = projects
+            .iter()
+            .filter(|p| p.name.to_lowercase() == name_or_id.to_lowercase())
+            .collect();
+        match matches.as_slice() {
+            [project] => Ok((*project).clone()),
+            [] => Err(format!("项目不存在: {}", name_or_id)),
+            _ => Err(format!("有多个项目名为 {}，请改用项目 ID", name_or_id)),
+        }
+    }
+
+    /// Resolve an existing directory to the absolute form stored in projects
+    pub fn canonical_directory(path: &str) -> Result<String, String> {
+        let resolved = fs::canonicalize(path.trim()).map_err(|e| format!("目录不存在: {} ({})", path, e))?;
+        if !resolved.is_dir() {
+            return Err(format!("不是目录: {}", path));
+        }
+        // `canonicalize` returns `\\?\C:\…` on Windows, which the launch scripts cannot `cd` into
+        let resolved = resolved.to_string_lossy();
+        Ok(resolved.strip_prefix(r"\\?\").unwrap_or(&resolved).to_string())
+    }
+
+    /// The project whose working directory is `path`, if any
+    pub fn find_project_by_directory(path: &str) -> Result<Option<Project>, String> {
+        let target = Self::canonical_directory(path)?;
+        Ok(Self::get_projects()?.into_iter().find(|project| {
+            Self::canonical_directory(&project.working_directory).unwrap_or_else(|_| project.working_directory.clone()) == target
+        }))
+    }
+
+    /// All projects in export form; the token and proxy password are left out unless `with_secrets`
+    pub fn export_projects(with_secrets: bool) -> Result<ProjectExport, String> {
+        let projects = Self::get_projects()?
+            .into_iter()
+            .map(|project| {
+                let mut config = project.config;
+                if !with_secrets {
+                    config.token.clear();
+                    config.proxy_password.clear();
+                }
+                ExportedProject {
+                    name: project.name,
+                    working_directory: project.working_directory,
+                    config,
+                    is_pinned: project.is_pinned,
+                }
+            })
+            .collect();
+
+        Ok(ProjectExport {
+            format_version: PROJECT_EXPORT_VERSION,
+            exported_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs(),
+            projects,
+        })
+    }
+
+    /// Create the projects of an export; returns the created projects and the names
+    /// that were skipped because a project with that name already exists
+    pub fn import_projects(export: ProjectExport) -> Result<(Vec<Project>, Vec<String>), String> {
+        if export.format_version > PROJECT_EXPORT_VERSION {
+            return Err(format!("不支持的导出文件版本: {}，请升级启动器", export.format_version));
+        }
+
+        let existing: Vec<String> = Self::get_projects()?
+            .into_iter()
+            .map(|p| p.name.to_lowercase())
+            .collect();
+        let mut created = Vec::new();
+        let mut skipped = Vec::new();
+        for item in export.projects {
+            if existing.contains(&item.name.to_lowercase()) {
+                skipped.push(item.name);
+                continue;
+            }
+            let mut project = Self::create_project(CreateProjectInput {
+                name: item.name,
+                working_directory: item.working_directory,
+                config: item.config,
+            })?;
+            if item.is_pinned {
+                project = Self::toggle_project_pinned(&project.id, true)?;
+            }
+            created.push(project);
+        }
+        Ok((created, skipped))
+    }
+
     /// Create a new project
     pub fn create_project(input: CreateProjectInput) -> Result<Project, String> {
         let mut config = Self::load_config_v2()?;
@@ -220,7 +489,7 @@ impl ConfigStorage {
         let project = Project::new_with_sort_order(
             input.name,
             input.working_directory,
-            input.config,
+            Self::prepare_project_config(input.config)?,
             false, // New projects are not default
             max_order + 1,
         );
@@ -248,6 +517,13 @@ impl ConfigStorage {
             .find(|p| p.id == id)
             .ok_or_else(|| format!("项目不存在: {}", id))?;
 
+        if updates.version.is_some_and(|version| version != Self::project_version(project)) {
+            return Err(format!(
+                "项目 {} 在打开编辑后被其他程序修改，已取消保存以免覆盖这些修改，请重新加载后重试",
+                project.name
+            ));
+        }
+
         if let Some(name) = updates.name {
             project.name = name;
         }
@@ -255,7 +531,7 @@ impl ConfigStorage {
             project.working_directory = working_directory;
         }
         if let Some(new_config) = updates.config {
-            project.config = new_config;
+            project.config = Self::prepare_project_config(new_config)?;
         }
         if let Some(is_pinned) = updates.is_pinned {
             let now = std::time::SystemTime::now()
@@ -299,12 +575,48 @@ impl ConfigStorage {
             return Err("不能删除默认项目".to_string());
         }
 
+        // A leftover shortcut would only fail to launch, so it does not block the deletion
+        if let Err(e) = Shortcuts::remove(project) {
+            Logger::warn("config", e);
+        }
+
         config.projects.retain(|p| p.id != id);
         Self::save_config_v2(&config)?;
 
+        if let Err(e) = Keychain::set(&Keychain::proxy_password_account(id), "") {
+            Logger::warn("config", e);
+        }
         Ok(())
     }
 
+    /// Create the project's OS shortcut, replacing an earlier one, and remember where it is
+    pub fn create_project_shortcut(id: &str) -> Result<Project, String> {
+        let mut config = Self::load_config_v2()?;
+        let project = config.projects
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("项目不存在: {}", id))?;
+
+        project.shortcut_path = Some(Shortcuts::create(project)?);
+        let project = project.clone();
+        Self::save_config_v2(&config)?;
+        Ok(project)
+    }
+
+    pub fn remove_project_shortcut(id: &str) -> Result<Project, String> {
+        let mut config = Self::load_config_v2()?;
+        let project = config.projects
+            .iter_mut()
+            .find(|p| p.id == id)
+            .ok_or_else(|| format!("项目不存在: {}", id))?;
+
+        Shortcuts::remove(project)?;
+        project.shortcut_path = None;
+        let project = project.clone();
+        Self::save_config_v2(&config)?;
+        Ok(project)
+    }
+
     /// Update project's last launched timestamp
     pub fn update_project_launched(id: &str) -> Result<(), String> {
         let mut config = Self::load_config_v2()?;
@@ -363,6 +675,7 @@ impl ConfigStorage {
             working_directory: None,
             config: None,
             is_pinned: Some(is_pinned),
+            version: None,
         };
         Self::update_project(id, updates)
     }
@@ -380,22 +693,91 @@ impl ConfigStorage {
         Self::save_config_v2(&config)
     }
 
+    /// Get launcher-wide settings
+    pub fn get_launcher_settings() -> Result<LauncherSettings, String> {
+        let config = Self::load_config_v2()?;
+        Ok(config.settings)
+    }
+
+    /// Version of the launcher settings; project edits and launch bookkeeping do not change it
+    fn settings_version(settings: &LauncherSettings) -> String {
+        let json = serde_json::to_string(settings).unwrap_or_default();
+        FileWatch::version_of(Some(json.as_bytes()))
+    }
+
+    /// Launcher settings together with the version `save_launcher_settings` checks the save against
+    pub fn get_launcher_settings_snapshot() -> Result<LauncherSettingsSnapshot, String> {
+        let settings = Self::get_launcher_settings()?;
+        Ok(LauncherSettingsSnapshot { version: Self::settings_version(&settings), settings })
+    }
+
+    /// Save launcher-wide settings; `version` is the snapshot version the new settings are based on
+    pub fn save_launcher_settings(mut settings: LauncherSettings, version: Option<&str>) -> Result<(), String> {
+        settings.npm_registry = settings.npm_registry.trim().to_string();
+        Self::prepare_ca_bundle(&mut settings.ca_bundle)?;
+        let registry = &settings.npm_registry;
+        if !registry.is_empty() && !registry.starts_with("http://") && !registry.starts_with("https://") {
+            return Err(format!("npm 镜像地址必须以 http:// 或 https:// 开头: {}", registry));
+        }
+
+        settings.claude_pinned_version = settings
+            .claude_pinned_version
+            .map(|v| v.trim().trim_start_matches('v').to_string())
+            .filter(|v| !v.is_empty());
+        if let Some(ref pinned) = settings.claude_pinned_version {
+            if !NpmRegistry::is_valid_version(pinned) {
+                return Err(format!("无效的 Claude Code 版本号: {}", pinned));
+            }
+        }
+
+        let mut config = Self::load_config_v2()?;
+        if version.is_some_and(|version| version != Self::settings_version(&config.settings)) {
+            return Err("启动器设置在读取后被其他程序修改，已取消保存以免覆盖这些修改，请刷新后重试".to_string());
+        }
+        config.settings = settings;
+        Self::save_config_v2(&config)
+    }
+
+    /// Get the Claude Code version that the last update replaced
+    pub fn get_claude_previous_version() -> Result<Option<String>, String> {
+        let config = Self::load_config_v2()?;
+        Ok(config.claude_previous_version)
+    }
+
+    /// Remember the Claude Code version that an update replaced
+    pub fn set_claude_previous_version(version: Option<String>) -> Result<(), String> {
+        let mut config = Self::load_config_v2()?;
+        config.claude_previous_version = version;
+        Self::save_config_v2(&config)
+    }
+
     // ============ Legacy v1 API for backwards compatibility ============
 
     pub fn save_config(config: &AppConfig) -> Result<(), String> {
         // Convert to v2 and save
-        let mut v2_config = Self::load_config_v2().unwrap_or_default();
+        let mut v2_config = Self::load_config_v2()?;
 
         // Update default project with new config
         if let Some(default_project) = v2_config.projects.iter_mut().find(|p| p.is_default) {
-            default_project.config = ProjectConfig {
+            if !config.file_version.is_empty() && config.file_version != Self::project_version(default_project) {
+                return Err("默认项目在读取后被其他程序修改，已取消保存以免覆盖这些修改，请重新加载后重试".to_string());
+            }
+            default_project.config = Self::prepare_project_config(ProjectConfig {
                 mode: config.mode.clone(),
                 proxy: config.proxy.clone(),
+                https_proxy: String::new(),
+                no_proxy: String::new(),
+                inherit_system_proxy: false,
+                proxy_username: String::new(),
+                proxy_password: String::new(),
                 model: config.model.clone(),
                 base_url: config.base_url.clone(),
                 token: config.token.clone(),
                 skip_permissions: config.skip_permissions,
-            };
+                ca_bundle: CaBundleConfig::default(),
+                pre_launch_hooks: default_project.config.pre_launch_hooks.clone(),
+                post_exit_hooks: default_project.config.post_exit_hooks.clone(),
+            })?;
             default_project.updated_at = std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap_or_default()
@@ -415,13 +797,21 @@ impl ConfigStorage {
             .cloned()
             .unwrap_or_else(Project::default_project);
 
+        let file_version = Self::project_version(&default_project);
         Ok(AppConfig {
             mode: default_project.config.mode,
-            proxy: default_project.config.proxy,
+            // The legacy format has no credential fields
+            proxy: Proxy::with_credentials(
+                &default_project.config.proxy,
+                &default_project.config.proxy_username,
+                &default_project.config.proxy_password,
+            )
+            .unwrap_or(default_project.config.proxy),
             model: default_project.config.model,
             base_url: default_project.config.base_url,
             token: default_project.config.token,
             skip_permissions: default_project.config.skip_permissions,
+            file_version,
         })
     }
 }
@@ -0,0 +1,205 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use url::Url;
+
+use super::config_storage::ConfigStorage;
+use super::logger::{LogLevel, Logger};
+
+/// URL scheme registered for the app, e.g. `claude-launcher://launch?project=<id>`
+pub const DEEP_LINK_SCHEME: &str = "claude-launcher";
+
+/// Links waiting for confirmation; older ones are dropped beyond this
+const MAX_PENDING: usize = 10;
+
+/// What an external link asks the launcher to do
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DeepLinkAction {
+    /// `launch?project=<id or name>`
+    Launch {
+        project_id: String,
+        project_name: String,
+        working_directory: String,
+    },
+    /// `open?path=<dir>`; reuses the project already pointing at the directory, if any
+    Open {
+        path: String,
+        project_id: Option<String>,
+        project_name: Option<String>,
+    },
+}
+
+/// A received link; `action` is None and `error` is set when the link was rejected
+#[derive(Debug, Clone, Serialize)]
+pub struct DeepLinkRequest {
+    pub id: String,
+    pub url: String,
+    pub action: Option<DeepLinkAction>,
+    pub error: Option<String>,
+}
+
+fn pending() -> Result<MutexGuard<'static, Vec<DeepLinkRequest>>, String> {
+    static PENDING: OnceLock<Mutex<Vec<DeepLinkRequest>>> = OnceLock::new();
+    PENDING
+        .get_or_init(|| Mutex::new(Vec::new()))
+        .lock()
+        .map_err(|_| "链接请求列表被锁定".to_string())
+}
+
+/// Links never launch anything by themselves: they are parsed and validated here and
+/// kept until the user confirms or dismisses them in the UI.
+pub struct DeepLinks;
+
+impl DeepLinks {
+    /// Parse and validate a link against the current projects and file system
+    pub fn parse(link: &str) -> Result<DeepLinkAction, String> {
+        let url = Url::parse(link.trim()).map_err(|e| format!("无效的链接: {}", e))?;
+        if url.scheme() != DEEP_LINK_SCHEME {
+            return Err(format!("不支持的链接协议: {}", url.scheme()));
+        }
+
+        // `claude-launcher://launch?..` puts the action in the host, `claude-launcher:launch?..` in the path
+        let action = match url.host_str() {
+            Some(host) => host.to_string(),
+            None => url.path().trim_matches('/').to_string(),
+        };
+        let params: HashMap<String, String> = url.query_pairs().into_owned().collect();
+        let param = |name: &str| {
+            params
+                .get(name)
+                .map(|v| v.trim())
+                .filter(|v| !v.is_empty())
+                .ok_or(format!("链接缺少参数: {}", name))
+        };
+
+        match action.as_str() {
+            "launch" => {
+                let project = ConfigStorage::find_project(param("project")?)?;
+                Ok(DeepLinkAction::Launch {
+                    project_id: project.id,
+                    project_name: project.name,
+                    working_directory: project.working_directory,
+                })
+            }
+            "open" => {
+                let raw = param("path")?;
+                if !Path::new(raw).is_absolute() {
+                    return Err(format!("路径必须为绝对路径: {}", raw));
+                }
+                let path = ConfigStorage::canonical_directory(raw)?;
+                let project = ConfigStorage::find_project_by_directory(&path)?;
+                Ok(DeepLinkAction::Open {
+                    path,
+                    project_id: project.as_ref().map(|p| p.id.clone()),
+                    project_name: project.map(|p| p.name),
+                })
+            }
+            "" => Err("链接缺少操作 (launch 或 open)".to_string()),
+            other => Err(format!("未知的链接操作: {}", other)),
+        }
+    }
+
+    /// Record a received link; valid ones stay pending until confirmed or dismissed
+    pub fn receive(link: &str) -> DeepLinkRequest {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+        let (action, error) = match Self::parse(link) {
+            Ok(action) => (Some(action), None),
+            Err(e) => (None, Some(e)),
+        };
+        let mut request = DeepLinkRequest {
+            id: format!("link-{}", NEXT_ID.fetch_add(1, Ordering::SeqCst)),
+            url: link.to_string(),
+            action,
+            error,
+        };
+
+        Logger::log(LogLevel::Info, "deep_link", "deep link received", serde_json::json!({
+            "url": request.url,
+            "error": request.error,
+        }));
+
+        if request.action.is_some() {
+            match pending() {
+                Ok(mut list) => {
+                    list.push(request.clone());
+                    if list.len() > MAX_PENDING {
+                        let excess = list.len() - MAX_PENDING;
+                        list.drain(..excess);
+                    }
+                }
+                // A link that cannot be kept for confirmation is shown as rejected
+                Err(e) => {
+                    request.action = None;
+                    request.error = Some(e);
+                }
+            }
+        }
+        request
+    }
+
+    /// Links received before the UI was ready to show them
+    pub fn pending() -> Result<Vec<DeepLinkRequest>, String> {
+        Ok(pending()?.clone())
+    }
+
+    /// Remove a confirmed link and validate it again, as the project or directory may have changed
+    pub fn take(id: &str) -> Result<DeepLinkAction, String> {
+        let request = {
+            let mut list = pending()?;
+            let index = list.iter().position(|r| r.id == id).ok_or("链接请求不存在或已处理")?;
+            list.remove(index)
+        };
+        Self::parse(&request.url)
+    }
+
+    pub fn dismiss(id: &str) -> Result<(), String> {
+        pending()?.retain(|r| r.id != id);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_malformed_links() {
+        assert!(DeepLinks::parse("not a url").unwrap_err().starts_with("无效的链接"));
+        assert_eq!(
+            DeepLinks::parse("https://example.com/launch?project=x").unwrap_err(),
+            "不支持的链接协议: https"
+        );
+        assert_eq!(DeepLinks::parse("claude-launcher://").unwrap_err(), "链接缺少操作 (launch 或 open)");
+        assert_eq!(DeepLinks::parse("claude-launcher://delete?project=x").unwrap_err(), "未知的链接操作: delete");
+    }
+
+    #[test]
+    fn action_from_host_or_path() {
+        for link in ["claude-launcher://launch", "claude-launcher:launch", "claude-launcher:///launch?project=%20"] {
+            assert_eq!(DeepLinks::parse(link).unwrap_err(), "链接缺少参数: project", "{}", link);
+        }
+        assert_eq!(DeepLinks::parse(" claude-launcher://open?x=1 ").unwrap_err(), "链接缺少参数: path");
+    }
+
+    #[test]
+    fn open_requires_absolute_path() {
+        assert_eq!(
+            DeepLinks::parse("claude-launcher://open?path=code%2Fapp").unwrap_err(),
+            "路径必须为绝对路径: code/app"
+        );
+        assert_eq!(
+            DeepLinks::parse("claude-launcher://open?path=..%2Fapp").unwrap_err(),
+            "路径必须为绝对路径: ../app"
+        );
+    }
+
+    #[test]
+    fn unknown_request_cannot_be_confirmed() {
+        assert_eq!(DeepLinks::take("link-0").unwrap_err(), "链接请求不存在或已处理");
+        assert!(DeepLinks::dismiss("link-0").is_ok());
+    }
+}
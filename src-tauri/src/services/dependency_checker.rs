@@ -2,6 +2,12 @@ use serde::{Deserialize, Serialize};
 use std::process::Command;
 use regex::Regex;
 
+use super::npm_registry::{NpmRegistry, RegistryConfig, CLAUDE_PACKAGE};
+use super::update_cache::UpdateCache;
+use super::config_storage::ConfigStorage;
+use super::npm_prefix::NpmPrefix;
+use super::claude_native::ClaudeNative;
+
 #[cfg(target_os = "macos")]
 use std::env;
 
@@ -58,75 +64,84 @@ fn get_macos_extended_path() -> String {
     all_paths.join(":")
 }
 
+/// How a dependency ended up on this machine, inferred from its resolved binary path
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallMethod {
+    Npm,                                 // Global npm package
+    Native,                              // Claude Code native installer (~/.local/bin)
+    Homebrew,
+    VersionManager,                      // nvm / fnm / volta / n
+    PackageManager,                      // apt / dnf / pacman / zypper
+    SystemInstaller,                     // MSI / winget / Xcode Command Line Tools
+    Unknown,
+}
+
+/// Why a dependency check did not yield a usable version
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum DependencyError {
+    /// The command is not on PATH
+    NotFound { command: String },
+    /// The command ran but exited unsuccessfully
+    CommandFailed {
+        command: String,
+        exit_code: Option<i32>,
+        stderr: String,
+    },
+    /// The command could not be started for a reason other than "not found"
+    SpawnFailed { command: String, message: String },
+    /// The command succeeded but no version number could be found in its output
+    VersionUnparseable { raw_output: String },
+    /// The detected version is older than the launcher's minimum
+    BelowMinimum { version: String, minimum: String },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DependencyStatus {
     pub installed: bool,
     pub version: Option<String>,
     pub meets_requirement: bool,
     pub latest_version: Option<String>,
     pub update_available: bool,
-    pub error: Option<String>,
+    pub error: Option<DependencyError>,
+    pub path: Option<String>,            // Resolved binary path
+    pub install_method: Option<InstallMethod>,
+    pub last_checked_at: Option<u64>,    // When latest_version was fetched from its source
+    pub latest_version_stale: bool,      // latest_version came from an expired cache or offline mode
+    pub pinned_version: Option<String>,  // Update checks are suppressed while a version pin is set
 }
 
+/// Maximum number of characters of command output kept in error details
+const OUTPUT_EXCERPT_LEN: usize = 500;
+
 pub struct DependencyChecker;
 
 impl DependencyChecker {
     pub fn check_nodejs() -> DependencyStatus {
         #[cfg(target_os = "macos")]
-        {
+        let output = {
             // On macOS, use extended PATH to find node
             let extended_path = get_macos_extended_path();
-            let output = Command::new("sh")
+            Command::new("sh")
                 .args(&["-c", &format!("PATH='{}' node --version", extended_path)])
-                .output();
-
-            match output {
-                Ok(out) if out.status.success() => {
-                    let stdout = String::from_utf8_lossy(&out.stdout);
-                    if let Ok(re) = Regex::new(r"v(\d+\.\d+\.\d+)") {
-                        if let Some(caps) = re.captures(&stdout) {
-                            let version = caps.get(1).map(|m| m.as_str().to_string());
-                            let meets_requirement = if let Some(ref v) = version {
-                                Self::compare_versions(v, "18.0.0")
-                            } else {
-                                false
-                            };
-                            return DependencyStatus {
-                                installed: true,
-                                version,
-                                meets_requirement,
-                                latest_version: None,
-                                update_available: false,
-                                error: None,
-                            };
-                        }
-                    }
-                    DependencyStatus {
-                        installed: true,
-                        version: None,
-                        meets_requirement: false,
-                        latest_version: None,
-                        update_available: false,
-                        error: Some("无法解析版本号".to_string()),
-                    }
-                }
-                _ => DependencyStatus {
-                    installed: false,
-                    version: None,
-                    meets_requirement: false,
-                    latest_version: None,
-                    update_available: false,
-                    error: Some("Node.js not found".to_string()),
-                },
-            }
-        }
+                .output()
+        };
 
         #[cfg(not(target_os = "macos"))]
-        Self::check_dependency("node", &["--version"], r"v(\d+\.\d+\.\d+)", Some("18.0.0"))
+        let output = Command::new("node")
+            .arg("--version")
+            .output();
+
+        Self::evaluate("node", output, &[r"v(\d+\.\d+\.\d+)"], Some("18.0.0"))
     }
 
     pub fn check_gitbash() -> DependencyStatus {
-        Self::check_dependency("git", &["--version"], r"git version (\d+\.\d+\.\d+)", None)
+        let output = Command::new("git")
+            .arg("--version")
+            .output();
+
+        Self::evaluate("git", output, &[r"git version (\d+\.\d+\.\d+)"], None)
     }
 
     pub fn check_claude() -> DependencyStatus {
@@ -137,157 +152,289 @@ impl DependencyChecker {
         #[cfg(windows)]
         let output = Command::new("cmd")
             .args(&["/c", "claude", "--version"])
+            .env("PATH", Self::search_path())
             .output();
 
         #[cfg(target_os = "macos")]
         let output = {
-            // On macOS, use extended PATH to find claude
-            let extended_path = get_macos_extended_path();
+            // On macOS, use extended PATH (plus the npm user prefix) to find claude
+            let extended_path = Self::search_path();
             Command::new("sh")
-                .args(&["-c", &format!("PATH='{}' claude --version", extended_path)])
+                .args(&["-c", &format!("PATH='{}' claude --version", extended_path.to_string_lossy())])
                 .output()
         };
 
         #[cfg(all(not(windows), not(target_os = "macos")))]
         let output = Command::new("claude")
             .arg("--version")
+            .env("PATH", Self::search_path())
             .output();
 
-        match output {
-            Ok(out) if out.status.success() => {
-                let stdout = String::from_utf8_lossy(&out.stdout);
-
-                let patterns = vec![
-                    r"(\d+\.\d+\.\d+)\s*\(Claude Code\)",
-                    r"v(\d+\.\d+\.\d+)",
-                    r"^(\d+\.\d+\.\d+)",
-                    r"(\d+\.\d+\.\d+)",
-                ];
-
-                for pattern in patterns {
-                    if let Ok(re) = Regex::new(pattern) {
-                        if let Some(caps) = re.captures(&stdout) {
-                            let version = caps.get(1).map(|m| m.as_str().to_string());
-                            return DependencyStatus {
-                                installed: true,
-                                version,
-                                meets_requirement: true,
-                                latest_version: None,
-                                update_available: false,
-                                error: None,
-                            };
-                        }
-                    }
-                }
-
-                DependencyStatus {
-                    installed: true,
-                    version: None,
-                    meets_requirement: true,
-                    latest_version: None,
-                    update_available: false,
-                    error: Some("无法解析版本号".to_string()),
-                }
-            }
-            _ => DependencyStatus {
-                installed: false,
-                version: None,
-                meets_requirement: false,
-                latest_version: None,
-                update_available: false,
-                error: Some("Claude Code not found".to_string()),
-            },
-        }
+        Self::evaluate(
+            "claude",
+            output,
+            &[
+                r"(\d+\.\d+\.\d+)\s*\(Claude Code\)",
+                r"v(\d+\.\d+\.\d+)",
+                r"^(\d+\.\d+\.\d+)",
+                r"(\d+\.\d+\.\d+)",
+            ],
+            None,
+        )
     }
 
-    fn check_dependency(
+    /// Turn the output of `<command> --version` into a DependencyStatus
+    fn evaluate(
         command: &str,
-        args: &[&str],
-        pattern: &str,
+        output: std::io::Result<std::process::Output>,
+        patterns: &[&str],
         min_version: Option<&str>,
     ) -> DependencyStatus {
-        let output = Command::new(command)
-            .args(args)
-            .output();
+        let path = Self::resolve_binary(command);
+        let install_method = path.as_deref().map(Self::detect_install_method);
 
-        match output {
+        let status = match output {
             Ok(out) if out.status.success() => {
                 let stdout = String::from_utf8_lossy(&out.stdout);
-                let re = Regex::new(pattern).unwrap();
-
-                if let Some(caps) = re.captures(&stdout) {
-                    let version = caps.get(1).map(|m| m.as_str().to_string());
-
-                    let meets_requirement = if let (Some(ref v), Some(min)) = (&version, min_version) {
-                        Self::compare_versions(v, min)
-                    } else {
-                        true
-                    };
+                let version = patterns
+                    .iter()
+                    .filter_map(|pattern| Regex::new(pattern).ok())
+                    .find_map(|re| re.captures(&stdout).and_then(|caps| caps.get(1)).map(|m| m.as_str().to_string()));
 
-                    DependencyStatus {
+                match (version, min_version) {
+                    (Some(version), Some(minimum)) if !Self::compare_versions(&version, minimum) => DependencyStatus {
                         installed: true,
-                        version,
-                        meets_requirement,
-                        latest_version: None,
-                        update_available: false,
-                        error: None,
-                    }
-                } else {
-                    DependencyStatus {
-                        installed: false,
-                        version: None,
+                        version: Some(version.clone()),
                         meets_requirement: false,
-                        latest_version: None,
-                        update_available: false,
-                        error: Some("无法解析版本号".to_string()),
-                    }
+                        error: Some(DependencyError::BelowMinimum {
+                            version,
+                            minimum: minimum.to_string(),
+                        }),
+                        ..Default::default()
+                    },
+                    (Some(version), _) => DependencyStatus {
+                        installed: true,
+                        version: Some(version),
+                        meets_requirement: true,
+                        ..Default::default()
+                    },
+                    (None, _) => DependencyStatus {
+                        installed: true,
+                        meets_requirement: min_version.is_none(),
+                        error: Some(DependencyError::VersionUnparseable {
+                            raw_output: Self::excerpt(&stdout),
+                        }),
+                        ..Default::default()
+                    },
                 }
             }
-            Ok(_) => DependencyStatus {
-                installed: false,
-                version: None,
-                meets_requirement: false,
-                latest_version: None,
-                update_available: false,
-                error: Some("命令执行失败".to_string()),
+            // Wrapper shells (cmd /c, sh -c) report a missing binary as a failed exit
+            Ok(_) if path.is_none() => DependencyStatus {
+                error: Some(DependencyError::NotFound { command: command.to_string() }),
+                ..Default::default()
+            },
+            Ok(out) => DependencyStatus {
+                error: Some(DependencyError::CommandFailed {
+                    command: command.to_string(),
+                    exit_code: out.status.code(),
+                    stderr: Self::excerpt(&String::from_utf8_lossy(&out.stderr)),
+                }),
+                ..Default::default()
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => DependencyStatus {
+                error: Some(DependencyError::NotFound { command: command.to_string() }),
+                ..Default::default()
             },
             Err(e) => DependencyStatus {
-                installed: false,
-                version: None,
-                meets_requirement: false,
-                latest_version: None,
-                update_available: false,
-                error: Some(format!("Not installed: {}", e)),
+                error: Some(DependencyError::SpawnFailed {
+                    command: command.to_string(),
+                    message: e.to_string(),
+                }),
+                ..Default::default()
             },
+        };
+
+        DependencyStatus {
+            path: path.map(|p| p.to_string_lossy().to_string()),
+            install_method,
+            ..status
         }
     }
 
-    pub async fn check_nodejs_with_update() -> DependencyStatus {
-        let mut status = Self::check_nodejs();
-        if status.installed {
-            status.latest_version = Self::get_nodejs_latest_version().await;
-            if let (Some(ref current), Some(ref latest)) = (&status.version, &status.latest_version) {
-                status.update_available = !Self::compare_versions(current, latest);
-            }
+    fn excerpt(text: &str) -> String {
+        let trimmed = text.trim();
+        if trimmed.chars().count() > OUTPUT_EXCERPT_LEN {
+            format!("{}...", trimmed.chars().take(OUTPUT_EXCERPT_LEN).collect::<String>())
+        } else {
+            trimmed.to_string()
         }
-        status
     }
 
-    pub async fn check_claude_with_update() -> DependencyStatus {
-        let mut status = Self::check_claude();
-        if status.installed {
-            status.latest_version = Self::get_claude_latest_version().await;
-            if let (Some(ref current), Some(ref latest)) = (&status.version, &status.latest_version) {
-                status.update_available = !Self::compare_versions(current, latest);
+    /// PATH used to look up dependency binaries, including the npm user-prefix and native installer bin dirs
+    pub fn search_path() -> std::ffi::OsString {
+        #[cfg(target_os = "macos")]
+        let base = std::ffi::OsString::from(get_macos_extended_path());
+        #[cfg(not(target_os = "macos"))]
+        let base = std::env::var_os("PATH").unwrap_or_default();
+
+        // GUI processes often start without ~/.local/bin even when the shell profile adds it
+        let native_bin_dir = ClaudeNative::bin_dir().filter(|dir| dir.is_dir());
+        // nvm only adds its node to PATH from the shell profile
+        #[cfg(target_os = "linux")]
+        let nvm_bin_dir = std::env::var_os("NVM_DIR")
+            .map(std::path::PathBuf::from)
+            .or_else(|| dirs::home_dir().map(|home| home.join(".nvm")))
+            .and_then(|nvm_dir| Self::nvm_bin_dir(&nvm_dir));
+        #[cfg(not(target_os = "linux"))]
+        let nvm_bin_dir: Option<std::path::PathBuf> = None;
+        let extra: Vec<std::path::PathBuf> = NpmPrefix::user_bin_dir()
+            .into_iter()
+            .chain(native_bin_dir)
+            .chain(nvm_bin_dir)
+            .filter(|dir| !std::env::split_paths(&base).any(|p| p == *dir))
+            .collect();
+
+        if extra.is_empty() {
+            return base;
+        }
+        std::env::join_paths(std::env::split_paths(&base).chain(extra)).unwrap_or(base)
+    }
+
+    /// bin directory of the node version nvm's `default` alias selects, the one a new shell would use
+    #[cfg(target_os = "linux")]
+    fn nvm_bin_dir(nvm_dir: &std::path::Path) -> Option<std::path::PathBuf> {
+        // Aliases may point at other aliases (default -> lts/* -> lts/iron -> v20.18.0)
+        let mut name = "default".to_string();
+        for _ in 0..8 {
+            match std::fs::read_to_string(nvm_dir.join("alias").join(&name)) {
+                Ok(target) => name = target.trim().to_string(),
+                Err(_) => break,
             }
         }
+        if name == "default" {
+            return None;
+        }
+
+        let wanted = name.trim_start_matches('v');
+        let any_version = wanted == "node" || wanted == "stable";
+        let versions_dir = nvm_dir.join("versions").join("node");
+        std::fs::read_dir(&versions_dir)
+            .ok()?
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str().map(|dir| dir.trim_start_matches('v').to_string()))
+            .filter(|version| any_version || version == wanted || version.starts_with(&format!("{}.", wanted)))
+            .max_by_key(|version| NpmRegistry::version_sort_key(version))
+            .map(|version| versions_dir.join(format!("v{}", version)).join("bin"))
+            .filter(|dir| dir.is_dir())
+    }
+
+    /// Find the first matching executable on PATH (honours PATHEXT on Windows)
+    pub fn resolve_binary(name: &str) -> Option<std::path::PathBuf> {
+        #[cfg(windows)]
+        let extensions: Vec<String> = std::env::var("PATHEXT")
+            .unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".to_string())
+            .split(';')
+            .filter(|ext| !ext.is_empty())
+            .map(|ext| ext.to_lowercase())
+            .collect();
+
+        #[cfg(not(windows))]
+        let extensions: Vec<String> = vec![String::new()];
+
+        std::env::split_paths(&Self::search_path())
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .flat_map(|dir| {
+                extensions
+                    .iter()
+                    .map(|ext| dir.join(format!("{}{}", name, ext)))
+                    .collect::<Vec<_>>()
+            })
+            .find(|candidate| candidate.is_file())
+    }
+
+    /// Guess the install method from where the binary (or its symlink target) lives
+    pub fn detect_install_method(path: &std::path::Path) -> InstallMethod {
+        let normalize = |p: &std::path::Path| p.to_string_lossy().replace('\\', "/").to_lowercase();
+        let resolved = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let original = normalize(path);
+        let target = normalize(&resolved);
+
+        // npm shims on Windows sit next to a node_modules folder instead of symlinking into it
+        let npm_shim = path
+            .parent()
+            .map(|dir| dir.join("node_modules").is_dir())
+            .unwrap_or(false);
+
+        if target.contains("/node_modules/") || npm_shim {
+            InstallMethod::Npm
+        } else if original.contains("/.local/bin/") || target.contains("/.local/share/claude/") || target.contains("/.claude/local/") {
+            InstallMethod::Native
+        } else if target.contains("/homebrew/") || target.contains("/cellar/") || target.contains("/linuxbrew/") {
+            InstallMethod::Homebrew
+        } else if ["/.nvm/", "/nvm/", "/fnm/", "/fnm_multishells/", "/.volta/", "/n/versions/"]
+            .iter()
+            .any(|marker| target.contains(marker))
+        {
+            InstallMethod::VersionManager
+        } else if target.contains("/program files") || target.starts_with("/library/developer/") || target.starts_with("/applications/xcode") {
+            InstallMethod::SystemInstaller
+        } else if cfg!(target_os = "linux") && ["/usr/bin/", "/bin/", "/usr/sbin/"].iter().any(|dir| target.starts_with(dir)) {
+            InstallMethod::PackageManager
+        } else if cfg!(target_os = "macos") && target.starts_with("/usr/bin/") {
+            InstallMethod::SystemInstaller
+        } else {
+            InstallMethod::Unknown
+        }
+    }
+
+    pub async fn check_nodejs_with_update(force: bool) -> DependencyStatus {
+        let status = Self::check_nodejs();
+        Self::apply_latest_version(status, "nodejs", force, Self::get_nodejs_latest_version).await
+    }
+
+    pub async fn check_claude_with_update(force: bool) -> DependencyStatus {
+        let status = Self::check_claude();
+        // Native installs update from Anthropic's release channel, not from the npm registry
+        let mut status = if status.install_method == Some(InstallMethod::Native) {
+            Self::apply_latest_version(status, "claude_native", force, Self::get_claude_native_latest_version).await
+        } else {
+            // Mirrors can lag behind the default registry, so each registry gets its own entry
+            let registry = NpmRegistry::resolve();
+            let cache_key = format!("claude@{}", registry.url);
+            Self::apply_latest_version(status, &cache_key, force, || Self::get_claude_latest_version(registry)).await
+        };
+
+        let pinned = ConfigStorage::get_launcher_settings()
+            .ok()
+            .and_then(|settings| settings.claude_pinned_version);
+        if pinned.is_some() {
+            status.update_available = false;
+            status.pinned_version = pinned;
+        }
         status
     }
 
-    pub async fn check_gitbash_with_update() -> DependencyStatus {
-        let mut status = Self::check_gitbash();
+    pub async fn check_gitbash_with_update(force: bool) -> DependencyStatus {
+        let status = Self::check_gitbash();
+        Self::apply_latest_version(status, "gitbash", force, Self::get_gitbash_latest_version).await
+    }
+
+    /// Fill in latest_version through the update cache and compute update_available
+    async fn apply_latest_version<F, Fut>(
+        mut status: DependencyStatus,
+        cache_key: &str,
+        force: bool,
+        fetch: F,
+    ) -> DependencyStatus
+    where
+        F: FnOnce() -> Fut,
+        Fut: std::future::Future<Output = Option<String>>,
+    {
         if status.installed {
-            status.latest_version = Self::get_gitbash_latest_version().await;
+            let lookup = UpdateCache::lookup(cache_key, force, fetch).await;
+            status.latest_version = lookup.latest_version;
+            status.last_checked_at = lookup.checked_at;
+            status.latest_version_stale = lookup.stale;
             if let (Some(ref current), Some(ref latest)) = (&status.version, &status.latest_version) {
                 status.update_available = !Self::compare_versions(current, latest);
             }
@@ -381,22 +528,13 @@ impl DependencyChecker {
         }
     }
 
-    async fn get_claude_latest_version() -> Option<String> {
-        // 从 npm registry 获取最新版本 (跨平台)
-        let url = "https://registry.npmjs.org/@anthropic-ai/claude-code/latest";
-
-        match reqwest::get(url).await {
-            Ok(response) => {
-                if let Ok(json) = response.json::<serde_json::Value>().await {
-                    return json.get("version")
-                        .and_then(|v| v.as_str())
-                        .map(|s| s.to_string());
-                }
-            }
-            Err(_) => {}
-        }
+    async fn get_claude_latest_version(registry: RegistryConfig) -> Option<String> {
+        // 从 npm registry 获取最新版本 (跨平台, 支持自定义镜像和 .npmrc 认证)
+        NpmRegistry::fetch_latest_version(&registry, CLAUDE_PACKAGE).await.ok()
+    }
 
-        None
+    async fn get_claude_native_latest_version() -> Option<String> {
+        ClaudeNative::fetch_latest_version().await.ok()
     }
 
     async fn get_gitbash_latest_version() -> Option<String> {
@@ -464,6 +602,7 @@ impl DependencyChecker {
         use winreg::RegKey;
         use winreg::enums::*;
         use std::env;
+        use super::logger::Logger;
 
         let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
         let hkcu = RegKey::predef(HKEY_CURRENT_USER);
@@ -510,7 +649,7 @@ impl DependencyChecker {
         } else {
             current_path.clone()
         };
-        eprintln!("[DEBUG] 当前 PATH (前200字符): {}", path_preview);
+        Logger::debug("dependency_checker", format!("当前 PATH (前200字符): {}", path_preview));
 
         let nodejs_paths = vec![
             r"C:\Program Files\nodejs",
@@ -519,8 +658,51 @@ impl DependencyChecker {
 
         for path in nodejs_paths {
             if std::path::Path::new(path).exists() {
-                println!("检测到Node.js路径: {}", path);
+                Logger::debug("dependency_checker", format!("检测到Node.js路径: {}", path));
             }
         }
     }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    fn nvm_fixture(versions: &[&str], aliases: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for version in versions {
+            std::fs::create_dir_all(dir.path().join("versions/node").join(version).join("bin")).unwrap();
+        }
+        for (name, target) in aliases {
+            let path = dir.path().join("alias").join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, format!("{}\n", target)).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn nvm_default_alias_selects_the_bin_dir() {
+        let versions = ["v18.20.4", "v20.9.0", "v20.18.0", "v22.11.0"];
+        let bin = |dir: &tempfile::TempDir, version: &str| Some(dir.path().join("versions/node").join(version).join("bin"));
+
+        let exact = nvm_fixture(&versions, &[("default", "v20.9.0")]);
+        assert_eq!(DependencyChecker::nvm_bin_dir(exact.path()), bin(&exact, "v20.9.0"));
+
+        let major = nvm_fixture(&versions, &[("default", "20")]);
+        assert_eq!(DependencyChecker::nvm_bin_dir(major.path()), bin(&major, "v20.18.0"));
+
+        let lts = nvm_fixture(&versions, &[("default", "lts/*"), ("lts/*", "lts/iron"), ("lts/iron", "v20.18.0")]);
+        assert_eq!(DependencyChecker::nvm_bin_dir(lts.path()), bin(&lts, "v20.18.0"));
+
+        let latest = nvm_fixture(&versions, &[("default", "node")]);
+        assert_eq!(DependencyChecker::nvm_bin_dir(latest.path()), bin(&latest, "v22.11.0"));
+
+        // No default alias: nvm leaves the system node in place
+        let none = nvm_fixture(&versions, &[]);
+        assert_eq!(DependencyChecker::nvm_bin_dir(none.path()), None);
+
+        let missing = nvm_fixture(&versions, &[("default", "16")]);
+        assert_eq!(DependencyChecker::nvm_bin_dir(missing.path()), None);
+    }
+}
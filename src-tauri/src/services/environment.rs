@@ -1,3 +1,7 @@
+/// Marks the PATH lines `add_to_user_path` writes to shell startup files
+#[cfg(not(windows))]
+const PATH_MARKER: &str = "# Added by Claude Code Launcher";
+
 pub struct Environment;
 
 impl Environment {
@@ -54,6 +58,203 @@ impl Environment {
         Err("此功能仅在Windows上可用".to_string())
     }
 
+    /// Read a persistent user environment variable
+    #[cfg(windows)]
+    pub fn get_permanent(key: &str) -> Option<String> {
+        use winreg::RegKey;
+        use winreg::enums::*;
+
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey("Environment")
+            .and_then(|env| env.get_value::<String, _>(key))
+            .ok()
+    }
+
+    #[cfg(not(windows))]
+    pub fn get_permanent(_key: &str) -> Option<String> {
+        None
+    }
+
+    #[cfg(windows)]
+    fn read_user_path() -> String {
+        use winreg::RegKey;
+        use winreg::enums::*;
+
+        RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey("Environment")
+            .and_then(|key| key.get_value::<String, _>("Path"))
+            .unwrap_or_default()
+    }
+
+    /// Whether `dir` is already part of the persistent user PATH
+    #[cfg(windows)]
+    pub fn user_path_contains(dir: &std::path::Path) -> bool {
+        let dir = dir.to_string_lossy().trim_end_matches('\\').to_lowercase();
+        Self::read_user_path()
+            .split(';')
+            .any(|entry| entry.trim_end_matches('\\').to_lowercase() == dir)
+    }
+
+    /// Append `dir` to the user PATH in the registry, keeping it REG_EXPAND_SZ
+    #[cfg(windows)]
+    pub fn add_to_user_path(dir: &std::path::Path) -> Result<(), String> {
+        if Self::user_path_contains(dir) {
+            return Ok(());
+        }
+
+        let current = Self::read_user_path();
+        let new_path = if current.trim().is_empty() {
+            dir.to_string_lossy().to_string()
+        } else {
+            format!("{};{}", current.trim_end_matches(';'), dir.to_string_lossy())
+        };
+        Self::write_user_path(&new_path)
+    }
+
+    /// Remove `dir` from the user PATH in the registry; other entries are kept as they are
+    #[cfg(windows)]
+    pub fn remove_from_user_path(dir: &std::path::Path) -> Result<(), String> {
+        if !Self::user_path_contains(dir) {
+            return Ok(());
+        }
+
+        let dir = dir.to_string_lossy().trim_end_matches('\\').to_lowercase();
+        let new_path = Self::read_user_path()
+            .split(';')
+            .filter(|entry| !entry.is_empty() && entry.trim_end_matches('\\').to_lowercase() != dir)
+            .collect::<Vec<_>>()
+            .join(";");
+        Self::write_user_path(&new_path)
+    }
+
+    #[cfg(windows)]
+    fn write_user_path(value: &str) -> Result<(), String> {
+        use winreg::{RegKey, RegValue};
+        use winreg::enums::*;
+
+        let hkcu = RegKey::predef(HKEY_CURRENT_USER);
+        let env = hkcu
+            .open_subkey_with_flags("Environment", KEY_READ | KEY_SET_VALUE)
+            .map_err(|e| format!("无法打开注册表: {}", e))?;
+
+        let bytes: Vec<u8> = value
+            .encode_utf16()
+            .chain(std::iter::once(0))
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        env.set_raw_value("Path", &RegValue { bytes, vtype: REG_EXPAND_SZ })
+            .map_err(|e| format!("无法设置环境变量: {}", e))?;
+
+        Self::broadcast_environment_change();
+        Ok(())
+    }
+
+    /// Shell startup files that get a PATH line; login files are created when missing
+    #[cfg(not(windows))]
+    fn shell_profiles() -> Vec<(std::path::PathBuf, bool)> {
+        let Some(home) = dirs::home_dir() else {
+            return Vec::new();
+        };
+
+        let mut profiles = vec![
+            (home.join(".profile"), true),
+            (home.join(".bashrc"), false),
+            (home.join(".zshrc"), false),
+        ];
+        // zsh is the default login shell on macOS and does not read ~/.profile
+        if cfg!(target_os = "macos") {
+            profiles.push((home.join(".zprofile"), true));
+        }
+        profiles
+    }
+
+    #[cfg(not(windows))]
+    pub fn user_path_contains(dir: &std::path::Path) -> bool {
+        let dir = dir.to_string_lossy();
+        Self::shell_profiles()
+            .iter()
+            .filter_map(|(path, _)| std::fs::read_to_string(path).ok())
+            .any(|content| content.contains(dir.as_ref()))
+    }
+
+    /// Add an `export PATH=...` line for `dir` to the user's shell startup files
+    #[cfg(not(windows))]
+    pub fn add_to_user_path(dir: &std::path::Path) -> Result<(), String> {
+        use std::io::Write;
+
+        let dir = dir.to_string_lossy().to_string();
+        let block = format!(
+            "\n{}\nexport PATH=\"{}:$PATH\"\n",
+            PATH_MARKER,
+            dir.replace('"', "\\\"")
+        );
+
+        for (path, create) in Self::shell_profiles() {
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(_) if create => String::new(),
+                Err(_) => continue,
+            };
+            if content.contains(&dir) {
+                continue;
+            }
+
+            let mut file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| format!("无法写入 {}: {}", path.display(), e))?;
+            file.write_all(block.as_bytes())
+                .map_err(|e| format!("无法写入 {}: {}", path.display(), e))?;
+        }
+
+        Ok(())
+    }
+
+    /// Shell startup files that still contain a PATH line written by `add_to_user_path`
+    #[cfg(not(windows))]
+    pub fn profiles_with_path_entry() -> Vec<std::path::PathBuf> {
+        Self::shell_profiles()
+            .into_iter()
+            .map(|(path, _)| path)
+            .filter(|path| {
+                std::fs::read_to_string(path)
+                    .map(|content| content.lines().any(|line| line.trim() == PATH_MARKER))
+                    .unwrap_or(false)
+            })
+            .collect()
+    }
+
+    /// Remove the PATH lines `add_to_user_path` wrote to `profile`, including the blank line before them
+    #[cfg(not(windows))]
+    pub fn remove_path_entry(profile: &std::path::Path) -> Result<(), String> {
+        let content = std::fs::read_to_string(profile)
+            .map_err(|e| format!("无法读取 {}: {}", profile.display(), e))?;
+
+        let mut kept: Vec<&str> = Vec::new();
+        let mut lines = content.lines().peekable();
+        while let Some(line) = lines.next() {
+            if line.trim() == PATH_MARKER {
+                if lines.peek().is_some_and(|next| next.trim_start().starts_with("export PATH=")) {
+                    lines.next();
+                }
+                if kept.last().is_some_and(|last| last.trim().is_empty()) {
+                    kept.pop();
+                }
+                continue;
+            }
+            kept.push(line);
+        }
+
+        let mut updated = kept.join("\n");
+        if content.ends_with('\n') && !updated.is_empty() {
+            updated.push('\n');
+        }
+        std::fs::write(profile, updated).map_err(|e| format!("无法写入 {}: {}", profile.display(), e))
+    }
+
+    /// Variables older launcher versions set persistently with `set_permanent`;
+    /// users often set the same names themselves
     pub fn get_env_keys() -> Vec<String> {
         vec![
             "ANTHROPIC_MODEL".to_string(),
@@ -64,3 +265,28 @@ impl Environment {
         ]
     }
 }
+
+#[cfg(all(test, not(windows)))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_entry_is_removed_from_profile() {
+        let dir = tempfile::tempdir().unwrap();
+        let profile = dir.path().join(".bashrc");
+        std::fs::write(
+            &profile,
+            format!(
+                "alias ll='ls -l'\n\n{}\nexport PATH=\"/home/u/.npm-global/bin:$PATH\"\nexport EDITOR=vim\n",
+                PATH_MARKER
+            ),
+        )
+        .unwrap();
+
+        Environment::remove_path_entry(&profile).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&profile).unwrap(),
+            "alias ll='ls -l'\nexport EDITOR=vim\n"
+        );
+    }
+}
@@ -0,0 +1,226 @@
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+use std::time::{Duration, SystemTime};
+
+/// How often watched files are checked for changes
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// State of a file on disk; `hash` settles whether a new mtime really means new content
+#[derive(Debug, Clone)]
+struct Fingerprint {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: String,
+}
+
+impl Fingerprint {
+    fn of(path: &Path, content: &[u8]) -> Self {
+        let modified = fs::metadata(path).and_then(|m| m.modified()).ok();
+        Self {
+            modified,
+            len: content.len() as u64,
+            hash: FileWatch::version_of(Some(content)),
+        }
+    }
+
+    /// None when the file does not exist (or cannot be read)
+    fn read(path: &Path) -> Option<Self> {
+        fs::read(path).ok().map(|content| Self::of(path, &content))
+    }
+
+    /// Same content; the cheap mtime/size check first, the hash only when those differ
+    fn same(a: &Option<Self>, b: &Option<Self>) -> bool {
+        match (a, b) {
+            (None, None) => true,
+            (Some(a), Some(b)) => {
+                (a.modified.is_some() && a.modified == b.modified && a.len == b.len) || a.hash == b.hash
+            }
+            _ => false,
+        }
+    }
+}
+
+type Versions = Mutex<HashMap<PathBuf, Option<Fingerprint>>>;
+
+/// The version of each file this process last wrote, so the watcher can skip its own changes
+fn written() -> Result<MutexGuard<'static, HashMap<PathBuf, Option<Fingerprint>>>, String> {
+    static WRITTEN: OnceLock<Versions> = OnceLock::new();
+    WRITTEN
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .map_err(|_| "文件版本记录被锁定".to_string())
+}
+
+fn write_lock() -> Result<MutexGuard<'static, ()>, String> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().map_err(|_| "文件写入被锁定".to_string())
+}
+
+/// Guards files the launcher shares with other writers (a hand edit, Claude Code itself,
+/// the CLI in another process): a write names the version it is based on and is refused
+/// when the file no longer has it, and a polling watcher reports changes made by others.
+///
+/// The check is best-effort across processes: the other writers take no lock of ours, so a
+/// change landing between the check and the rename is still lost. Writes go to a temp file
+/// that is renamed over the target, so readers never see a half-written file.
+pub struct FileWatch;
+
+impl FileWatch {
+    /// Version of `content` as read from a file; empty when the file does not exist
+    pub fn version_of(content: Option<&[u8]>) -> String {
+        content
+            .map(|content| Sha256::digest(content).iter().map(|b| format!("{:02x}", b)).collect())
+            .unwrap_or_default()
+    }
+
+    /// Current version of `path`; a file that cannot be read counts as missing
+    pub fn version(path: &Path) -> String {
+        Self::version_of(fs::read(path).ok().as_deref())
+    }
+
+    /// Err if `path` no longer has the version `base` the caller read or was handed.
+    /// A file deleted in the meantime is never in conflict: writing it again loses nothing.
+    fn ensure_version(path: &Path, base: &str) -> Result<(), String> {
+        let current = Self::version(path);
+        if current.is_empty() || current == base {
+            return Ok(());
+        }
+        Err(format!(
+            "{} 在读取后被其他程序修改，已取消保存以免覆盖这些修改，请刷新后重试",
+            path.display()
+        ))
+    }
+
+    fn mark_written(path: &Path, content: Option<&[u8]>) -> Result<(), String> {
+        let fingerprint = content.map(|content| Fingerprint::of(path, content));
+        written()?.insert(path.to_path_buf(), fingerprint);
+        Ok(())
+    }
+
+    /// Write `path` unless it changed since version `base`, and record it as this process's
+    /// own change. Within this process the check and the write happen under one lock, which
+    /// also keeps the watcher from seeing the new content before it is recorded.
+    pub fn write(path: &Path, content: &[u8], base: &str) -> Result<(), String> {
+        let _guard = write_lock()?;
+        Self::ensure_version(path, base)?;
+        Self::replace(path, content).map_err(|e| format!("无法写入 {}: {}", path.display(), e))?;
+        Self::mark_written(path, Some(content))
+    }
+
+    /// Write a temp file next to `path` and rename it over. A symlinked file (e.g. settings.json
+    /// kept in a dotfiles repo) is replaced at its target, and existing permissions are kept.
+    fn replace(path: &Path, content: &[u8]) -> std::io::Result<()> {
+        use std::io::Write;
+
+        let target = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+        let dir = target.parent().unwrap_or(Path::new("."));
+        let mut temp = tempfile::NamedTempFile::new_in(dir)?;
+        temp.write_all(content)?;
+        temp.as_file().sync_all()?;
+        if let Ok(metadata) = fs::metadata(&target) {
+            fs::set_permissions(temp.path(), metadata.permissions())?;
+        }
+        temp.persist(&target).map(|_| ()).map_err(|e| e.error)
+    }
+
+    pub fn remove(path: &Path, base: &str) -> Result<(), String> {
+        let _guard = write_lock()?;
+        Self::ensure_version(path, base)?;
+        fs::remove_file(path).map_err(|e| format!("无法删除 {}: {}", path.display(), e))?;
+        Self::mark_written(path, None)
+    }
+
+    /// Poll `paths` in the background and call `on_change` for every change that this
+    /// process did not make itself
+    pub fn watch<F>(paths: Vec<PathBuf>, on_change: F)
+    where
+        F: Fn(&Path) + Send + 'static,
+    {
+        std::thread::spawn(move || {
+            let mut seen: HashMap<PathBuf, Option<Fingerprint>> =
+                paths.iter().map(|p| (p.clone(), Fingerprint::read(p))).collect();
+            loop {
+                std::thread::sleep(POLL_INTERVAL);
+                for path in &paths {
+                    let Ok(guard) = write_lock() else {
+                        return;
+                    };
+                    let current = Fingerprint::read(path);
+                    let previous = seen.get(path).cloned().flatten();
+                    if Fingerprint::same(&previous, &current) {
+                        continue;
+                    }
+                    seen.insert(path.clone(), current.clone());
+
+                    let own = written()
+                        .map(|written| written.get(path).is_some_and(|known| Fingerprint::same(known, &current)))
+                        .unwrap_or(false);
+                    // Listeners may write the file themselves
+                    drop(guard);
+                    if !own {
+                        on_change(path);
+                    }
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn write_is_refused_after_a_foreign_change() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("config.json");
+
+        // A missing file has the empty version
+        assert_eq!(FileWatch::version(&path), "");
+        FileWatch::write(&path, b"one", "").unwrap();
+        let base = FileWatch::version(&path);
+        assert_eq!(base, FileWatch::version_of(Some(b"one")));
+
+        fs::write(&path, b"edited by hand").unwrap();
+        let err = FileWatch::write(&path, b"two", &base).unwrap_err();
+        assert!(err.contains("在读取后被其他程序修改"), "{}", err);
+        assert_eq!(fs::read(&path).unwrap(), b"edited by hand");
+        assert!(FileWatch::remove(&path, &base).is_err());
+
+        // Created by someone else after we saw it missing
+        assert!(FileWatch::write(&path, b"two", "").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn write_keeps_symlinks_and_permissions() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("dotfiles-settings.json");
+        let link = dir.path().join("settings.json");
+        fs::write(&target, b"old").unwrap();
+        fs::set_permissions(&target, fs::Permissions::from_mode(0o644)).unwrap();
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        FileWatch::write(&link, b"new", &FileWatch::version(&link)).unwrap();
+        assert!(fs::symlink_metadata(&link).unwrap().file_type().is_symlink());
+        assert_eq!(fs::read(&target).unwrap(), b"new");
+        assert_eq!(fs::metadata(&target).unwrap().permissions().mode() & 0o777, 0o644);
+    }
+
+    #[test]
+    fn write_over_a_deleted_file_is_allowed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("settings.json");
+        fs::write(&path, b"old").unwrap();
+        let base = FileWatch::version(&path);
+
+        fs::remove_file(&path).unwrap();
+        FileWatch::write(&path, b"new", &base).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), b"new");
+    }
+}
@@ -0,0 +1,311 @@
+use crate::models::{LaunchHook, ProjectConfig};
+
+/// Upper bound for a single hook's timeout
+pub const MAX_HOOK_TIMEOUT_SECS: u64 = 3600;
+
+/// Hooks of one launch; empty for launches that are not tied to a project
+#[derive(Debug, Clone, Default)]
+pub struct LaunchHooks {
+    pub pre_launch: Vec<LaunchHook>,
+    pub post_exit: Vec<LaunchHook>,
+}
+
+impl LaunchHooks {
+    pub fn from_config(config: &ProjectConfig) -> Self {
+        Self {
+            pre_launch: config.pre_launch_hooks.clone(),
+            post_exit: config.post_exit_hooks.clone(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pre_launch.is_empty() && self.post_exit.is_empty()
+    }
+}
+
+/// Builds the shell fragments that run launch hooks around `claude`.
+///
+/// Every fragment prints each hook's output to the terminal and, when a log file is
+/// given, appends it there so the UI can show it after the fact. A failing hook with
+/// `abort_on_failure` stops the chain: before launch `claude` is not started, after
+/// exit the remaining hooks are skipped. The exit code reported for the session is
+/// still the one of `claude` whenever it ran.
+pub struct Hooks;
+
+impl Hooks {
+    fn quote_posix(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "'\\''"))
+    }
+
+    fn quote_powershell(value: &str) -> String {
+        format!("'{}'", value.replace('\'', "''"))
+    }
+
+    fn mode(hook: &LaunchHook) -> &'static str {
+        if hook.abort_on_failure {
+            "abort"
+        } else {
+            "continue"
+        }
+    }
+
+    /// sh/bash/zsh. Timeouts use `timeout` where coreutils provide it (exit 124) and
+    /// perl's `alarm` otherwise, e.g. on macOS (exit 142).
+    pub fn wrap_posix(claude_cmd: &str, hooks: &LaunchHooks, log_file: Option<&str>) -> String {
+        if hooks.is_empty() {
+            return claude_cmd.to_string();
+        }
+
+        let log = Self::quote_posix(log_file.unwrap_or("/dev/null"));
+        let helper = format!(
+            concat!(
+                "__launcher_hook() {{ ",
+                "echo \"[launcher] $1 hook: $4\" | tee -a {log}; ",
+                "__launcher_f=$(mktemp); ",
+                "{{ if command -v timeout >/dev/null 2>&1; then timeout \"$3\" /bin/sh -c \"$4\"; ",
+                "else perl -e 'alarm shift; exec @ARGV' \"$3\" /bin/sh -c \"$4\"; fi 2>&1; ",
+                "echo $? > \"$__launcher_f\"; }} | tee -a {log}; ",
+                "__launcher_rc=$(cat \"$__launcher_f\"); rm -f \"$__launcher_f\"; ",
+                "[ \"$__launcher_rc\" = 0 ] && return 0; ",
+                "case \"$__launcher_rc\" in ",
+                "124|142) echo \"[launcher] hook timed out after $3s\" | tee -a {log};; ",
+                "*) echo \"[launcher] hook failed with exit code $__launcher_rc\" | tee -a {log};; ",
+                "esac; ",
+                "[ \"$2\" = abort ] && return \"$__launcher_rc\"; ",
+                "return 0; }}"
+            ),
+            log = log,
+        );
+
+        let chain = |stage: &str, list: &[LaunchHook]| -> String {
+            list.iter()
+                .map(|hook| {
+                    format!(
+                        "__launcher_hook {} {} {} {}",
+                        stage,
+                        Self::mode(hook),
+                        hook.timeout_secs,
+                        Self::quote_posix(&hook.command)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" && ")
+        };
+
+        let mut parts = vec![helper];
+        if !hooks.pre_launch.is_empty() {
+            parts.push(chain("pre-launch", &hooks.pre_launch));
+        }
+        if hooks.post_exit.is_empty() {
+            parts.push(claude_cmd.to_string());
+        } else {
+            parts.push(format!(
+                "{{ {}; __launcher_ec=$?; {}; (exit $__launcher_ec); }}",
+                claude_cmd,
+                chain("post-exit", &hooks.post_exit)
+            ));
+        }
+        parts.join(" && ")
+    }
+
+    /// Windows PowerShell 5.1. Each hook runs in a child `powershell.exe` so it can be
+    /// killed (with its process tree) on timeout; its output is shown once it finishes.
+    pub fn wrap_powershell(claude_cmd: &str, hooks: &LaunchHooks, log_file: Option<&str>) -> String {
+        if hooks.is_empty() {
+            return claude_cmd.to_string();
+        }
+
+        let log = Self::quote_powershell(log_file.unwrap_or(""));
+        let helper = format!(
+            concat!(
+                "function Invoke-LauncherHook([string]$Stage, [string]$Mode, [int]$Timeout, [string]$Command) {{ ",
+                "$log = {log}; ",
+                "$write = {{ param($line) $line | Out-Host; if ($log) {{ Add-Content -LiteralPath $log -Value $line }} }}; ",
+                "& $write \"[launcher] $Stage hook: $Command\"; ",
+                "$out = [IO.Path]::GetTempFileName(); $err = [IO.Path]::GetTempFileName(); ",
+                "$enc = [Convert]::ToBase64String([Text.Encoding]::Unicode.GetBytes($Command)); ",
+                "$p = Start-Process -FilePath 'powershell.exe' ",
+                "-ArgumentList '-NoProfile','-NonInteractive','-ExecutionPolicy','Bypass','-EncodedCommand',$enc ",
+                "-WorkingDirectory (Get-Location).Path -NoNewWindow -PassThru ",
+                "-RedirectStandardOutput $out -RedirectStandardError $err; ",
+                "$null = $p.Handle; ",
+                "$done = $p.WaitForExit($Timeout * 1000); ",
+                "if (-not $done) {{ & taskkill.exe /T /F /PID $p.Id 2>&1 | Out-Null; $null = $p.WaitForExit(5000) }}; ",
+                "Get-Content -LiteralPath $out, $err | ForEach-Object {{ & $write $_ }}; ",
+                "Remove-Item -LiteralPath $out, $err -ErrorAction SilentlyContinue; ",
+                "if ($done -and $p.ExitCode -eq 0) {{ return $true }}; ",
+                "if ($done) {{ & $write \"[launcher] hook failed with exit code $($p.ExitCode)\" }} ",
+                "else {{ & $write \"[launcher] hook timed out after ${{Timeout}}s\" }}; ",
+                "if ($Mode -eq 'abort') {{ $global:LASTEXITCODE = $(if ($done) {{ $p.ExitCode }} else {{ 1 }}); return $false }}; ",
+                "return $true }}"
+            ),
+            log = log,
+        );
+
+        let chain = |stage: &str, list: &[LaunchHook]| -> String {
+            list.iter()
+                .map(|hook| {
+                    format!(
+                        "(Invoke-LauncherHook '{}' '{}' {} {})",
+                        stage,
+                        Self::mode(hook),
+                        hook.timeout_secs,
+                        Self::quote_powershell(&hook.command)
+                    )
+                })
+                .collect::<Vec<_>>()
+                .join(" -and ")
+        };
+
+        let run = if hooks.post_exit.is_empty() {
+            claude_cmd.to_string()
+        } else {
+            format!(
+                "{}; $__launcherExit = $LASTEXITCODE; $null = {}; $global:LASTEXITCODE = $__launcherExit",
+                claude_cmd,
+                chain("post-exit", &hooks.post_exit)
+            )
+        };
+
+        if hooks.pre_launch.is_empty() {
+            format!("{}; {}", helper, run)
+        } else {
+            format!("{}; if ({}) {{ {} }}", helper, chain("pre-launch", &hooks.pre_launch), run)
+        }
+    }
+
+    /// cmd.exe, for the copyable command only: cmd cannot bound a command's runtime,
+    /// so timeouts are not applied and output is not captured.
+    pub fn wrap_cmd(claude_cmd: &str, hooks: &LaunchHooks) -> String {
+        if hooks.is_empty() {
+            return claude_cmd.to_string();
+        }
+
+        let chain = |list: &[LaunchHook]| -> String {
+            list.iter()
+                .map(|hook| {
+                    if hook.abort_on_failure {
+                        format!("({})", hook.command)
+                    } else {
+                        format!("({} || ver >nul)", hook.command)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" && ")
+        };
+
+        let run = if hooks.post_exit.is_empty() {
+            claude_cmd.to_string()
+        } else {
+            format!("({} & {})", claude_cmd, chain(&hooks.post_exit))
+        };
+
+        if hooks.pre_launch.is_empty() {
+            run
+        } else {
+            format!("{} && {}", chain(&hooks.pre_launch), run)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hook(command: &str, abort_on_failure: bool) -> LaunchHook {
+        LaunchHook { command: command.to_string(), timeout_secs: 5, abort_on_failure }
+    }
+
+    fn hooks(pre_launch: Vec<LaunchHook>, post_exit: Vec<LaunchHook>) -> LaunchHooks {
+        LaunchHooks { pre_launch, post_exit }
+    }
+
+    /// Run a wrapped command in sh; returns (stdout, exit code)
+    #[cfg(unix)]
+    fn run_sh(script: &str) -> (String, i32) {
+        let output = std::process::Command::new("/bin/sh").arg("-c").arg(script).output().unwrap();
+        (String::from_utf8_lossy(&output.stdout).into_owned(), output.status.code().unwrap_or(-1))
+    }
+
+    #[test]
+    fn no_hooks_leaves_command_alone() {
+        let none = LaunchHooks::default();
+        assert_eq!(Hooks::wrap_posix("claude", &none, None), "claude");
+        assert_eq!(Hooks::wrap_powershell("claude", &none, None), "claude");
+        assert_eq!(Hooks::wrap_cmd("claude", &none), "claude");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn posix_runs_hooks_around_claude() {
+        let hooks = hooks(vec![hook("echo 'pre $HOME'", true)], vec![hook("echo post", false)]);
+        let (out, code) = run_sh(&Hooks::wrap_posix("echo claude; (exit 3)", &hooks, None));
+        let lines: Vec<&str> = out.lines().filter(|l| !l.starts_with("[launcher]")).collect();
+        // The hook's own quoting survives the wrapping
+        assert_eq!(lines, ["pre $HOME", "claude", "post"]);
+        // Claude's exit code wins over the post-exit hooks
+        assert_eq!(code, 3);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn posix_abort_hook_stops_the_chain() {
+        let hooks = hooks(vec![hook("exit 7", true), hook("echo second", false)], vec![]);
+        let (out, code) = run_sh(&Hooks::wrap_posix("echo claude", &hooks, None));
+        assert!(out.contains("hook failed with exit code 7"), "{}", out);
+        assert!(!out.contains("second") && !out.contains("claude\n"), "{}", out);
+        assert_eq!(code, 7);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn posix_continue_hook_does_not_stop_the_chain() {
+        let hooks = hooks(vec![hook("exit 7", false)], vec![]);
+        let (out, code) = run_sh(&Hooks::wrap_posix("echo claude", &hooks, None));
+        assert!(out.ends_with("claude\n"), "{}", out);
+        assert_eq!(code, 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn posix_hook_times_out_and_logs() {
+        let log = tempfile::NamedTempFile::new().unwrap();
+        let log_path = log.path().to_str().unwrap();
+        let mut slow = hook("sleep 10", true);
+        slow.timeout_secs = 1;
+        let (out, code) = run_sh(&Hooks::wrap_posix("echo claude", &hooks(vec![slow], vec![]), Some(log_path)));
+        assert!(out.contains("hook timed out after 1s"), "{}", out);
+        assert_ne!(code, 0);
+        let logged = std::fs::read_to_string(log.path()).unwrap();
+        assert!(logged.contains("[launcher] pre-launch hook: sleep 10"), "{}", logged);
+    }
+
+    #[test]
+    fn powershell_quotes_hook_commands() {
+        let hooks = hooks(vec![hook("Write-Host 'hi'", true)], vec![hook("npm test", false)]);
+        let script = Hooks::wrap_powershell("claude", &hooks, Some("C:\\logs\\it's.log"));
+        assert!(script.contains("$log = 'C:\\logs\\it''s.log';"), "{}", script);
+        assert!(
+            script.ends_with(concat!(
+                "if ((Invoke-LauncherHook 'pre-launch' 'abort' 5 'Write-Host ''hi''')) { ",
+                "claude; $__launcherExit = $LASTEXITCODE; ",
+                "$null = (Invoke-LauncherHook 'post-exit' 'continue' 5 'npm test'); ",
+                "$global:LASTEXITCODE = $__launcherExit }"
+            )),
+            "{}",
+            script
+        );
+    }
+
+    #[test]
+    fn cmd_chains_hooks() {
+        let hooks = hooks(
+            vec![hook("git pull", true), hook("npm ci", false)],
+            vec![hook("npm test", false)],
+        );
+        assert_eq!(
+            Hooks::wrap_cmd("claude", &hooks),
+            "(git pull) && (npm ci || ver >nul) && (claude & (npm test || ver >nul))"
+        );
+    }
+}
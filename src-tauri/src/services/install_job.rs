@@ -0,0 +1,543 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::io::Write;
+use std::path::Path;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use tokio::process::Command;
+use tokio::sync::oneshot;
+
+use super::dependency_checker::{DependencyChecker, DependencyStatus};
+use super::uninstaller::INSTALL_SCRIPT_PREFIX;
+use super::ca_bundle::CaBundle;
+use super::logger::{LogLevel, Logger};
+
+/// Number of output lines kept per job for `list()`; the full stream goes out as events
+const OUTPUT_TAIL_LINES: usize = 200;
+
+/// How long to keep reading output after the script exits; a background process
+/// it started may hold the pipes open indefinitely
+const DRAIN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallTarget {
+    Nodejs,
+    Claude,
+    Gitbash,
+}
+
+impl InstallTarget {
+    fn key(&self) -> &'static str {
+        match self {
+            InstallTarget::Nodejs => "nodejs",
+            InstallTarget::Claude => "claude",
+            InstallTarget::Gitbash => "gitbash",
+        }
+    }
+
+    /// Re-run the dependency check for this target once a job has finished
+    pub async fn check(&self) -> DependencyStatus {
+        #[cfg(windows)]
+        DependencyChecker::refresh_system_path();
+
+        match self {
+            InstallTarget::Nodejs => DependencyChecker::check_nodejs_with_update(false).await,
+            InstallTarget::Claude => DependencyChecker::check_claude_with_update(false).await,
+            InstallTarget::Gitbash => DependencyChecker::check_gitbash_with_update(false).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallAction {
+    Install,
+    Update,
+    SwitchVersion,                       // Install an exact version, possibly older than the current one
+    Migrate,                             // Replace an npm install of Claude Code with the native one
+}
+
+/// Interpreter used to run a recipe script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptShell {
+    PowerShell,
+    Cmd,
+    Bash,
+}
+
+impl ScriptShell {
+    fn extension(&self) -> &'static str {
+        match self {
+            ScriptShell::PowerShell => "ps1",
+            ScriptShell::Cmd => "bat",
+            ScriptShell::Bash => "sh",
+        }
+    }
+}
+
+/// A non-interactive script that installs or updates one dependency
+#[derive(Debug, Clone)]
+pub struct InstallRecipe {
+    pub target: InstallTarget,
+    pub action: InstallAction,
+    pub shell: ScriptShell,
+    pub script: String,
+}
+
+impl InstallRecipe {
+    pub fn new(target: InstallTarget, action: InstallAction, shell: ScriptShell, script: String) -> Self {
+        Self { target, action, shell, script }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InstallJobState {
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallJobInfo {
+    pub id: String,
+    pub target: InstallTarget,
+    pub action: InstallAction,
+    pub state: InstallJobState,
+    pub started_at: u64,                 // Unix timestamp
+    pub finished_at: Option<u64>,
+    pub exit_code: Option<i32>,
+    pub error: Option<String>,           // Why the job could not run to completion
+    pub script_sha256: Option<String>,   // Checksum of the exact script that was executed
+    pub output_tail: Vec<String>,        // Last OUTPUT_TAIL_LINES lines of stdout/stderr
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputStream {
+    Stdout,
+    Stderr,
+}
+
+/// Progress notifications for a running job
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InstallJobEvent {
+    Output {
+        job_id: String,
+        stream: OutputStream,
+        line: String,
+    },
+    /// The job ended; `status` is the dependency check re-run afterwards
+    Finished {
+        job: InstallJobInfo,
+        status: Box<DependencyStatus>,
+    },
+}
+
+struct JobEntry {
+    info: InstallJobInfo,
+    tail: VecDeque<String>,
+    cancel: Option<oneshot::Sender<()>>,
+}
+
+fn jobs() -> &'static Mutex<HashMap<String, JobEntry>> {
+    static JOBS: OnceLock<Mutex<HashMap<String, JobEntry>>> = OnceLock::new();
+    JOBS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub struct InstallJobs;
+
+impl InstallJobs {
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn snapshot(entry: &JobEntry) -> InstallJobInfo {
+        InstallJobInfo {
+            output_tail: entry.tail.iter().cloned().collect(),
+            ..entry.info.clone()
+        }
+    }
+
+    /// Start running `recipe` in the background.
+    ///
+    /// `on_event` receives every output line and, last, a `Finished` event carrying the
+    /// refreshed dependency status. Only one job per target may run at a time.
+    pub fn start<F>(recipe: InstallRecipe, on_event: F) -> Result<InstallJobInfo, String>
+    where
+        F: Fn(InstallJobEvent) + Send + Sync + 'static,
+    {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+        let started_at = Self::now();
+        let id = format!("{}-{}", recipe.target.key(), NEXT_ID.fetch_add(1, Ordering::Relaxed));
+        let (cancel_tx, cancel_rx) = oneshot::channel();
+
+        let info = {
+            let mut jobs = jobs().lock().map_err(|e| format!("无法访问安装任务: {}", e))?;
+            if jobs
+                .values()
+                .any(|entry| entry.info.target == recipe.target && entry.info.state == InstallJobState::Running)
+            {
+                return Err(format!("{} 已有正在运行的安装任务", recipe.target.key()));
+            }
+
+            // Keep only the latest finished job per target
+            jobs.retain(|_, entry| entry.info.target != recipe.target);
+
+            let info = InstallJobInfo {
+                id: id.clone(),
+                target: recipe.target,
+                action: recipe.action,
+                state: InstallJobState::Running,
+                started_at,
+                finished_at: None,
+                exit_code: None,
+                error: None,
+                script_sha256: None,
+                output_tail: Vec::new(),
+            };
+            jobs.insert(
+                id.clone(),
+                JobEntry {
+                    info: info.clone(),
+                    tail: VecDeque::new(),
+                    cancel: Some(cancel_tx),
+                },
+            );
+            info
+        };
+
+        tokio::spawn(async move {
+            let on_event = Arc::new(on_event);
+            let outcome = Self::run(&id, &recipe, cancel_rx, on_event.clone()).await;
+
+            let (state, exit_code, error) = match outcome {
+                Ok(RunOutcome::Exited(Some(0))) => (InstallJobState::Succeeded, Some(0), None),
+                Ok(RunOutcome::Exited(code)) => (
+                    InstallJobState::Failed,
+                    code,
+                    Some(match code {
+                        Some(code) => format!("安装脚本退出码: {}", code),
+                        None => "安装脚本被终止".to_string(),
+                    }),
+                ),
+                Ok(RunOutcome::Cancelled) => (InstallJobState::Cancelled, None, None),
+                Err(e) => (InstallJobState::Failed, None, Some(e)),
+            };
+
+            Self::audit(serde_json::json!({
+                "event": "finished",
+                "job_id": id,
+                "state": state,
+                "exit_code": exit_code,
+            }));
+
+            let status = recipe.target.check().await;
+
+            let job = {
+                let mut jobs = match jobs().lock() {
+                    Ok(jobs) => jobs,
+                    Err(poisoned) => poisoned.into_inner(),
+                };
+                jobs.get_mut(&id).map(|entry| {
+                    entry.info.state = state;
+                    entry.info.finished_at = Some(Self::now());
+                    entry.info.exit_code = exit_code;
+                    entry.info.error = error.clone();
+                    entry.cancel = None;
+                    Self::snapshot(entry)
+                })
+            };
+
+            if let Some(job) = job {
+                on_event(InstallJobEvent::Finished { job, status: Box::new(status) });
+            }
+        });
+
+        Ok(info)
+    }
+
+    /// Stop a running job and its child processes
+    pub fn cancel(job_id: &str) -> Result<(), String> {
+        let mut jobs = jobs().lock().map_err(|e| format!("无法访问安装任务: {}", e))?;
+        let entry = jobs
+            .get_mut(job_id)
+            .ok_or_else(|| format!("安装任务不存在: {}", job_id))?;
+
+        match entry.cancel.take() {
+            Some(cancel) => {
+                let _ = cancel.send(());
+                Ok(())
+            }
+            None => Err("安装任务已结束".to_string()),
+        }
+    }
+
+    pub fn list() -> Vec<InstallJobInfo> {
+        let jobs = match jobs().lock() {
+            Ok(jobs) => jobs,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        let mut list: Vec<InstallJobInfo> = jobs.values().map(Self::snapshot).collect();
+        list.sort_by_key(|job| job.started_at);
+        list
+    }
+
+    fn record_line(job_id: &str, line: &str) {
+        if let Ok(mut jobs) = jobs().lock() {
+            if let Some(entry) = jobs.get_mut(job_id) {
+                if entry.tail.len() >= OUTPUT_TAIL_LINES {
+                    entry.tail.pop_front();
+                }
+                entry.tail.push_back(line.to_string());
+            }
+        }
+    }
+
+    async fn run<F>(
+        job_id: &str,
+        recipe: &InstallRecipe,
+        cancel_rx: oneshot::Receiver<()>,
+        on_event: Arc<F>,
+    ) -> Result<RunOutcome, String>
+    where
+        F: Fn(InstallJobEvent) + Send + Sync + 'static,
+    {
+        // The TempPath deletes the script when dropped, whatever the outcome
+        let (script_path, sha256) = Self::write_script(recipe)?;
+
+        if let Ok(mut jobs) = jobs().lock() {
+            if let Some(entry) = jobs.get_mut(job_id) {
+                entry.info.script_sha256 = Some(sha256.clone());
+            }
+        }
+        Self::audit(serde_json::json!({
+            "event": "started",
+            "job_id": job_id,
+            "target": recipe.target,
+            "action": recipe.action,
+            "shell": recipe.shell,
+            "script": script_path.to_string_lossy(),
+            "sha256": sha256,
+        }));
+
+        Self::run_script(job_id, recipe.shell, &script_path, cancel_rx, on_event).await
+    }
+
+    /// Write the recipe to a new, uniquely named temp file that only the current user can read.
+    ///
+    /// The file is created exclusively (never reusing or following an existing path) with
+    /// mode 0600 on Unix; on Windows it lives in the per-user %TEMP% which is not shared.
+    fn write_script(recipe: &InstallRecipe) -> Result<(tempfile::TempPath, String), String> {
+        // Windows PowerShell 5 reads BOM-less scripts in the ANSI code page
+        let contents = match recipe.shell {
+            ScriptShell::PowerShell => format!("\u{feff}[Console]::OutputEncoding = [System.Text.Encoding]::UTF8\n{}", recipe.script),
+            _ => recipe.script.clone(),
+        };
+
+        let mut file = tempfile::Builder::new()
+            .prefix(&format!("{}_{}_", INSTALL_SCRIPT_PREFIX, recipe.target.key()))
+            .suffix(&format!(".{}", recipe.shell.extension()))
+            .tempfile()
+            .map_err(|e| format!("无法创建安装脚本: {}", e))?;
+
+        file.write_all(contents.as_bytes())
+            .and_then(|_| file.flush())
+            .map_err(|e| format!("无法写入安装脚本: {}", e))?;
+
+        let sha256: String = Sha256::digest(contents.as_bytes())
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+
+        // Close our handle so cmd.exe/PowerShell can open the file; the path still owns deletion
+        Ok((file.into_temp_path(), sha256))
+    }
+
+    /// Record of every script the launcher executed; goes to the rotated, redacted launcher log
+    fn audit(record: serde_json::Value) {
+        let level = match record["state"].as_str() {
+            Some("failed") => LogLevel::Error,
+            _ => LogLevel::Info,
+        };
+        let message = format!("install job {}", record["event"].as_str().unwrap_or("event"));
+        Logger::log(level, "install", &message, record);
+    }
+
+    fn build_command(shell: ScriptShell, script_path: &Path) -> Command {
+        let mut command = match shell {
+            ScriptShell::PowerShell => {
+                let mut command = Command::new("powershell.exe");
+                command.args(["-NoProfile", "-NonInteractive", "-ExecutionPolicy", "Bypass", "-File"]);
+                command.arg(script_path);
+                command
+            }
+            ScriptShell::Cmd => {
+                let mut command = Command::new("cmd.exe");
+                command.args(["/d", "/c"]);
+                command.arg(script_path);
+                command
+            }
+            ScriptShell::Bash => {
+                let mut command = Command::new("bash");
+                command.arg(script_path);
+                command.env("PATH", DependencyChecker::search_path());
+                command
+            }
+        };
+
+        // npm and the native installer download through the same TLS-inspecting proxy as claude
+        if let Some(bundle) = CaBundle::effective(None) {
+            command.env("NODE_EXTRA_CA_CERTS", bundle.path.trim());
+        }
+
+        command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+
+        #[cfg(windows)]
+        {
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            command.creation_flags(CREATE_NO_WINDOW);
+        }
+
+        // Own process group so cancellation also reaches package managers spawned by the script
+        #[cfg(unix)]
+        command.process_group(0);
+
+        command
+    }
+
+    async fn run_script<F>(
+        job_id: &str,
+        shell: ScriptShell,
+        script_path: &Path,
+        cancel_rx: oneshot::Receiver<()>,
+        on_event: Arc<F>,
+    ) -> Result<RunOutcome, String>
+    where
+        F: Fn(InstallJobEvent) + Send + Sync + 'static,
+    {
+        let mut child = Self::build_command(shell, script_path)
+            .spawn()
+            .map_err(|e| format!("无法启动安装脚本: {}", e))?;
+
+        let stdout = child.stdout.take().map(|out| {
+            tokio::spawn(Self::pump(job_id.to_string(), OutputStream::Stdout, out, on_event.clone()))
+        });
+        let stderr = child.stderr.take().map(|err| {
+            tokio::spawn(Self::pump(job_id.to_string(), OutputStream::Stderr, err, on_event.clone()))
+        });
+
+        let outcome = tokio::select! {
+            status = child.wait() => {
+                let status = status.map_err(|e| format!("等待安装脚本失败: {}", e))?;
+                RunOutcome::Exited(status.code())
+            }
+            Ok(()) = cancel_rx => {
+                if let Some(pid) = child.id() {
+                    Self::kill_tree(pid).await;
+                }
+                let _ = child.kill().await;
+                RunOutcome::Cancelled
+            }
+        };
+
+        // Drain whatever is left in the pipes before reporting completion
+        let mut pumps: Vec<_> = [stdout, stderr].into_iter().flatten().collect();
+        let drained = tokio::time::timeout(DRAIN_TIMEOUT, async {
+            for pump in pumps.iter_mut() {
+                let _ = pump.await;
+            }
+        })
+        .await;
+        if drained.is_err() {
+            Logger::warn("install", format!("任务 {} 的脚本已退出，但输出管道仍被其子进程占用，停止读取输出", job_id));
+            for pump in &pumps {
+                pump.abort();
+            }
+        }
+
+        Ok(outcome)
+    }
+
+    /// Forward output line by line; for `\r` progress redraws only the final segment is kept
+    async fn pump<R, F>(job_id: String, stream: OutputStream, mut reader: R, on_event: Arc<F>)
+    where
+        R: AsyncRead + Unpin,
+        F: Fn(InstallJobEvent) + Send + Sync + 'static,
+    {
+        let mut pending: Vec<u8> = Vec::new();
+        let mut buf = [0u8; 4096];
+
+        let emit = |raw: &[u8]| {
+            let text = String::from_utf8_lossy(raw);
+            let line = text
+                .trim_end_matches('\r')
+                .rsplit('\r')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            Self::record_line(&job_id, &line);
+            on_event(InstallJobEvent::Output {
+                job_id: job_id.clone(),
+                stream,
+                line,
+            });
+        };
+
+        loop {
+            match reader.read(&mut buf).await {
+                Ok(0) | Err(_) => break,
+                Ok(n) => {
+                    pending.extend_from_slice(&buf[..n]);
+                    while let Some(pos) = pending.iter().position(|b| *b == b'\n') {
+                        let raw: Vec<u8> = pending.drain(..=pos).collect();
+                        emit(&raw[..raw.len() - 1]);
+                    }
+                }
+            }
+        }
+
+        if !pending.is_empty() {
+            emit(&pending);
+        }
+    }
+
+    async fn kill_tree(pid: u32) {
+        #[cfg(windows)]
+        {
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+            let _ = Command::new("taskkill")
+                .args(["/T", "/F", "/PID", &pid.to_string()])
+                .creation_flags(CREATE_NO_WINDOW)
+                .status()
+                .await;
+        }
+
+        #[cfg(unix)]
+        {
+            let _ = Command::new("kill")
+                .args(["-TERM", &format!("-{}", pid)])
+                .status()
+                .await;
+        }
+    }
+}
+
+enum RunOutcome {
+    Exited(Option<i32>),
+    Cancelled,
+}
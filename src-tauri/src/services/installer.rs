@@ -1,111 +1,266 @@
-use std::process::Command;
+use super::install_job::{InstallAction, InstallRecipe, InstallTarget, ScriptShell};
+use super::npm_registry::NpmRegistry;
+use super::config_storage::ConfigStorage;
+use super::offline_bundle::VerifiedPackage;
+use super::npm_prefix::NpmPrefix;
+use super::dependency_checker::{DependencyChecker, InstallMethod};
+use super::claude_native::{self, ClaudeNative};
 
 pub struct Installer;
 
 impl Installer {
-    pub fn install_nodejs() -> Result<(), String> {
+    pub fn nodejs_install_recipe() -> Result<InstallRecipe, String> {
         #[cfg(windows)]
         {
             let script = Self::generate_nodejs_install_script_windows();
-            Self::execute_powershell_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Nodejs, InstallAction::Install, ScriptShell::PowerShell, script))
         }
         #[cfg(target_os = "macos")]
         {
             let script = Self::generate_nodejs_install_script_macos();
-            Self::execute_terminal_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Nodejs, InstallAction::Install, ScriptShell::Bash, script))
         }
-        #[cfg(all(not(windows), not(target_os = "macos")))]
+        #[cfg(target_os = "linux")]
+        {
+            let script = Self::generate_nodejs_install_script_linux();
+            Ok(InstallRecipe::new(InstallTarget::Nodejs, InstallAction::Install, ScriptShell::Bash, script))
+        }
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
         {
             Err("不支持的操作系统".to_string())
         }
     }
 
-    pub fn update_nodejs() -> Result<(), String> {
+    pub fn nodejs_update_recipe() -> Result<InstallRecipe, String> {
         #[cfg(windows)]
         {
             let script = Self::generate_nodejs_update_script_windows();
-            Self::execute_powershell_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Nodejs, InstallAction::Update, ScriptShell::PowerShell, script))
         }
         #[cfg(target_os = "macos")]
         {
             let script = Self::generate_nodejs_update_script_macos();
-            Self::execute_terminal_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Nodejs, InstallAction::Update, ScriptShell::Bash, script))
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let script = Self::generate_nodejs_update_script_linux();
+            Ok(InstallRecipe::new(InstallTarget::Nodejs, InstallAction::Update, ScriptShell::Bash, script))
         }
-        #[cfg(all(not(windows), not(target_os = "macos")))]
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
         {
             Err("不支持的操作系统".to_string())
         }
     }
 
-    pub fn install_claude() -> Result<(), String> {
+    /// Fresh install with the given method; by default npm when Node.js is available, native otherwise
+    pub fn claude_install_recipe(method: Option<InstallMethod>) -> Result<InstallRecipe, String> {
+        let native = match method {
+            Some(InstallMethod::Native) => true,
+            Some(InstallMethod::Npm) => false,
+            Some(other) => return Err(format!("不支持的 Claude Code 安装方式: {:?}", other)),
+            None => DependencyChecker::resolve_binary("npm").is_none(),
+        };
+
+        if native {
+            Self::claude_native_recipe(InstallAction::Install, "latest", "Installing Claude Code (native installer)...")
+        } else {
+            Self::claude_npm_recipe(InstallAction::Install, "@anthropic-ai/claude-code", "Installing Claude Code...")
+        }
+    }
+
+    /// Whether the `claude` on PATH came from the native installer, so updates must go through it too
+    fn claude_is_native() -> bool {
+        DependencyChecker::resolve_binary("claude")
+            .map(|path| DependencyChecker::detect_install_method(&path) == InstallMethod::Native)
+            .unwrap_or(false)
+    }
+
+    /// Update to latest, or to the pinned version when one is configured
+    pub fn claude_update_recipe() -> Result<InstallRecipe, String> {
+        let pinned = ConfigStorage::get_launcher_settings()
+            .ok()
+            .and_then(|settings| settings.claude_pinned_version);
+        match pinned {
+            Some(version) => Self::claude_version_recipe(&version),
+            None if Self::claude_is_native() => {
+                Self::claude_native_recipe(InstallAction::Update, "latest", "Updating Claude Code (native installer)...")
+            }
+            None => Self::claude_npm_recipe(InstallAction::Update, "@anthropic-ai/claude-code@latest", "Updating Claude Code..."),
+        }
+    }
+
+    /// Install an exact Claude Code version (upgrade, downgrade or rollback)
+    pub fn claude_version_recipe(version: &str) -> Result<InstallRecipe, String> {
+        let version = version.trim().trim_start_matches('v');
+        if !NpmRegistry::is_valid_version(version) {
+            return Err(format!("无效的 Claude Code 版本号: {}", version));
+        }
+
+        let title = format!("Installing Claude Code {}...", version);
+        if Self::claude_is_native() {
+            return Self::claude_native_recipe(InstallAction::SwitchVersion, version, &title);
+        }
+
+        let package_spec = format!("@anthropic-ai/claude-code@{}", version);
+        Self::claude_npm_recipe(InstallAction::SwitchVersion, &package_spec, &title)
+    }
+
+    /// Install the native build, verify it runs, then remove the global npm package
+    pub fn claude_migrate_recipe() -> Result<InstallRecipe, String> {
+        let installations = ClaudeNative::installations();
+        if installations.npm_path.is_none() {
+            return Err("未找到通过 npm 安装的 Claude Code,无需迁移".to_string());
+        }
+
         #[cfg(windows)]
         {
-            let script = Self::generate_claude_install_script_windows();
-            Self::execute_cmd_script(&script)
+            let script = Self::generate_claude_native_script_windows("latest", "Migrating Claude Code to the native installer...", true);
+            Ok(InstallRecipe::new(InstallTarget::Claude, InstallAction::Migrate, ScriptShell::PowerShell, script))
         }
-        #[cfg(target_os = "macos")]
+        #[cfg(not(windows))]
         {
-            let script = Self::generate_claude_install_script_macos();
-            Self::execute_terminal_script(&script)
+            let script = Self::generate_claude_native_script_unix("latest", "Migrating Claude Code to the native installer...", true);
+            Ok(InstallRecipe::new(InstallTarget::Claude, InstallAction::Migrate, ScriptShell::Bash, script))
         }
-        #[cfg(all(not(windows), not(target_os = "macos")))]
+    }
+
+    /// `target` is `latest`, `stable` or an exact version, as accepted by the native installer
+    fn claude_native_recipe(action: InstallAction, target: &str, title: &str) -> Result<InstallRecipe, String> {
+        #[cfg(windows)]
         {
-            Err("不支持的操作系统".to_string())
+            let script = Self::generate_claude_native_script_windows(target, title, false);
+            Ok(InstallRecipe::new(InstallTarget::Claude, action, ScriptShell::PowerShell, script))
+        }
+        #[cfg(not(windows))]
+        {
+            let script = Self::generate_claude_native_script_unix(target, title, false);
+            Ok(InstallRecipe::new(InstallTarget::Claude, action, ScriptShell::Bash, script))
+        }
+    }
+
+    /// Install a verified local tarball without touching the network
+    pub fn claude_offline_recipe(package: &VerifiedPackage) -> Result<InstallRecipe, String> {
+        // The version ends up in the script's title; manifest entries are not trusted to be well-formed
+        if !NpmRegistry::is_valid_version(&package.version) {
+            return Err(format!("离线安装包的版本号无效: {}", package.version));
         }
+
+        // cmd expands %VAR% even inside quotes
+        #[cfg(windows)]
+        let tarball = format!("\"{}\"", package.tarball.replace('"', "").replace('%', "%%"));
+        #[cfg(not(windows))]
+        let tarball = format!("'{}'", package.tarball.replace('\'', "'\\''"));
+
+        let package_spec = format!("{} --offline --no-audit --no-fund", tarball);
+        // The tarball path is user-picked and only quoted for the npm argument, so keep it out of `echo`
+        let title = format!("Installing Claude Code {} from an offline package...", package.version);
+        Self::claude_npm_recipe(InstallAction::SwitchVersion, &package_spec, &title)
     }
 
-    pub fn update_claude() -> Result<(), String> {
+    fn claude_npm_recipe(action: InstallAction, package_spec: &str, title: &str) -> Result<InstallRecipe, String> {
+        // A global install into an admin-owned prefix would fail (or prompt for sudo) halfway through
+        let prefix = NpmPrefix::status();
+        if let (Some(ref current), false) = (&prefix.prefix, prefix.writable) {
+            return Err(format!(
+                "npm 全局目录 {} 不可写,请先配置用户级 npm 目录 ({})",
+                current, prefix.user_prefix
+            ));
+        }
+
         #[cfg(windows)]
         {
-            let script = Self::generate_claude_update_script_windows();
-            Self::execute_cmd_script(&script)
+            let script = Self::generate_claude_script_windows(package_spec, title);
+            Ok(InstallRecipe::new(InstallTarget::Claude, action, ScriptShell::Cmd, script))
         }
         #[cfg(target_os = "macos")]
         {
-            let script = Self::generate_claude_update_script_macos();
-            Self::execute_terminal_script(&script)
+            let script = Self::generate_claude_script_macos(package_spec, title);
+            Ok(InstallRecipe::new(InstallTarget::Claude, action, ScriptShell::Bash, script))
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let script = Self::generate_claude_script_linux(package_spec, title);
+            Ok(InstallRecipe::new(InstallTarget::Claude, action, ScriptShell::Bash, script))
         }
-        #[cfg(all(not(windows), not(target_os = "macos")))]
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
         {
+            let _ = (action, package_spec, title);
             Err("不支持的操作系统".to_string())
         }
     }
 
-    pub fn install_gitbash() -> Result<(), String> {
+    pub fn gitbash_install_recipe() -> Result<InstallRecipe, String> {
         #[cfg(windows)]
         {
             let script = Self::generate_gitbash_install_script_windows();
-            Self::execute_powershell_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Gitbash, InstallAction::Install, ScriptShell::PowerShell, script))
         }
         #[cfg(target_os = "macos")]
         {
             let script = Self::generate_git_install_script_macos();
-            Self::execute_terminal_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Gitbash, InstallAction::Install, ScriptShell::Bash, script))
         }
-        #[cfg(all(not(windows), not(target_os = "macos")))]
+        #[cfg(target_os = "linux")]
+        {
+            let script = Self::generate_git_script_linux(false);
+            Ok(InstallRecipe::new(InstallTarget::Gitbash, InstallAction::Install, ScriptShell::Bash, script))
+        }
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
         {
             Err("不支持的操作系统".to_string())
         }
     }
 
-    pub fn update_gitbash() -> Result<(), String> {
+    pub fn gitbash_update_recipe() -> Result<InstallRecipe, String> {
         #[cfg(windows)]
         {
             let script = Self::generate_gitbash_update_script_windows();
-            Self::execute_powershell_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Gitbash, InstallAction::Update, ScriptShell::PowerShell, script))
         }
         #[cfg(target_os = "macos")]
         {
             let script = Self::generate_git_update_script_macos();
-            Self::execute_terminal_script(&script)
+            Ok(InstallRecipe::new(InstallTarget::Gitbash, InstallAction::Update, ScriptShell::Bash, script))
+        }
+        #[cfg(target_os = "linux")]
+        {
+            let script = Self::generate_git_script_linux(true);
+            Ok(InstallRecipe::new(InstallTarget::Gitbash, InstallAction::Update, ScriptShell::Bash, script))
         }
-        #[cfg(all(not(windows), not(target_os = "macos")))]
+        #[cfg(not(any(windows, target_os = "macos", target_os = "linux")))]
         {
             Err("不支持的操作系统".to_string())
         }
     }
 
+    // ==================== npm Registry ====================
+
+    /// `--registry` flag for npm when a custom registry or mirror is configured
+    #[cfg(windows)]
+    fn npm_registry_flag_cmd() -> String {
+        let registry = NpmRegistry::resolve();
+        if registry.is_default() {
+            String::new()
+        } else {
+            format!(" --registry \"{}\"", registry.url.replace('"', ""))
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn npm_registry_flag_bash() -> String {
+        let registry = NpmRegistry::resolve();
+        if registry.is_default() {
+            String::new()
+        } else {
+            format!(" --registry '{}'", registry.url.replace('\'', "'\\''"))
+        }
+    }
+
     // ==================== Windows Scripts ====================
+    //
+    // Recipes run without a console window and with stdin closed, so they must never
+    // prompt; the exit code of the script is reported back as the job result.
 
     #[cfg(windows)]
     fn generate_nodejs_install_script_windows() -> String {
@@ -118,28 +273,25 @@ if (-not $wingetCmd) {
     Write-Host '✗ winget 不可用' -ForegroundColor Red
     Write-Host '正在打开 Node.js 下载页面...' -ForegroundColor Yellow
     Start-Process 'https://nodejs.org/en/download/'
-    Write-Host ''
-    Write-Host '按任意键关闭此窗口...'
-    $null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
-    exit
+    exit 1
 }
 
-Write-Host '提示:安装过程中会显示进度条,请耐心等待' -ForegroundColor Yellow
+Write-Host '提示:安装过程可能需要几分钟,请耐心等待' -ForegroundColor Yellow
 Write-Host ''
-winget install OpenJS.NodeJS.LTS
+winget install OpenJS.NodeJS.LTS --accept-source-agreements --accept-package-agreements
 $wingetExitCode = $LASTEXITCODE
 Write-Host ''
 if ($wingetExitCode -eq 0) {
     Write-Host '✓ 安装成功完成!' -ForegroundColor Green
+    exit 0
 } elseif ($wingetExitCode -eq -1978335189 -or $wingetExitCode -eq -1978335212) {
     Write-Host 'ℹ Node.js 已安装' -ForegroundColor Cyan
+    exit 0
 } else {
-    Write-Host "✗ 安装失败! (错误代码: $wingetExitCode)" -ForegroundColor Red
-    winget install OpenJS.NodeJS.LTS --force
+    Write-Host "✗ 安装失败! (错误代码: $wingetExitCode),尝试强制重新安装..." -ForegroundColor Red
+    winget install OpenJS.NodeJS.LTS --force --accept-source-agreements --accept-package-agreements
+    exit $LASTEXITCODE
 }
-Write-Host ''
-Write-Host '按任意键关闭此窗口...'
-$null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
 "#.to_string()
     }
 
@@ -153,65 +305,85 @@ $wingetCmd = Get-Command winget -ErrorAction SilentlyContinue
 if (-not $wingetCmd) {
     Write-Host '✗ winget 不可用' -ForegroundColor Red
     Start-Process 'https://nodejs.org/en/download/'
-    Write-Host '按任意键关闭此窗口...'
-    $null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
-    exit
+    exit 1
 }
 
-winget upgrade OpenJS.NodeJS.LTS
-Write-Host ''
-Write-Host '按任意键关闭此窗口...'
-$null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
+winget upgrade OpenJS.NodeJS.LTS --accept-source-agreements --accept-package-agreements
+exit $LASTEXITCODE
 "#.to_string()
     }
 
     #[cfg(windows)]
-    fn generate_claude_install_script_windows() -> String {
-        r#"@echo off
-echo Installing Claude Code...
+    fn generate_claude_script_windows(package_spec: &str, title: &str) -> String {
+        let registry_flag = Self::npm_registry_flag_cmd();
+        format!(r#"@echo off
+chcp 65001 >nul
+echo {title}
 echo.
+set "NPM_CMD=npm"
 where npm >nul 2>nul
-if %errorlevel%==0 (
-    npm install -g @anthropic-ai/claude-code
-) else (
+if errorlevel 1 (
     if exist "C:\Program Files\nodejs\npm.cmd" (
-        "C:\Program Files\nodejs\npm.cmd" install -g @anthropic-ai/claude-code
+        set "NPM_CMD=C:\Program Files\nodejs\npm.cmd"
     ) else (
         echo npm not found. Please make sure Node.js is installed.
-        pause
         exit /b 1
     )
 )
-if %errorlevel%==0 (
-    echo.
-    echo [OK] Installation completed!
+call "%NPM_CMD%" install -g {package_spec}{registry_flag}
+set "NPM_EXIT=%errorlevel%"
+echo.
+if "%NPM_EXIT%"=="0" (
+    echo [OK] Completed!
 ) else (
-    echo.
-    echo [FAILED] Installation failed!
+    echo [FAILED] npm exited with code %NPM_EXIT%
 )
-echo.
-pause"#.to_string()
+exit /b %NPM_EXIT%
+"#)
     }
 
     #[cfg(windows)]
-    fn generate_claude_update_script_windows() -> String {
-        r#"@echo off
-echo Updating Claude Code...
-echo.
-where npm >nul 2>nul
-if %errorlevel%==0 (
-    npm install -g @anthropic-ai/claude-code@latest
-) else (
-    if exist "C:\Program Files\nodejs\npm.cmd" (
-        "C:\Program Files\nodejs\npm.cmd" install -g @anthropic-ai/claude-code@latest
-    ) else (
-        echo npm not found.
-        pause
-        exit /b 1
-    )
-)
-echo.
-pause"#.to_string()
+    fn generate_claude_native_script_windows(target: &str, title: &str, migrate: bool) -> String {
+        let migrate_step = if migrate {
+            r#"
+Write-Host ''
+Write-Host '正在移除 npm 全局安装的 Claude Code...' -ForegroundColor Green
+npm uninstall -g @anthropic-ai/claude-code
+if ($LASTEXITCODE -ne 0) {
+    Write-Host '⚠ npm 卸载失败,请手动执行: npm uninstall -g @anthropic-ai/claude-code' -ForegroundColor Yellow
+}
+"#
+        } else {
+            ""
+        };
+
+        format!(r#"
+Write-Host '{title}' -ForegroundColor Green
+Write-Host ''
+
+try {{
+    $installer = Invoke-RestMethod '{url}'
+    & ([scriptblock]::Create($installer)) {target}
+}} catch {{
+    Write-Host "✗ 原生安装器运行失败: $_" -ForegroundColor Red
+    exit 1
+}}
+
+$claudeExe = Join-Path $env:USERPROFILE '.local\bin\claude.exe'
+if (-not (Test-Path $claudeExe)) {{
+    Write-Host "✗ 未找到 $claudeExe" -ForegroundColor Red
+    exit 1
+}}
+& $claudeExe --version
+if ($LASTEXITCODE -ne 0) {{
+    Write-Host '✗ 原生安装的 claude 无法运行' -ForegroundColor Red
+    exit 1
+}}
+{migrate_step}
+Write-Host ''
+Write-Host '✓ Completed!' -ForegroundColor Green
+exit 0
+"#, url = claude_native::INSTALL_PS1_URL)
     }
 
     #[cfg(windows)]
@@ -224,15 +396,11 @@ $wingetCmd = Get-Command winget -ErrorAction SilentlyContinue
 if (-not $wingetCmd) {
     Write-Host '✗ winget 不可用' -ForegroundColor Red
     Start-Process 'https://git-scm.com/download/windows'
-    Write-Host '按任意键关闭此窗口...'
-    $null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
-    exit
+    exit 1
 }
 
-winget install --id Git.Git -e --source winget
-Write-Host ''
-Write-Host '按任意键关闭此窗口...'
-$null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
+winget install --id Git.Git -e --source winget --accept-source-agreements --accept-package-agreements
+exit $LASTEXITCODE
 "#.to_string()
     }
 
@@ -246,15 +414,11 @@ $wingetCmd = Get-Command winget -ErrorAction SilentlyContinue
 if (-not $wingetCmd) {
     Write-Host '✗ winget 不可用' -ForegroundColor Red
     Start-Process 'https://git-scm.com/download/windows'
-    Write-Host '按任意键关闭此窗口...'
-    $null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
-    exit
+    exit 1
 }
 
-winget upgrade --id Git.Git -e --source winget
-Write-Host ''
-Write-Host '按任意键关闭此窗口...'
-$null = $Host.UI.RawUI.ReadKey('NoEcho,IncludeKeyDown')
+winget upgrade --id Git.Git -e --source winget --accept-source-agreements --accept-package-agreements
+exit $LASTEXITCODE
 "#.to_string()
     }
 
@@ -270,7 +434,15 @@ echo ""
 if ! command -v brew &> /dev/null; then
     echo "✗ Homebrew 未安装"
     echo "正在安装 Homebrew..."
-    /bin/bash -c "$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)"
+    NONINTERACTIVE=1 /bin/bash -c "$(curl -fsSL https://raw.githubusercontent.com/Homebrew/install/HEAD/install.sh)"
+    if [ -x /opt/homebrew/bin/brew ]; then
+        eval "$(/opt/homebrew/bin/brew shellenv)"
+    elif [ -x /usr/local/bin/brew ]; then
+        eval "$(/usr/local/bin/brew shellenv)"
+    else
+        echo "✗ Homebrew 安装失败,请在终端中手动安装后重试"
+        exit 1
+    fi
 fi
 
 echo "使用 Homebrew 安装 Node.js..."
@@ -283,10 +455,8 @@ if [ $? -eq 0 ]; then
 else
     echo ""
     echo "✗ 安装失败!"
+    exit 1
 fi
-
-echo ""
-read -p "按回车键关闭此窗口..."
 "#.to_string()
     }
 
@@ -301,61 +471,37 @@ if ! command -v brew &> /dev/null; then
     exit 1
 fi
 
-brew upgrade node
+brew upgrade node || exit 1
 
 echo ""
 echo "✓ 更新完成!"
 node --version
-
-read -p "按回车键关闭此窗口..."
 "#.to_string()
     }
 
     #[cfg(target_os = "macos")]
-    fn generate_claude_install_script_macos() -> String {
-        r#"
-echo "Installing Claude Code..."
+    fn generate_claude_script_macos(package_spec: &str, title: &str) -> String {
+        let registry_flag = Self::npm_registry_flag_bash();
+        format!(r#"
+echo "{title}"
 echo ""
 
 if ! command -v npm &> /dev/null; then
     echo "✗ npm not found. Please install Node.js first."
-    read -p "Press Enter to close..."
     exit 1
 fi
 
-npm install -g @anthropic-ai/claude-code
+npm install -g {package_spec}{registry_flag}
+STATUS=$?
 
-if [ $? -eq 0 ]; then
-    echo ""
-    echo "✓ Installation completed!"
-else
-    echo ""
-    echo "✗ Installation failed!"
-fi
-
-read -p "Press Enter to close..."
-"#.to_string()
-    }
-
-    #[cfg(target_os = "macos")]
-    fn generate_claude_update_script_macos() -> String {
-        r#"
-echo "Updating Claude Code..."
 echo ""
-
-if ! command -v npm &> /dev/null; then
-    echo "✗ npm not found."
-    read -p "Press Enter to close..."
-    exit 1
+if [ $STATUS -eq 0 ]; then
+    echo "✓ Completed!"
+else
+    echo "✗ npm exited with code $STATUS"
 fi
-
-npm install -g @anthropic-ai/claude-code@latest
-
-echo ""
-echo "✓ Update completed!"
-
-read -p "Press Enter to close..."
-"#.to_string()
+exit $STATUS
+"#)
     }
 
     #[cfg(target_os = "macos")]
@@ -368,21 +514,17 @@ echo ""
 if command -v git &> /dev/null; then
     echo "Git 已安装:"
     git --version
-    read -p "按回车键关闭此窗口..."
     exit 0
 fi
 
 # Try Homebrew
 if command -v brew &> /dev/null; then
-    brew install git
+    brew install git || exit 1
 else
-    # Install Xcode Command Line Tools
-    echo "正在安装 Xcode Command Line Tools..."
+    # Install Xcode Command Line Tools (opens the system installer dialog)
+    echo "正在安装 Xcode Command Line Tools,请在弹出的系统对话框中完成安装..."
     xcode-select --install
 fi
-
-echo ""
-read -p "按回车键关闭此窗口..."
 "#.to_string()
     }
 
@@ -393,91 +535,251 @@ echo "正在更新 Git..."
 echo ""
 
 if command -v brew &> /dev/null; then
-    brew upgrade git
+    brew upgrade git || exit 1
     echo ""
     echo "✓ 更新完成!"
     git --version
 else
     echo "请使用 Homebrew 管理 Git 更新"
+    exit 1
 fi
-
-read -p "按回车键关闭此窗口..."
 "#.to_string()
     }
 
-    // ==================== Execution Functions ====================
+    // ==================== Native Installer (macOS / Linux) ====================
 
-    #[cfg(windows)]
-    fn execute_powershell_script(script: &str) -> Result<(), String> {
-        use std::os::windows::process::CommandExt;
-        const CREATE_NEW_CONSOLE: u32 = 0x00000010;
-
-        Command::new("powershell")
-            .arg("-Command")
-            .arg(script)
-            .creation_flags(CREATE_NEW_CONSOLE)
-            .spawn()
-            .map_err(|e| format!("无法启动PowerShell: {}", e))?;
-
-        Ok(())
+    #[cfg(not(windows))]
+    fn generate_claude_native_script_unix(target: &str, title: &str, migrate: bool) -> String {
+        let migrate_step = if migrate {
+            r#"
+if [ $STATUS -eq 0 ]; then
+    echo ""
+    echo "正在移除 npm 全局安装的 Claude Code..."
+    npm uninstall -g @anthropic-ai/claude-code || echo "⚠ npm 卸载失败,请手动执行: npm uninstall -g @anthropic-ai/claude-code"
+fi
+"#
+        } else {
+            ""
+        };
+
+        format!(r#"
+echo "{title}"
+echo ""
+
+if ! command -v curl &> /dev/null; then
+    echo "✗ 未找到 curl,无法下载 Claude Code 原生安装器"
+    exit 1
+fi
+
+set -o pipefail
+STATUS=0
+curl -fsSL {url} | bash -s -- {target} || STATUS=1
+
+if [ $STATUS -eq 0 ] && ! "$HOME/.local/bin/claude" --version; then
+    echo "✗ 原生安装的 claude 无法运行"
+    STATUS=1
+fi
+{migrate_step}
+echo ""
+if [ $STATUS -eq 0 ]; then
+    echo "✓ Completed!"
+else
+    echo "✗ Failed!"
+fi
+exit $STATUS
+"#, url = claude_native::INSTALL_SH_URL)
     }
 
-    #[cfg(windows)]
-    fn execute_cmd_script(script: &str) -> Result<(), String> {
-        use std::os::windows::process::CommandExt;
-        use std::io::Write;
-        const CREATE_NEW_CONSOLE: u32 = 0x00000010;
-
-        let temp_dir = std::env::temp_dir();
-        let batch_file = temp_dir.join("claude_install.bat");
-
-        let mut file = std::fs::File::create(&batch_file)
-            .map_err(|e| format!("无法创建临时批处理文件: {}", e))?;
-        file.write_all(script.as_bytes())
-            .map_err(|e| format!("无法写入批处理文件: {}", e))?;
-
-        Command::new("cmd")
-            .args(&["/k", batch_file.to_str().unwrap()])
-            .creation_flags(CREATE_NEW_CONSOLE)
-            .spawn()
-            .map_err(|e| format!("无法启动CMD: {}", e))?;
-
-        Ok(())
+    // ==================== Linux Scripts ====================
+
+    /// Shared prelude: pick a privilege helper that works without a controlling TTY
+    #[cfg(target_os = "linux")]
+    const LINUX_SCRIPT_PRELUDE: &'static str = r#"
+if [ "$(id -u)" -eq 0 ]; then
+    SUDO=""
+elif command -v sudo &> /dev/null && sudo -n true 2> /dev/null; then
+    SUDO="sudo"
+elif command -v pkexec &> /dev/null && [ -n "$DISPLAY$WAYLAND_DISPLAY" ]; then
+    SUDO="pkexec"
+else
+    SUDO="none"
+fi
+
+detect_package_manager() {
+    for pm in apt-get dnf pacman zypper; do
+        if command -v "$pm" &> /dev/null; then
+            echo "$pm"
+            return
+        fi
+    done
+    echo "none"
+}
+"#;
+
+    #[cfg(target_os = "linux")]
+    fn generate_nodejs_install_script_linux() -> String {
+        format!(r#"{prelude}
+echo "正在安装 Node.js..."
+echo ""
+
+node_is_recent() {{
+    command -v node &> /dev/null && node -e 'process.exit(parseInt(process.versions.node, 10) >= 18 ? 0 : 1)'
+}}
+
+install_with_nvm() {{
+    echo "使用 nvm 在用户目录安装 Node.js LTS (无需管理员权限)..."
+    export NVM_DIR="$HOME/.nvm"
+    if [ ! -s "$NVM_DIR/nvm.sh" ]; then
+        curl -fsSL https://raw.githubusercontent.com/nvm-sh/nvm/v0.40.1/install.sh | bash || return 1
+    fi
+    . "$NVM_DIR/nvm.sh"
+    nvm install --lts && nvm alias default 'lts/*'
+}}
+
+PM="$(detect_package_manager)"
+if [ "$SUDO" != "none" ] && [ "$PM" != "none" ]; then
+    echo "使用 $PM 安装 Node.js..."
+    case "$PM" in
+        apt-get) $SUDO apt-get update && $SUDO apt-get install -y nodejs npm ;;
+        dnf)     $SUDO dnf install -y nodejs npm ;;
+        pacman)  $SUDO pacman -S --noconfirm --needed nodejs npm ;;
+        zypper)  $SUDO zypper --non-interactive install nodejs-default npm-default ;;
+    esac
+fi
+
+if ! node_is_recent; then
+    echo ""
+    echo "系统包管理器未提供 Node.js 18+,改用版本管理器安装"
+    install_with_nvm
+fi
+
+STATUS=1
+if node_is_recent || {{ [ -s "$HOME/.nvm/nvm.sh" ] && . "$HOME/.nvm/nvm.sh" && node_is_recent; }}; then
+    echo ""
+    echo "✓ 安装成功完成!"
+    node --version
+    STATUS=0
+else
+    echo ""
+    echo "✗ 安装失败!"
+fi
+
+exit $STATUS
+"#, prelude = Self::LINUX_SCRIPT_PRELUDE)
     }
 
-    #[cfg(target_os = "macos")]
-    fn execute_terminal_script(script: &str) -> Result<(), String> {
-        use std::io::Write;
-
-        // Create temp script file
-        let temp_dir = std::env::temp_dir();
-        let script_file = temp_dir.join("claude_install.sh");
-
-        let mut file = std::fs::File::create(&script_file)
-            .map_err(|e| format!("无法创建临时脚本文件: {}", e))?;
-        file.write_all(script.as_bytes())
-            .map_err(|e| format!("无法写入脚本文件: {}", e))?;
-
-        // Make executable
-        Command::new("chmod")
-            .args(&["+x", script_file.to_str().unwrap()])
-            .output()
-            .map_err(|e| format!("无法设置执行权限: {}", e))?;
-
-        // Open in Terminal.app
-        let apple_script = format!(
-            r#"tell application "Terminal"
-                activate
-                do script "{}"
-            end tell"#,
-            script_file.display()
-        );
-
-        Command::new("osascript")
-            .args(&["-e", &apple_script])
-            .spawn()
-            .map_err(|e| format!("无法启动Terminal: {}", e))?;
-
-        Ok(())
+    #[cfg(target_os = "linux")]
+    fn generate_nodejs_update_script_linux() -> String {
+        format!(r#"{prelude}
+echo "正在更新 Node.js..."
+echo ""
+
+STATUS=0
+NODE_PATH="$(command -v node)"
+if [ -s "$HOME/.nvm/nvm.sh" ] && {{ [ -z "$NODE_PATH" ] || echo "$NODE_PATH" | grep -q "/.nvm/"; }}; then
+    . "$HOME/.nvm/nvm.sh"
+    nvm install --lts --reinstall-packages-from=current && nvm alias default 'lts/*' || STATUS=1
+else
+    PM="$(detect_package_manager)"
+    if [ "$SUDO" = "none" ] || [ "$PM" = "none" ]; then
+        echo "✗ 无法获取管理员权限或未找到包管理器"
+        STATUS=1
+    else
+        case "$PM" in
+            apt-get) $SUDO apt-get update && $SUDO apt-get install -y --only-upgrade nodejs npm || STATUS=1 ;;
+            dnf)     $SUDO dnf upgrade -y nodejs npm || STATUS=1 ;;
+            pacman)  $SUDO pacman -S --noconfirm nodejs npm || STATUS=1 ;;
+            zypper)  $SUDO zypper --non-interactive update nodejs-default npm-default || STATUS=1 ;;
+        esac
+    fi
+fi
+
+echo ""
+if [ $STATUS -eq 0 ]; then
+    echo "✓ 更新完成!"
+    node --version
+else
+    echo "✗ 更新失败!"
+fi
+
+exit $STATUS
+"#, prelude = Self::LINUX_SCRIPT_PRELUDE)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn generate_claude_script_linux(package_spec: &str, title: &str) -> String {
+        let registry_flag = Self::npm_registry_flag_bash();
+        format!(r#"
+echo "{title}"
+echo ""
+
+if ! command -v npm &> /dev/null; then
+    echo "✗ npm not found. Please install Node.js first, or use the native installer."
+    exit 1
+fi
+
+STATUS=0
+npm install -g {package_spec}{registry_flag} || STATUS=1
+
+echo ""
+if [ $STATUS -eq 0 ]; then
+    echo "✓ Installation completed!"
+else
+    echo "✗ Installation failed!"
+fi
+
+exit $STATUS
+"#)
+    }
+
+    #[cfg(target_os = "linux")]
+    fn generate_git_script_linux(upgrade: bool) -> String {
+        let (apt, dnf, pacman, zypper) = if upgrade {
+            (
+                "$SUDO apt-get update && $SUDO apt-get install -y --only-upgrade git",
+                "$SUDO dnf upgrade -y git",
+                "$SUDO pacman -S --noconfirm git",
+                "$SUDO zypper --non-interactive update git",
+            )
+        } else {
+            (
+                "$SUDO apt-get update && $SUDO apt-get install -y git",
+                "$SUDO dnf install -y git",
+                "$SUDO pacman -S --noconfirm --needed git",
+                "$SUDO zypper --non-interactive install git",
+            )
+        };
+
+        format!(r#"{prelude}
+echo "正在{action} Git..."
+echo ""
+
+STATUS=0
+PM="$(detect_package_manager)"
+if [ "$SUDO" = "none" ]; then
+    echo "✗ 需要管理员权限 (sudo 或 pkexec) 才能{action} Git"
+    STATUS=1
+elif [ "$PM" = "none" ]; then
+    echo "✗ 未找到支持的包管理器 (apt, dnf, pacman, zypper)"
+    STATUS=1
+else
+    case "$PM" in
+        apt-get) {apt} || STATUS=1 ;;
+        dnf)     {dnf} || STATUS=1 ;;
+        pacman)  {pacman} || STATUS=1 ;;
+        zypper)  {zypper} || STATUS=1 ;;
+    esac
+fi
+
+echo ""
+if [ $STATUS -eq 0 ]; then
+    echo "✓ 完成!"
+    git --version
+else
+    echo "✗ 失败!"
+fi
+
+exit $STATUS
+"#, prelude = Self::LINUX_SCRIPT_PRELUDE, action = if upgrade { "更新" } else { "安装" })
     }
 }
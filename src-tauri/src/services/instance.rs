@@ -0,0 +1,233 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use super::logger::Logger;
+
+/// How long a second instance waits for the running one to connect and answer
+const CONNECT_TIMEOUT: Duration = Duration::from_millis(500);
+const REPLY_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long to wait for an instance that holds the lock but is not listening yet
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(10);
+const STARTUP_POLL: Duration = Duration::from_millis(100);
+
+/// Where the running instance can be reached, written next to config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InstanceFile {
+    port: u16,
+    token: String,   // Connections without it are dropped, so other local users cannot drive the launcher
+    pid: u32,
+}
+
+/// Arguments of a second invocation, passed to the running instance
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ForwardedArgs {
+    token: String,
+    pub args: Vec<String>,
+    pub cwd: String,     // Relative paths in `args` are relative to this
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ForwardReply {
+    pub exit_code: i32,
+    pub stdout: String,
+    pub stderr: String,
+}
+
+/// Keeps a single launcher GUI running.
+///
+/// The instance holds an exclusive OS lock on `instance.lock` for its whole lifetime, so
+/// two launchers started at once cannot both become it. It listens on a loopback port
+/// recorded in `instance.json`; later invocations send it their arguments (deep-link
+/// URLs, config-changing subcommands) and exit with its reply instead of opening a
+/// second window.
+pub struct SingleInstance;
+
+impl SingleInstance {
+    fn config_dir() -> Result<PathBuf, String> {
+        let dir = dirs::config_dir()
+            .ok_or("无法获取配置目录")?
+            .join("ClaudeCodeLauncher");
+        fs::create_dir_all(&dir).map_err(|e| format!("无法创建配置目录: {}", e))?;
+        Ok(dir)
+    }
+
+    fn instance_file() -> Result<PathBuf, String> {
+        Ok(Self::config_dir()?.join("instance.json"))
+    }
+
+    fn open_lock() -> Result<fs::File, String> {
+        let path = Self::config_dir()?.join("instance.lock");
+        fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("无法打开实例锁 {}: {}", path.display(), e))
+    }
+
+    /// Take the instance lock if it is free; it is released by the OS when the process exits
+    fn try_lock() -> Result<Option<fs::File>, String> {
+        let file = Self::open_lock()?;
+        match file.try_lock() {
+            Ok(()) => Ok(Some(file)),
+            Err(fs::TryLockError::WouldBlock) => Ok(None),
+            Err(fs::TryLockError::Error(e)) => Err(format!("无法获取实例锁: {}", e)),
+        }
+    }
+
+    /// Whether another process holds the instance lock, i.e. a launcher is running or starting
+    fn is_locked_elsewhere() -> bool {
+        matches!(Self::try_lock(), Ok(None))
+    }
+
+    fn new_token() -> Result<String, String> {
+        let mut bytes = [0u8; 16];
+        getrandom::fill(&mut bytes).map_err(|e| format!("无法生成实例令牌: {}", e))?;
+        Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+    }
+
+    /// Become the running instance, or hand `args` to the one that already is.
+    /// Ok(true) means this process is the instance and has to `listen`; Ok(false) that
+    /// the invocation was forwarded and this process should exit.
+    pub fn claim(args: &[String]) -> Result<bool, String> {
+        static LOCK: OnceLock<fs::File> = OnceLock::new();
+
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        loop {
+            if let Some(file) = Self::try_lock()? {
+                let _ = LOCK.set(file);
+                return Ok(true);
+            }
+            if let Ok(connection) = Self::connect() {
+                return Self::send(connection, args).map(|_| false);
+            }
+            if Instant::now() >= deadline {
+                return Err("另一个启动器实例正在运行但没有响应".to_string());
+            }
+            std::thread::sleep(STARTUP_POLL);
+        }
+    }
+
+    /// Send `args` to the running instance, waiting for one that is still starting up.
+    /// Err means there is none (or it did not answer) and the caller should handle the
+    /// invocation itself.
+    pub fn forward(args: &[String]) -> Result<ForwardReply, String> {
+        let deadline = Instant::now() + STARTUP_TIMEOUT;
+        let connection = loop {
+            match Self::connect() {
+                Ok(connection) => break connection,
+                Err(_) if Instant::now() < deadline && Self::is_locked_elsewhere() => {
+                    std::thread::sleep(STARTUP_POLL)
+                }
+                Err(e) => return Err(e),
+            }
+        };
+        Self::send(connection, args)
+    }
+
+    /// Connect to the port in `instance.json`; returns the stream and the instance's token
+    fn connect() -> Result<(TcpStream, String), String> {
+        let path = Self::instance_file()?;
+        let content = fs::read_to_string(&path).map_err(|_| "没有正在运行的启动器".to_string())?;
+        let info: InstanceFile = serde_json::from_str(&content).map_err(|e| format!("实例信息无效: {}", e))?;
+
+        let addr = SocketAddr::from((Ipv4Addr::LOCALHOST, info.port));
+        let stream = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT)
+            .map_err(|e| format!("无法连接正在运行的启动器: {}", e))?;
+        Ok((stream, info.token))
+    }
+
+    /// Not retried once connected: the instance may already have acted on `args`
+    fn send((mut stream, token): (TcpStream, String), args: &[String]) -> Result<ForwardReply, String> {
+        stream.set_read_timeout(Some(REPLY_TIMEOUT)).ok();
+
+        let request = ForwardedArgs {
+            token,
+            args: args.to_vec(),
+            cwd: std::env::current_dir().map(|d| d.to_string_lossy().to_string()).unwrap_or_default(),
+        };
+        let mut line = serde_json::to_string(&request).map_err(|e| format!("无法序列化参数: {}", e))?;
+        line.push('\n');
+        stream.write_all(line.as_bytes()).map_err(|e| format!("无法发送参数: {}", e))?;
+
+        let mut reply = String::new();
+        BufReader::new(stream)
+            .read_line(&mut reply)
+            .map_err(|e| format!("启动器未响应: {}", e))?;
+        serde_json::from_str(&reply).map_err(|e| format!("启动器响应无效: {}", e))
+    }
+
+    /// Become the running instance: accept forwarded invocations and answer each with `handler`
+    pub fn listen<F>(handler: F) -> Result<(), String>
+    where
+        F: Fn(ForwardedArgs) -> ForwardReply + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0)).map_err(|e| format!("无法监听本地端口: {}", e))?;
+        let port = listener.local_addr().map_err(|e| format!("无法获取本地端口: {}", e))?.port();
+        let token = Self::new_token()?;
+
+        let info = InstanceFile { port, token: token.clone(), pid: std::process::id() };
+        let json = serde_json::to_string_pretty(&info).map_err(|e| format!("无法序列化实例信息: {}", e))?;
+        // The token lets the holder add projects with hooks, so the file is never readable by
+        // others, not even briefly: tempfile creates it 0600 and it is renamed into place
+        let path = Self::instance_file()?;
+        let mut temp = tempfile::NamedTempFile::new_in(Self::config_dir()?)
+            .map_err(|e| format!("无法写入实例信息: {}", e))?;
+        temp.write_all(json.as_bytes()).map_err(|e| format!("无法写入实例信息: {}", e))?;
+        temp.persist(&path).map_err(|e| format!("无法写入实例信息: {}", e.error))?;
+
+        let handler = std::sync::Arc::new(handler);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let handler = handler.clone();
+                let token = token.clone();
+                std::thread::spawn(move || {
+                    if let Err(e) = Self::serve(stream, &token, handler.as_ref()) {
+                        Logger::warn("instance", format!("forwarded invocation failed: {}", e));
+                    }
+                });
+            }
+        });
+        Ok(())
+    }
+
+    fn serve<F>(stream: TcpStream, token: &str, handler: &F) -> Result<(), String>
+    where
+        F: Fn(ForwardedArgs) -> ForwardReply,
+    {
+        stream.set_read_timeout(Some(REPLY_TIMEOUT)).ok();
+        let mut reader = BufReader::new(stream.try_clone().map_err(|e| e.to_string())?);
+        let mut line = String::new();
+        reader.read_line(&mut line).map_err(|e| e.to_string())?;
+
+        let request: ForwardedArgs = serde_json::from_str(&line).map_err(|e| e.to_string())?;
+        if request.token != token {
+            return Err("rejected connection with a wrong token".to_string());
+        }
+
+        let reply = handler(request);
+        let mut json = serde_json::to_string(&reply).map_err(|e| e.to_string())?;
+        json.push('\n');
+        (&stream).write_all(json.as_bytes()).map_err(|e| e.to_string())
+    }
+
+    /// Forget the instance file on exit, unless another instance has replaced it since
+    pub fn release() {
+        let Ok(path) = Self::instance_file() else {
+            return;
+        };
+        let owned = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<InstanceFile>(&content).ok())
+            .is_some_and(|info| info.pid == std::process::id());
+        if owned {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+use std::sync::{Mutex, OnceLock};
+
+use super::logger::Logger;
+
+/// Service name the launcher's entries are filed under in the credential store
+const SERVICE: &str = "ClaudeCodeLauncher";
+
+/// Values already read or written by this process; None when there is no entry
+fn cache() -> &'static Mutex<HashMap<String, Option<String>>> {
+    static CACHE: OnceLock<Mutex<HashMap<String, Option<String>>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Secrets kept in the OS credential store (macOS Keychain, Windows Credential Manager,
+/// Secret Service on Linux) rather than in config.json.
+pub struct Keychain;
+
+impl Keychain {
+    /// Account of a project's proxy password
+    pub fn proxy_password_account(project_id: &str) -> String {
+        format!("proxy-password:{}", project_id)
+    }
+
+    fn entry(account: &str) -> Result<keyring::Entry, String> {
+        keyring::Entry::new(SERVICE, account).map_err(|e| format!("无法访问系统密钥链: {}", e))
+    }
+
+    /// Read a secret; a store that cannot be reached counts as empty and is logged
+    pub fn get(account: &str) -> Option<String> {
+        let mut cache = cache().lock().ok()?;
+        if let Some(value) = cache.get(account) {
+            return value.clone();
+        }
+
+        let value = match Self::entry(account).map(|entry| entry.get_password()) {
+            Ok(Ok(value)) => Some(value),
+            Ok(Err(keyring::Error::NoEntry)) => None,
+            Ok(Err(e)) => {
+                Logger::warn("keychain", format!("无法读取系统密钥链 ({}): {}", account, e));
+                None
+            }
+            Err(e) => {
+                Logger::warn("keychain", format!("{} ({})", e, account));
+                None
+            }
+        };
+        cache.insert(account.to_string(), value.clone());
+        value
+    }
+
+    /// Store a secret, or delete it when `value` is empty
+    pub fn set(account: &str, value: &str) -> Result<(), String> {
+        let mut cache = cache().lock().map_err(|_| "密钥链缓存被锁定".to_string())?;
+        let wanted = Some(value.to_string()).filter(|v| !v.is_empty());
+        if cache.get(account) == Some(&wanted) {
+            return Ok(());
+        }
+
+        match wanted {
+            Some(ref value) => Self::entry(account)?
+                .set_password(value)
+                .map_err(|e| format!("无法将密码保存到系统密钥链 (请确认系统密钥服务可用): {}", e))?,
+            // Nothing can be stored in a store that cannot be reached, so this never blocks a save
+            None => match Self::entry(account).map(|entry| entry.delete_credential()) {
+                Ok(Ok(())) | Ok(Err(keyring::Error::NoEntry)) => {}
+                Ok(Err(e)) => Logger::warn("keychain", format!("无法从系统密钥链删除密码 ({}): {}", account, e)),
+                Err(e) => Logger::warn("keychain", format!("{} ({})", e, account)),
+            },
+        }
+        cache.insert(account.to_string(), wanted);
+        Ok(())
+    }
+}
@@ -4,9 +4,47 @@ pub mod launcher;
 pub mod settings_manager;
 pub mod config_storage;
 pub mod environment;
+pub mod npm_registry;
+pub mod update_cache;
+pub mod install_job;
+pub mod offline_bundle;
+pub mod npm_prefix;
+pub mod uninstaller;
+pub mod claude_native;
+pub mod session;
+pub mod pty;
+pub mod logger;
+pub mod proxy;
+pub mod system_proxy;
+pub mod ca_bundle;
+pub mod hooks;
+pub mod deep_link;
+pub mod shortcut;
+pub mod instance;
+pub mod file_watch;
+pub mod keychain;
 
 pub use dependency_checker::DependencyChecker;
 pub use installer::Installer;
 pub use launcher::Launcher;
 pub use settings_manager::SettingsManager;
 pub use config_storage::{ConfigStorage, AppConfig};
+pub use npm_registry::NpmRegistry;
+pub use update_cache::UpdateCache;
+pub use install_job::InstallJobs;
+pub use offline_bundle::OfflineBundle;
+pub use npm_prefix::NpmPrefix;
+pub use uninstaller::Uninstaller;
+pub use claude_native::ClaudeNative;
+pub use session::Sessions;
+pub use pty::PtyTerminals;
+pub use logger::{LogEntry, LogFilter, LogLevel, Logger};
+pub use proxy::Proxy;
+pub use system_proxy::SystemProxy;
+pub use ca_bundle::CaBundle;
+pub use hooks::{Hooks, LaunchHooks};
+pub use deep_link::DeepLinks;
+pub use shortcut::Shortcuts;
+pub use instance::SingleInstance;
+pub use file_watch::FileWatch;
+pub use keychain::Keychain;
@@ -0,0 +1,184 @@
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use super::dependency_checker::DependencyChecker;
+use super::environment::Environment;
+use super::npm_registry::NpmRegistry;
+
+/// Where global npm packages go and whether we can write there without admin rights
+#[derive(Debug, Clone, Serialize)]
+pub struct NpmPrefixStatus {
+    pub prefix: Option<String>,          // `npm config get prefix`; None when npm is not available
+    pub writable: bool,
+    pub user_prefix: String,             // User-level prefix that is (or would be) configured
+    pub using_user_prefix: bool,         // The effective prefix is the user-level one
+    pub user_bin_dir: String,
+    pub user_bin_on_path: bool,          // user_bin_dir is in the persistent PATH
+}
+
+pub struct NpmPrefix;
+
+impl NpmPrefix {
+    /// `%APPDATA%\npm` on Windows (npm's own default), `~/.npm-global` elsewhere
+    pub fn default_user_prefix() -> Option<PathBuf> {
+        #[cfg(windows)]
+        {
+            dirs::data_dir().map(|dir| dir.join("npm"))
+        }
+        #[cfg(not(windows))]
+        {
+            dirs::home_dir().map(|home| home.join(".npm-global"))
+        }
+    }
+
+    /// Directory holding the executables of globally installed packages
+    pub fn bin_dir(prefix: &Path) -> PathBuf {
+        #[cfg(windows)]
+        {
+            prefix.to_path_buf()
+        }
+        #[cfg(not(windows))]
+        {
+            prefix.join("bin")
+        }
+    }
+
+    fn modules_dir(prefix: &Path) -> PathBuf {
+        #[cfg(windows)]
+        {
+            prefix.join("node_modules")
+        }
+        #[cfg(not(windows))]
+        {
+            prefix.join("lib").join("node_modules")
+        }
+    }
+
+    /// Prefix set in ~/.npmrc, with a leading `~` expanded
+    pub fn configured_prefix() -> Option<PathBuf> {
+        let value = NpmRegistry::npmrc_value("prefix")?;
+        match value.strip_prefix("~/").or_else(|| value.strip_prefix("~\\")) {
+            Some(rest) => dirs::home_dir().map(|home| home.join(rest)),
+            None => Some(PathBuf::from(value)),
+        }
+    }
+
+    /// Bin directory of the configured user-level prefix, if there is one
+    pub fn user_bin_dir() -> Option<PathBuf> {
+        let user_prefix = Self::default_user_prefix()?;
+        let configured = Self::configured_prefix()?;
+        if configured == user_prefix {
+            Some(Self::bin_dir(&configured))
+        } else {
+            None
+        }
+    }
+
+    fn npm_command() -> Command {
+        #[cfg(windows)]
+        {
+            use std::os::windows::process::CommandExt;
+            const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+            let mut command = Command::new("cmd");
+            command.args(["/c", "npm"]).creation_flags(CREATE_NO_WINDOW);
+            command
+        }
+        #[cfg(not(windows))]
+        {
+            let mut command = Command::new("npm");
+            command.env("PATH", DependencyChecker::search_path());
+            command
+        }
+    }
+
+    /// Effective global prefix as reported by npm
+    pub fn current_prefix() -> Option<PathBuf> {
+        let output = Self::npm_command()
+            .args(["config", "get", "prefix"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        let prefix = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if prefix.is_empty() {
+            None
+        } else {
+            Some(PathBuf::from(prefix))
+        }
+    }
+
+    /// Probe by creating a file, since permission bits do not tell the whole story on Windows
+    fn is_writable(prefix: &Path) -> bool {
+        let modules = Self::modules_dir(prefix);
+        let probe_dir = if modules.is_dir() { modules } else { prefix.to_path_buf() };
+        if !probe_dir.is_dir() {
+            // npm creates missing directories, so a missing prefix under a writable parent is fine
+            return prefix.parent().map(Self::is_writable_dir).unwrap_or(false);
+        }
+        Self::is_writable_dir(&probe_dir)
+    }
+
+    fn is_writable_dir(dir: &Path) -> bool {
+        let probe = dir.join(format!(".claude-launcher-write-test-{}", std::process::id()));
+        match fs::write(&probe, b"") {
+            Ok(()) => {
+                let _ = fs::remove_file(&probe);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn status() -> NpmPrefixStatus {
+        let prefix = Self::current_prefix();
+        let user_prefix = Self::default_user_prefix().unwrap_or_default();
+        let user_bin_dir = Self::bin_dir(&user_prefix);
+
+        NpmPrefixStatus {
+            writable: prefix.as_deref().map(Self::is_writable).unwrap_or(false),
+            using_user_prefix: prefix.as_deref() == Some(user_prefix.as_path()),
+            prefix: prefix.map(|p| p.to_string_lossy().to_string()),
+            user_prefix: user_prefix.to_string_lossy().to_string(),
+            user_bin_on_path: Environment::user_path_contains(&user_bin_dir),
+            user_bin_dir: user_bin_dir.to_string_lossy().to_string(),
+        }
+    }
+
+    /// Point npm's global prefix at the user-level directory and put its bin dir on PATH
+    pub fn configure_user_prefix() -> Result<NpmPrefixStatus, String> {
+        let user_prefix = Self::default_user_prefix().ok_or("无法获取用户目录")?;
+        let bin_dir = Self::bin_dir(&user_prefix);
+        fs::create_dir_all(&bin_dir)
+            .map_err(|e| format!("无法创建 npm 用户目录: {}", e))?;
+
+        let output = Self::npm_command()
+            .args(["config", "set", "prefix"])
+            .arg(&user_prefix)
+            .output()
+            .map_err(|e| format!("无法执行 npm: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "无法设置 npm prefix: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Environment::add_to_user_path(&bin_dir)?;
+
+        // Make the new bin dir visible to this process right away
+        let current = std::env::var_os("PATH").unwrap_or_default();
+        if !std::env::split_paths(&current).any(|p| p == bin_dir) {
+            let paths = std::iter::once(bin_dir.clone()).chain(std::env::split_paths(&current));
+            if let Ok(joined) = std::env::join_paths(paths) {
+                std::env::set_var("PATH", joined);
+            }
+        }
+
+        Ok(Self::status())
+    }
+}
@@ -0,0 +1,656 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use super::config_storage::ConfigStorage;
+use super::ca_bundle::CaBundle;
+
+pub const DEFAULT_REGISTRY: &str = "https://registry.npmjs.org/";
+pub const CLAUDE_PACKAGE: &str = "@anthropic-ai/claude-code";
+
+/// One dot-separated pre-release identifier; numeric ones sort below alphanumeric ones (semver §11)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PrereleaseId {
+    Numeric(u64),
+    Text(String),
+}
+
+/// Where the effective registry URL came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrySource {
+    Settings,
+    Environment,
+    Npmrc,
+    Default,
+}
+
+/// Effective npm registry used for version lookups and install scripts
+#[derive(Debug, Clone, Serialize)]
+pub struct RegistryConfig {
+    pub url: String,                     // Always ends with '/'
+    pub source: RegistrySource,
+    #[serde(skip_serializing)]
+    pub auth_token: Option<String>,      // Bearer token from .npmrc, never sent to the frontend
+    pub has_auth_token: bool,
+}
+
+impl RegistryConfig {
+    pub fn new(url: &str, source: RegistrySource, auth_token: Option<String>) -> Self {
+        let mut url = url.trim().to_string();
+        if !url.ends_with('/') {
+            url.push('/');
+        }
+        Self {
+            url,
+            source,
+            has_auth_token: auth_token.is_some(),
+            auth_token,
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.url == DEFAULT_REGISTRY
+    }
+}
+
+/// One published release of a package
+#[derive(Debug, Clone, Serialize)]
+pub struct PublishedVersion {
+    pub version: String,
+    pub published_at: Option<String>,    // ISO timestamp from the packument `time` map
+    pub deprecated: bool,
+}
+
+/// Release history of a package, newest first
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageVersions {
+    pub dist_tags: HashMap<String, String>,
+    pub versions: Vec<PublishedVersion>,
+}
+
+/// Parsed subset of an .npmrc file
+#[derive(Debug, Default)]
+struct Npmrc {
+    entries: Vec<(String, String)>,
+}
+
+impl Npmrc {
+    fn parse(content: &str) -> Self {
+        let mut entries = Vec::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim().trim_matches('"');
+                entries.push((key.trim().to_string(), Self::expand_env(value)));
+            }
+        }
+        Self { entries }
+    }
+
+    /// npm expands `${VAR}` references in .npmrc values
+    fn expand_env(value: &str) -> String {
+        let mut out = String::with_capacity(value.len());
+        let mut rest = value;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            match rest[start + 2..].find('}') {
+                Some(end) => {
+                    let name = &rest[start + 2..start + 2 + end];
+                    out.push_str(&std::env::var(name).unwrap_or_default());
+                    rest = &rest[start + 3 + end..];
+                }
+                None => {
+                    out.push_str(&rest[start..]);
+                    rest = "";
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+
+    fn get(&self, key: &str) -> Option<&str> {
+        // Later entries win, same as npm
+        self.entries
+            .iter()
+            .rev()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Find the `//host/path/:_authToken` entry with the longest prefix matching the registry URL
+    fn auth_token_for(&self, registry_url: &str) -> Option<String> {
+        let without_scheme = registry_url
+            .trim_start_matches("https:")
+            .trim_start_matches("http:");
+
+        self.entries
+            .iter()
+            .filter_map(|(k, v)| {
+                let prefix = k.strip_suffix(":_authToken")?;
+                let prefix_with_slash = if prefix.ends_with('/') {
+                    prefix.to_string()
+                } else {
+                    format!("{}/", prefix)
+                };
+                if without_scheme.starts_with(&prefix_with_slash) && !v.is_empty() {
+                    Some((prefix_with_slash.len(), v.clone()))
+                } else {
+                    None
+                }
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, v)| v)
+    }
+}
+
+pub struct NpmRegistry;
+
+impl NpmRegistry {
+    fn user_npmrc_path() -> Option<PathBuf> {
+        if let Ok(path) = std::env::var("NPM_CONFIG_USERCONFIG").or_else(|_| std::env::var("npm_config_userconfig")) {
+            if !path.is_empty() {
+                return Some(PathBuf::from(path));
+            }
+        }
+        dirs::home_dir().map(|home| home.join(".npmrc"))
+    }
+
+    fn load_npmrc() -> Npmrc {
+        Self::user_npmrc_path()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .map(|content| Npmrc::parse(&content))
+            .unwrap_or_default()
+    }
+
+    /// Read a single key from the user's .npmrc
+    pub fn npmrc_value(key: &str) -> Option<String> {
+        Self::load_npmrc().get(key).map(|v| v.to_string())
+    }
+
+    /// Resolve the registry: launcher settings > npm_config_registry env > ~/.npmrc > npmjs.org
+    pub fn resolve() -> RegistryConfig {
+        let configured = ConfigStorage::get_launcher_settings()
+            .map(|s| s.npm_registry)
+            .unwrap_or_default();
+        Self::resolve_with(&configured)
+    }
+
+    pub fn resolve_with(configured: &str) -> RegistryConfig {
+        let npmrc = Self::load_npmrc();
+
+        let (url, source) = if !configured.trim().is_empty() {
+            (configured.trim().to_string(), RegistrySource::Settings)
+        } else if let Some(env_url) = ["npm_config_registry", "NPM_CONFIG_REGISTRY"]
+            .iter()
+            .filter_map(|k| std::env::var(k).ok())
+            .find(|v| !v.trim().is_empty())
+        {
+            (env_url, RegistrySource::Environment)
+        } else if let Some(npmrc_url) = npmrc
+            .get("@anthropic-ai:registry")
+            .or_else(|| npmrc.get("registry"))
+        {
+            (npmrc_url.to_string(), RegistrySource::Npmrc)
+        } else {
+            (DEFAULT_REGISTRY.to_string(), RegistrySource::Default)
+        };
+
+        let mut config = RegistryConfig::new(&url, source, None);
+        config.auth_token = npmrc.auth_token_for(&config.url);
+        config.has_auth_token = config.auth_token.is_some();
+        config
+    }
+
+    pub fn http_client() -> Result<reqwest::Client, String> {
+        Self::http_client_with_timeout(Duration::from_secs(15))
+    }
+
+    fn http_client_with_timeout(timeout: Duration) -> Result<reqwest::Client, String> {
+        let builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .user_agent(concat!("claude-code-launcher/", env!("CARGO_PKG_VERSION")));
+        // Behind a TLS-inspecting proxy the registry is only trusted through the global CA bundle
+        CaBundle::configure_client(builder, CaBundle::effective(None).as_ref())?
+            .build()
+            .map_err(|e| format!("无法创建HTTP客户端: {}", e))
+    }
+
+    /// Exact semver version such as `1.0.58` or `1.1.0-beta.2`; safe to splice into install scripts
+    pub fn is_valid_version(version: &str) -> bool {
+        regex::Regex::new(r"^\d+\.\d+\.\d+(-[0-9A-Za-z.-]+)?$")
+            .map(|re| re.is_match(version))
+            .unwrap_or(false)
+    }
+
+    /// Scoped package names must keep the '@' but escape the '/' in registry paths
+    fn escape_package_name(package: &str) -> String {
+        package.replace('/', "%2f")
+    }
+
+    async fn get_json(registry: &RegistryConfig, url: &str) -> Result<serde_json::Value, String> {
+        let client = Self::http_client()?;
+        let mut request = client
+            .get(url)
+            .header(reqwest::header::ACCEPT, "application/json");
+        if let Some(ref token) = registry.auth_token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("无法连接 npm 镜像 {}: {}", registry.url, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("npm 镜像返回错误状态 {}: {}", response.status(), url));
+        }
+
+        response
+            .json::<serde_json::Value>()
+            .await
+            .map_err(|e| format!("无法解析 npm 镜像响应: {}", e))
+    }
+
+    /// Look up the `latest` dist-tag of a package on the given registry
+    pub async fn fetch_latest_version(registry: &RegistryConfig, package: &str) -> Result<String, String> {
+        // `/<pkg>/latest` is cheap on npmjs and npmmirror; some private registries only serve dist-tags
+        let latest_url = format!("{}{}/latest", registry.url, package);
+        if let Ok(json) = Self::get_json(registry, &latest_url).await {
+            if let Some(version) = json.get("version").and_then(|v| v.as_str()) {
+                return Ok(version.to_string());
+            }
+        }
+
+        let dist_tags_url = format!("{}-/package/{}/dist-tags", registry.url, Self::escape_package_name(package));
+        let json = Self::get_json(registry, &dist_tags_url).await?;
+        json.get("latest")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| format!("npm 镜像未返回 {} 的最新版本", package))
+    }
+
+    /// Fetch the manifest of one version (or dist-tag) of a package, including its `dist` block
+    pub async fn fetch_version_manifest(registry: &RegistryConfig, package: &str, version: &str) -> Result<serde_json::Value, String> {
+        let url = format!("{}{}/{}", registry.url, package, version);
+        Self::get_json(registry, &url).await
+    }
+
+    /// Download a package tarball from the registry
+    pub async fn download_tarball(registry: &RegistryConfig, url: &str) -> Result<Vec<u8>, String> {
+        let client = Self::http_client_with_timeout(Duration::from_secs(600))?;
+        let mut request = client.get(url);
+        // Tarballs may live on a CDN; only send the token back to the registry itself
+        if let (Some(ref token), true) = (&registry.auth_token, url.starts_with(&registry.url)) {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("无法下载 {}: {}", url, e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("下载失败,状态 {}: {}", response.status(), url));
+        }
+
+        response
+            .bytes()
+            .await
+            .map(|bytes| bytes.to_vec())
+            .map_err(|e| format!("无法下载 {}: {}", url, e))
+    }
+
+    /// List every published version of a package from its full registry document
+    pub async fn fetch_versions(registry: &RegistryConfig, package: &str) -> Result<PackageVersions, String> {
+        let url = format!("{}{}", registry.url, Self::escape_package_name(package));
+        let json = Self::get_json(registry, &url).await?;
+
+        let dist_tags: HashMap<String, String> = json
+            .get("dist-tags")
+            .and_then(|v| v.as_object())
+            .map(|tags| {
+                tags.iter()
+                    .filter_map(|(tag, version)| version.as_str().map(|v| (tag.clone(), v.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let time = json.get("time").and_then(|v| v.as_object());
+        let mut versions: Vec<PublishedVersion> = json
+            .get("versions")
+            .and_then(|v| v.as_object())
+            .ok_or_else(|| format!("npm 镜像未返回 {} 的版本列表", package))?
+            .iter()
+            .map(|(version, manifest)| PublishedVersion {
+                version: version.clone(),
+                published_at: time
+                    .and_then(|t| t.get(version))
+                    .and_then(|v| v.as_str())
+                    .map(|s| s.to_string()),
+                deprecated: manifest.get("deprecated").map(|d| !d.is_null()).unwrap_or(false),
+            })
+            .collect();
+
+        versions.sort_by(|a, b| Self::version_sort_key(&b.version).cmp(&Self::version_sort_key(&a.version)));
+
+        Ok(PackageVersions { dist_tags, versions })
+    }
+
+    /// Semver ordering key: numeric core, then releases above their pre-releases,
+    /// then the pre-release identifiers one by one (`beta.2` < `beta.10`)
+    pub fn version_sort_key(version: &str) -> (Vec<u64>, bool, Vec<PrereleaseId>) {
+        let version = version.split('+').next().unwrap_or_default();
+        let (core, pre) = version.split_once('-').unwrap_or((version, ""));
+        let numbers = core
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect();
+        let identifiers = pre
+            .split('.')
+            .filter(|id| !id.is_empty())
+            .map(|id| match id.parse() {
+                Ok(number) => PrereleaseId::Numeric(number),
+                Err(_) => PrereleaseId::Text(id.to_string()),
+            })
+            .collect();
+        (numbers, pre.is_empty(), identifiers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+
+    /// Serialises the tests that change process-wide environment variables
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+    /// Requests seen by a mock registry: (path, Authorization header)
+    type Seen = Arc<Mutex<Vec<(String, Option<String>)>>>;
+
+    /// Serve `routes` (path -> status, body) on a local port; unknown paths answer 404
+    fn mock_registry(routes: &[(&str, u16, &str)]) -> (String, Seen) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let routes: Vec<(String, u16, String)> = routes
+            .iter()
+            .map(|(path, status, body)| (path.to_string(), *status, body.to_string()))
+            .collect();
+        let seen: Seen = Arc::new(Mutex::new(Vec::new()));
+
+        let recorded = seen.clone();
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request_line = String::new();
+                reader.read_line(&mut request_line).unwrap();
+                let path = request_line.split_whitespace().nth(1).unwrap_or("").to_string();
+
+                let mut authorization = None;
+                loop {
+                    let mut header = String::new();
+                    if reader.read_line(&mut header).unwrap_or(0) == 0 || header.trim().is_empty() {
+                        break;
+                    }
+                    if let Some((name, value)) = header.split_once(':') {
+                        if name.eq_ignore_ascii_case("authorization") {
+                            authorization = Some(value.trim().to_string());
+                        }
+                    }
+                }
+                recorded.lock().unwrap().push((path.clone(), authorization));
+
+                let (status, body) = routes
+                    .iter()
+                    .find(|(p, _, _)| *p == path)
+                    .map(|(_, status, body)| (*status, body.clone()))
+                    .unwrap_or((404, "{}".to_string()));
+                let response = format!(
+                    "HTTP/1.1 {} X\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = (&stream).write_all(response.as_bytes());
+            }
+        });
+        (url, seen)
+    }
+
+    fn registry(url: &str, token: Option<&str>) -> RegistryConfig {
+        RegistryConfig::new(url, RegistrySource::Settings, token.map(|t| t.to_string()))
+    }
+
+    #[test]
+    fn registry_url_gets_trailing_slash() {
+        let config = RegistryConfig::new(" https://registry.npmmirror.com ", RegistrySource::Settings, None);
+        assert_eq!(config.url, "https://registry.npmmirror.com/");
+        assert!(!config.is_default());
+        assert!(RegistryConfig::new(DEFAULT_REGISTRY, RegistrySource::Default, None).is_default());
+    }
+
+    #[test]
+    fn resolve_precedence() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let npmrc = dir.path().join(".npmrc");
+        fs::write(
+            &npmrc,
+            "registry=https://npmrc.example.com/\n//npmrc.example.com/:_authToken=npmrc-token\n",
+        )
+        .unwrap();
+        std::env::set_var("NPM_CONFIG_USERCONFIG", &npmrc);
+        std::env::remove_var("npm_config_registry");
+        std::env::remove_var("NPM_CONFIG_REGISTRY");
+
+        // .npmrc beats the default
+        let config = NpmRegistry::resolve_with("");
+        assert_eq!(config.source, RegistrySource::Npmrc);
+        assert_eq!(config.url, "https://npmrc.example.com/");
+        assert_eq!(config.auth_token.as_deref(), Some("npmrc-token"));
+
+        // Environment beats .npmrc
+        std::env::set_var("npm_config_registry", "https://env.example.com");
+        let config = NpmRegistry::resolve_with("  ");
+        assert_eq!(config.source, RegistrySource::Environment);
+        assert_eq!(config.url, "https://env.example.com/");
+        assert!(!config.has_auth_token);
+
+        // Launcher settings beat everything
+        let config = NpmRegistry::resolve_with("https://npmrc.example.com");
+        assert_eq!(config.source, RegistrySource::Settings);
+        assert_eq!(config.auth_token.as_deref(), Some("npmrc-token"));
+
+        // Nothing configured
+        std::env::remove_var("npm_config_registry");
+        fs::write(&npmrc, "").unwrap();
+        let config = NpmRegistry::resolve_with("");
+        assert_eq!(config.source, RegistrySource::Default);
+        assert_eq!(config.url, DEFAULT_REGISTRY);
+
+        // The scoped registry wins over the global one
+        fs::write(
+            &npmrc,
+            "registry=https://npmrc.example.com/\n@anthropic-ai:registry=https://scoped.example.com/\n",
+        )
+        .unwrap();
+        assert_eq!(NpmRegistry::resolve_with("").url, "https://scoped.example.com/");
+
+        std::env::remove_var("NPM_CONFIG_USERCONFIG");
+    }
+
+    #[test]
+    fn npmrc_auth_token_longest_prefix() {
+        let npmrc = Npmrc::parse(
+            "; comment\n\
+             # another comment\n\
+             //npm.example.com/:_authToken=host-token\n\
+             //npm.example.com/private/:_authToken=\"path-token\"\n\
+             //other.example.com:_authToken=other-token\n",
+        );
+        assert_eq!(npmrc.auth_token_for("https://npm.example.com/").as_deref(), Some("host-token"));
+        assert_eq!(
+            npmrc.auth_token_for("https://npm.example.com/private/").as_deref(),
+            Some("path-token")
+        );
+        assert_eq!(npmrc.auth_token_for("http://other.example.com/").as_deref(), Some("other-token"));
+        assert_eq!(npmrc.auth_token_for("https://npm.example.com.evil/"), None);
+        assert_eq!(npmrc.auth_token_for(DEFAULT_REGISTRY), None);
+    }
+
+    #[test]
+    fn npmrc_later_entries_and_env_expansion() {
+        let _guard = ENV_LOCK.lock().unwrap();
+        std::env::set_var("LAUNCHER_TEST_NPM_TOKEN", "from-env");
+        let npmrc = Npmrc::parse(
+            "registry=https://first.example.com/\n\
+             registry=https://second.example.com/\n\
+             //second.example.com/:_authToken=${LAUNCHER_TEST_NPM_TOKEN}\n\
+             empty=\n",
+        );
+        std::env::remove_var("LAUNCHER_TEST_NPM_TOKEN");
+
+        assert_eq!(npmrc.get("registry"), Some("https://second.example.com/"));
+        assert_eq!(npmrc.get("empty"), None);
+        assert_eq!(
+            npmrc.auth_token_for("https://second.example.com/").as_deref(),
+            Some("from-env")
+        );
+        assert_eq!(Npmrc::expand_env("a${UNCLOSED"), "a${UNCLOSED");
+    }
+
+    #[tokio::test]
+    async fn latest_version_from_latest_endpoint() {
+        let (url, seen) = mock_registry(&[("/@anthropic-ai/claude-code/latest", 200, r#"{"version":"1.2.3"}"#)]);
+        let version = NpmRegistry::fetch_latest_version(&registry(&url, Some("secret")), CLAUDE_PACKAGE)
+            .await
+            .unwrap();
+        assert_eq!(version, "1.2.3");
+        assert_eq!(seen.lock().unwrap()[0].1.as_deref(), Some("Bearer secret"));
+    }
+
+    #[tokio::test]
+    async fn latest_version_falls_back_to_dist_tags() {
+        let (url, seen) = mock_registry(&[(
+            "/-/package/@anthropic-ai%2fclaude-code/dist-tags",
+            200,
+            r#"{"latest":"2.0.1","next":"2.1.0-beta.1"}"#,
+        )]);
+        let version = NpmRegistry::fetch_latest_version(&registry(&url, None), CLAUDE_PACKAGE)
+            .await
+            .unwrap();
+        assert_eq!(version, "2.0.1");
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 2);
+        assert_eq!(seen[1].1, None);
+    }
+
+    #[tokio::test]
+    async fn latest_version_error_mapping() {
+        let (url, _) = mock_registry(&[
+            ("/@anthropic-ai/claude-code/latest", 500, "{}"),
+            ("/-/package/@anthropic-ai%2fclaude-code/dist-tags", 503, "{}"),
+        ]);
+        let err = NpmRegistry::fetch_latest_version(&registry(&url, None), CLAUDE_PACKAGE)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("npm 镜像返回错误状态 503"), "{}", err);
+
+        let (url, _) = mock_registry(&[("/-/package/@anthropic-ai%2fclaude-code/dist-tags", 200, r#"{"next":"1.0.0"}"#)]);
+        let err = NpmRegistry::fetch_latest_version(&registry(&url, None), CLAUDE_PACKAGE)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "npm 镜像未返回 @anthropic-ai/claude-code 的最新版本");
+
+        let (url, _) = mock_registry(&[("/-/package/@anthropic-ai%2fclaude-code/dist-tags", 200, "not json")]);
+        let err = NpmRegistry::fetch_latest_version(&registry(&url, None), CLAUDE_PACKAGE)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("无法解析 npm 镜像响应"), "{}", err);
+
+        // Nothing listens on a port that was just released
+        let closed = TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap();
+        let err = NpmRegistry::fetch_latest_version(&registry(&format!("http://{}/", closed), None), CLAUDE_PACKAGE)
+            .await
+            .unwrap_err();
+        assert!(err.starts_with("无法连接 npm 镜像"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn versions_sorted_newest_first() {
+        let (url, _) = mock_registry(&[(
+            "/@anthropic-ai%2fclaude-code",
+            200,
+            r#"{
+                "dist-tags": {"latest": "1.10.0"},
+                "versions": {
+                    "1.9.0": {},
+                    "1.10.0": {},
+                    "1.10.0-beta.1": {},
+                    "1.2.0": {"deprecated": "broken"}
+                },
+                "time": {"1.10.0": "2025-01-02T00:00:00.000Z"}
+            }"#,
+        )]);
+        let result = NpmRegistry::fetch_versions(&registry(&url, None), CLAUDE_PACKAGE)
+            .await
+            .unwrap();
+
+        let order: Vec<&str> = result.versions.iter().map(|v| v.version.as_str()).collect();
+        assert_eq!(order, ["1.10.0", "1.10.0-beta.1", "1.9.0", "1.2.0"]);
+        assert_eq!(result.dist_tags.get("latest").map(String::as_str), Some("1.10.0"));
+        assert_eq!(result.versions[0].published_at.as_deref(), Some("2025-01-02T00:00:00.000Z"));
+        assert!(result.versions[3].deprecated);
+        assert!(!result.versions[0].deprecated);
+
+        let (url, _) = mock_registry(&[("/@anthropic-ai%2fclaude-code", 200, r#"{"dist-tags":{}}"#)]);
+        let err = NpmRegistry::fetch_versions(&registry(&url, None), CLAUDE_PACKAGE)
+            .await
+            .unwrap_err();
+        assert_eq!(err, "npm 镜像未返回 @anthropic-ai/claude-code 的版本列表");
+    }
+
+    #[test]
+    fn version_validation() {
+        assert!(NpmRegistry::is_valid_version("1.0.58"));
+        assert!(NpmRegistry::is_valid_version("1.1.0-beta.2"));
+        assert!(!NpmRegistry::is_valid_version("latest"));
+        assert!(!NpmRegistry::is_valid_version("1.0"));
+        assert!(!NpmRegistry::is_valid_version("1.0.0; rm -rf /"));
+    }
+
+    #[test]
+    fn versions_sort_by_semver_precedence() {
+        // Ascending order from the semver spec, plus numeric identifiers past 9
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.10",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+            "1.0.10",
+            "1.2.0",
+        ];
+        let mut shuffled = ordered.to_vec();
+        shuffled.reverse();
+        shuffled.swap(1, 6);
+        shuffled.sort_by_key(|v| NpmRegistry::version_sort_key(v));
+        assert_eq!(shuffled, ordered);
+        assert_eq!(NpmRegistry::version_sort_key("1.0.0+build.5"), NpmRegistry::version_sort_key("1.0.0"));
+    }
+}
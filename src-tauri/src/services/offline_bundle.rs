@@ -0,0 +1,456 @@
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha1::Sha1;
+use sha2::{Digest, Sha256, Sha512};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::npm_registry::{NpmRegistry, CLAUDE_PACKAGE};
+
+/// Name of the index file written by `prepare` and read by `resolve`
+pub const MANIFEST_FILE: &str = "manifest.json";
+
+/// Sidecar checksum files accepted next to a bare tarball
+const SHASUM_FILES: &[&str] = &["SHASUMS256.txt", "SHASUMS.txt", "shasums.txt", "sha256sums.txt", "sha1sums.txt"];
+
+/// One package tarball stored in an offline bundle
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflinePackage {
+    pub name: String,
+    pub version: String,
+    pub file: String,                    // Tarball file name, relative to the bundle folder
+    pub shasum: String,                  // SHA-1 hex, as published in the registry `dist` block
+    #[serde(default)]
+    pub integrity: Option<String>,       // Subresource Integrity string (sha512-...)
+}
+
+/// Index of an offline bundle folder
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineManifest {
+    pub format_version: u32,
+    pub created_at: u64,
+    pub registry: String,                // Registry the packages were downloaded from
+    pub packages: Vec<OfflinePackage>,
+}
+
+impl Default for OfflineManifest {
+    fn default() -> Self {
+        Self {
+            format_version: 1,
+            created_at: 0,
+            registry: String::new(),
+            packages: Vec::new(),
+        }
+    }
+}
+
+/// A tarball whose checksum has been checked and is ready to install
+#[derive(Debug, Clone, Serialize)]
+pub struct VerifiedPackage {
+    pub name: String,
+    pub version: String,
+    pub tarball: String,                 // Absolute path of the tarball
+    pub verified_with: String,           // Which checksum matched, e.g. "manifest.json (sha512)"
+}
+
+/// Expected digest of a tarball, from a manifest or shasum file
+enum ExpectedDigest {
+    Sha1(String),
+    Sha256(String),
+    Sha512(String),
+    Integrity(String),
+}
+
+impl ExpectedDigest {
+    /// Pick the algorithm from the length of a hex digest
+    fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex.trim().to_lowercase();
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        match hex.len() {
+            40 => Some(ExpectedDigest::Sha1(hex)),
+            64 => Some(ExpectedDigest::Sha256(hex)),
+            128 => Some(ExpectedDigest::Sha512(hex)),
+            _ => None,
+        }
+    }
+
+    fn algorithm(&self) -> &'static str {
+        match self {
+            ExpectedDigest::Sha1(_) => "sha1",
+            ExpectedDigest::Sha256(_) => "sha256",
+            ExpectedDigest::Sha512(_) | ExpectedDigest::Integrity(_) => "sha512",
+        }
+    }
+
+    fn matches(&self, data: &[u8]) -> bool {
+        match self {
+            ExpectedDigest::Sha1(hex) => to_hex(&Sha1::digest(data)) == *hex,
+            ExpectedDigest::Sha256(hex) => to_hex(&Sha256::digest(data)) == *hex,
+            ExpectedDigest::Sha512(hex) => to_hex(&Sha512::digest(data)) == *hex,
+            ExpectedDigest::Integrity(sri) => {
+                let actual = general_purpose::STANDARD.encode(Sha512::digest(data));
+                // An SRI string may list several hashes; any sha512 entry must match
+                sri.split_whitespace()
+                    .filter_map(|entry| entry.strip_prefix("sha512-"))
+                    .any(|expected| expected == actual)
+            }
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub struct OfflineBundle;
+
+impl OfflineBundle {
+    /// File name `npm pack` would produce, e.g. `anthropic-ai-claude-code-1.0.58.tgz`
+    fn tarball_name(package: &str, version: &str) -> String {
+        format!("{}-{}.tgz", package.trim_start_matches('@').replace('/', "-"), version)
+    }
+
+    fn load_manifest(dir: &Path) -> Option<OfflineManifest> {
+        fs::read_to_string(dir.join(MANIFEST_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+    }
+
+    /// Guess the version from an `npm pack` file name when there is no manifest entry
+    fn version_from_file_name(file_name: &str) -> Option<String> {
+        let prefix = Self::tarball_name(CLAUDE_PACKAGE, "");
+        file_name
+            .strip_prefix(prefix.trim_end_matches(".tgz"))
+            .and_then(|rest| rest.strip_suffix(".tgz"))
+            .filter(|version| NpmRegistry::is_valid_version(version))
+            .map(|version| version.to_string())
+    }
+
+    /// Look for `<hex>  <file>` lines in shasum files, or a bare digest in `<file>.sha*`
+    fn find_shasum(tarball: &Path) -> Option<(ExpectedDigest, String)> {
+        let dir = tarball.parent()?;
+        let file_name = tarball.file_name()?.to_string_lossy().to_string();
+
+        for ext in ["sha512", "sha256", "sha1"] {
+            let sidecar = dir.join(format!("{}.{}", file_name, ext));
+            if let Some(digest) = fs::read_to_string(&sidecar)
+                .ok()
+                .and_then(|content| content.split_whitespace().next().and_then(ExpectedDigest::from_hex))
+            {
+                return Some((digest, sidecar.file_name()?.to_string_lossy().to_string()));
+            }
+        }
+
+        for name in SHASUM_FILES {
+            let Ok(content) = fs::read_to_string(dir.join(name)) else {
+                continue;
+            };
+            for line in content.lines() {
+                let mut parts = line.split_whitespace();
+                let (Some(hex), Some(listed)) = (parts.next(), parts.next()) else {
+                    continue;
+                };
+                if listed.trim_start_matches('*') == file_name {
+                    if let Some(digest) = ExpectedDigest::from_hex(hex) {
+                        return Some((digest, name.to_string()));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    fn verify_file(path: &Path, digest: &ExpectedDigest) -> Result<(), String> {
+        let data = fs::read(path)
+            .map_err(|e| format!("无法读取离线安装包 {}: {}", path.display(), e))?;
+        if digest.matches(&data) {
+            Ok(())
+        } else {
+            Err(format!("离线安装包校验失败 ({}): {}", digest.algorithm(), path.display()))
+        }
+    }
+
+    fn verify_entry(dir: &Path, entry: &OfflinePackage) -> Result<VerifiedPackage, String> {
+        let tarball = dir.join(&entry.file);
+        if !tarball.is_file() {
+            return Err(format!("清单中列出的安装包不存在: {}", tarball.display()));
+        }
+
+        let digest = match entry.integrity {
+            Some(ref integrity) if integrity.contains("sha512-") => ExpectedDigest::Integrity(integrity.clone()),
+            _ => ExpectedDigest::from_hex(&entry.shasum)
+                .ok_or_else(|| format!("清单中的校验值无效: {}", entry.shasum))?,
+        };
+        Self::verify_file(&tarball, &digest)?;
+
+        Ok(VerifiedPackage {
+            name: entry.name.clone(),
+            version: entry.version.clone(),
+            tarball: tarball.to_string_lossy().to_string(),
+            verified_with: format!("{} ({})", MANIFEST_FILE, digest.algorithm()),
+        })
+    }
+
+    /// Find and verify a Claude Code tarball.
+    ///
+    /// `path` may be a `.tgz` (checked against the folder's manifest.json or shasum files)
+    /// or a bundle folder; for folders the requested `version`, or the newest one, is used.
+    pub fn resolve(path: &str, version: Option<&str>) -> Result<VerifiedPackage, String> {
+        let path = PathBuf::from(path.trim());
+        let version = version.map(|v| v.trim().trim_start_matches('v')).filter(|v| !v.is_empty());
+
+        if path.is_file() {
+            let dir = path.parent().unwrap_or(Path::new("."));
+            let file_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let listed = Self::load_manifest(dir)
+                .and_then(|manifest| manifest.packages.into_iter().find(|p| p.file == file_name));
+            if let Some(entry) = listed {
+                return Self::verify_entry(dir, &entry);
+            }
+
+            let (digest, source) = Self::find_shasum(&path)
+                .ok_or_else(|| format!("未找到 {} 的校验文件 ({} 或 .sha1/.sha256/.sha512)", file_name, MANIFEST_FILE))?;
+            Self::verify_file(&path, &digest)?;
+
+            let found_version = Self::version_from_file_name(&file_name)
+                .ok_or_else(|| format!("无法从文件名识别 Claude Code 版本: {}", file_name))?;
+            if let Some(requested) = version {
+                if requested != found_version {
+                    return Err(format!("安装包版本 {} 与请求的版本 {} 不一致", found_version, requested));
+                }
+            }
+
+            return Ok(VerifiedPackage {
+                name: CLAUDE_PACKAGE.to_string(),
+                version: found_version,
+                tarball: fs::canonicalize(&path).unwrap_or(path).to_string_lossy().to_string(),
+                verified_with: format!("{} ({})", source, digest.algorithm()),
+            });
+        }
+
+        if !path.is_dir() {
+            return Err(format!("离线安装包路径不存在: {}", path.display()));
+        }
+
+        let manifest = Self::load_manifest(&path)
+            .ok_or_else(|| format!("目录中缺少 {}: {}", MANIFEST_FILE, path.display()))?;
+        let entry = manifest
+            .packages
+            .iter()
+            .filter(|p| p.name == CLAUDE_PACKAGE)
+            .filter(|p| version.map(|v| v == p.version).unwrap_or(true))
+            .max_by(|a, b| NpmRegistry::version_sort_key(&a.version).cmp(&NpmRegistry::version_sort_key(&b.version)))
+            .ok_or_else(|| match version {
+                Some(v) => format!("离线包中没有 Claude Code {}", v),
+                None => "离线包中没有 Claude Code".to_string(),
+            })?;
+
+        Self::verify_entry(&path, entry)
+    }
+
+    /// Download Claude Code (a version, or latest) and its registry metadata into `dest_dir`.
+    ///
+    /// Existing manifest entries are kept, so one folder can hold several versions.
+    pub async fn prepare(dest_dir: &str, version: Option<&str>) -> Result<OfflineManifest, String> {
+        let dest = PathBuf::from(dest_dir.trim());
+        fs::create_dir_all(&dest)
+            .map_err(|e| format!("无法创建离线包目录: {}", e))?;
+
+        let version = version.map(|v| v.trim().trim_start_matches('v')).filter(|v| !v.is_empty());
+        if let Some(v) = version {
+            if !NpmRegistry::is_valid_version(v) {
+                return Err(format!("无效的 Claude Code 版本号: {}", v));
+            }
+        }
+
+        let registry = NpmRegistry::resolve();
+        let metadata = NpmRegistry::fetch_version_manifest(&registry, CLAUDE_PACKAGE, version.unwrap_or("latest")).await?;
+
+        let resolved_version = metadata
+            .get("version")
+            .and_then(|v| v.as_str())
+            .ok_or("npm 镜像返回的版本信息缺少 version 字段")?
+            .to_string();
+        let dist = metadata.get("dist").ok_or("npm 镜像返回的版本信息缺少 dist 字段")?;
+        let tarball_url = dist
+            .get("tarball")
+            .and_then(|v| v.as_str())
+            .ok_or("npm 镜像返回的版本信息缺少 tarball 地址")?;
+        let shasum = dist
+            .get("shasum")
+            .and_then(|v| v.as_str())
+            .ok_or("npm 镜像返回的版本信息缺少 shasum")?
+            .to_string();
+        let integrity = dist.get("integrity").and_then(|v| v.as_str()).map(|s| s.to_string());
+
+        let data = NpmRegistry::download_tarball(&registry, tarball_url).await?;
+
+        let digest = match integrity {
+            Some(ref sri) if sri.contains("sha512-") => ExpectedDigest::Integrity(sri.clone()),
+            _ => ExpectedDigest::from_hex(&shasum).ok_or_else(|| format!("无效的 shasum: {}", shasum))?,
+        };
+        if !digest.matches(&data) {
+            return Err(format!("下载的安装包校验失败 ({}): {}", digest.algorithm(), tarball_url));
+        }
+
+        let file = Self::tarball_name(CLAUDE_PACKAGE, &resolved_version);
+        fs::write(dest.join(&file), &data)
+            .map_err(|e| format!("无法写入离线安装包: {}", e))?;
+
+        let metadata_json = serde_json::to_string_pretty(&metadata)
+            .map_err(|e| format!("无法序列化版本信息: {}", e))?;
+        fs::write(dest.join(format!("{}.json", file.trim_end_matches(".tgz"))), metadata_json)
+            .map_err(|e| format!("无法写入版本信息: {}", e))?;
+
+        let mut manifest = Self::load_manifest(&dest).unwrap_or_default();
+        manifest.packages.retain(|p| !(p.name == CLAUDE_PACKAGE && p.version == resolved_version));
+        manifest.packages.push(OfflinePackage {
+            name: CLAUDE_PACKAGE.to_string(),
+            version: resolved_version,
+            file,
+            shasum,
+            integrity,
+        });
+        manifest.registry = registry.url;
+        manifest.created_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let manifest_json = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| format!("无法序列化离线包清单: {}", e))?;
+        fs::write(dest.join(MANIFEST_FILE), manifest_json)
+            .map_err(|e| format!("无法写入离线包清单: {}", e))?;
+
+        Ok(manifest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TARBALL: &[u8] = b"package tarball";
+    const FILE: &str = "anthropic-ai-claude-code-1.0.58.tgz";
+
+    fn sha256_hex(data: &[u8]) -> String {
+        to_hex(&Sha256::digest(data))
+    }
+
+    fn bundle_with_manifest(packages: &[(&str, &[u8])]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let mut manifest = OfflineManifest::default();
+        for (version, data) in packages {
+            let file = OfflineBundle::tarball_name(CLAUDE_PACKAGE, version);
+            fs::write(dir.path().join(&file), data).unwrap();
+            manifest.packages.push(OfflinePackage {
+                name: CLAUDE_PACKAGE.to_string(),
+                version: version.to_string(),
+                file,
+                shasum: to_hex(&Sha1::digest(data)),
+                integrity: Some(format!("sha512-{}", general_purpose::STANDARD.encode(Sha512::digest(data)))),
+            });
+        }
+        fs::write(dir.path().join(MANIFEST_FILE), serde_json::to_string(&manifest).unwrap()).unwrap();
+        dir
+    }
+
+    #[test]
+    fn digests_are_chosen_by_length_and_compared() {
+        let sha1 = to_hex(&Sha1::digest(TARBALL));
+        let sha256 = sha256_hex(TARBALL);
+        let sha512 = to_hex(&Sha512::digest(TARBALL));
+
+        for (hex, algorithm) in [(&sha1, "sha1"), (&sha256, "sha256"), (&sha512, "sha512")] {
+            let digest = ExpectedDigest::from_hex(&format!(" {} ", hex.to_uppercase())).unwrap();
+            assert_eq!(digest.algorithm(), algorithm);
+            assert!(digest.matches(TARBALL));
+            assert!(!digest.matches(b"tampered"));
+        }
+        assert!(ExpectedDigest::from_hex(&sha256[..63]).is_none());
+        assert!(ExpectedDigest::from_hex(&format!("{}g", &sha1[..39])).is_none());
+
+        let sri = general_purpose::STANDARD.encode(Sha512::digest(TARBALL));
+        let other = general_purpose::STANDARD.encode(Sha512::digest(b"other"));
+        assert!(ExpectedDigest::Integrity(format!("sha1-abc sha512-{} sha512-{}", other, sri)).matches(TARBALL));
+        assert!(!ExpectedDigest::Integrity(format!("sha512-{}", other)).matches(TARBALL));
+        assert!(!ExpectedDigest::Integrity("sha1-abc".to_string()).matches(TARBALL));
+    }
+
+    #[test]
+    fn shasum_is_found_in_sidecar_and_list_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let tarball = dir.path().join(FILE);
+        fs::write(&tarball, TARBALL).unwrap();
+        assert!(OfflineBundle::find_shasum(&tarball).is_none());
+
+        fs::write(
+            dir.path().join("SHASUMS256.txt"),
+            format!("{}  other.tgz\n{} *{}\n", sha256_hex(b"other"), sha256_hex(TARBALL), FILE),
+        )
+        .unwrap();
+        let (digest, source) = OfflineBundle::find_shasum(&tarball).unwrap();
+        assert_eq!(source, "SHASUMS256.txt");
+        assert!(digest.matches(TARBALL));
+
+        // A sidecar next to the tarball wins over the list
+        fs::write(dir.path().join(format!("{}.sha1", FILE)), format!("{}  {}\n", to_hex(&Sha1::digest(TARBALL)), FILE)).unwrap();
+        let (digest, source) = OfflineBundle::find_shasum(&tarball).unwrap();
+        assert_eq!(source, format!("{}.sha1", FILE));
+        assert_eq!(digest.algorithm(), "sha1");
+    }
+
+    #[test]
+    fn version_is_read_from_npm_pack_names() {
+        assert_eq!(OfflineBundle::version_from_file_name(FILE).as_deref(), Some("1.0.58"));
+        assert_eq!(
+            OfflineBundle::version_from_file_name("anthropic-ai-claude-code-2.0.0-beta.1.tgz").as_deref(),
+            Some("2.0.0-beta.1")
+        );
+        assert_eq!(OfflineBundle::version_from_file_name("claude-code-1.0.58.tgz"), None);
+        assert_eq!(OfflineBundle::version_from_file_name("anthropic-ai-claude-code-latest.tgz"), None);
+    }
+
+    #[test]
+    fn resolve_picks_and_verifies_bundle_entries() {
+        let dir = bundle_with_manifest(&[("1.0.9", b"old"), ("1.0.10", TARBALL)]);
+        let path = dir.path().to_string_lossy().to_string();
+
+        let newest = OfflineBundle::resolve(&path, None).unwrap();
+        assert_eq!(newest.version, "1.0.10");
+        assert_eq!(newest.verified_with, "manifest.json (sha512)");
+        assert_eq!(OfflineBundle::resolve(&path, Some("v1.0.9")).unwrap().version, "1.0.9");
+        assert!(OfflineBundle::resolve(&path, Some("1.0.11")).is_err());
+
+        // The tarball no longer matches the digest recorded when the bundle was prepared
+        fs::write(dir.path().join(OfflineBundle::tarball_name(CLAUDE_PACKAGE, "1.0.10")), b"tampered").unwrap();
+        let error = OfflineBundle::resolve(&path, None).unwrap_err();
+        assert!(error.contains("校验失败"), "{}", error);
+    }
+
+    #[test]
+    fn resolve_checks_a_bare_tarball_against_its_shasum() {
+        let dir = tempfile::tempdir().unwrap();
+        let tarball = dir.path().join(FILE);
+        fs::write(&tarball, TARBALL).unwrap();
+        fs::write(dir.path().join(format!("{}.sha256", FILE)), sha256_hex(TARBALL)).unwrap();
+        let path = tarball.to_string_lossy().to_string();
+
+        let package = OfflineBundle::resolve(&path, Some("1.0.58")).unwrap();
+        assert_eq!(package.version, "1.0.58");
+        assert_eq!(package.verified_with, format!("{}.sha256 (sha256)", FILE));
+
+        let error = OfflineBundle::resolve(&path, Some("1.0.59")).unwrap_err();
+        assert!(error.contains("不一致"), "{}", error);
+
+        fs::write(&tarball, b"tampered").unwrap();
+        assert!(OfflineBundle::resolve(&path, None).unwrap_err().contains("校验失败"));
+    }
+}
@@ -0,0 +1,362 @@
+use base64::{engine::general_purpose, Engine as _};
+use portable_pty::{native_pty_system, ChildKiller, CommandBuilder, MasterPty, PtySize};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::io::{Read, Write};
+use std::path::PathBuf;
+use std::sync::{mpsc, Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use super::dependency_checker::DependencyChecker;
+use super::logger::{LogLevel, Logger};
+use super::session::Sessions;
+
+/// Recent output kept per terminal so a re-mounted view can repaint its screen
+const SCROLLBACK_BYTES: usize = 512 * 1024;
+
+/// How long to wait for the last output after the child exited. On Unix the reader only
+/// sees EOF once every holder of the slave is gone, e.g. a server a hook left running.
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+type PtyWriter = Arc<Mutex<Box<dyn Write + Send>>>;
+
+/// What to run and where; `env` is applied on top of the launcher's own environment
+#[derive(Debug, Clone, Default)]
+pub struct PtySpawnOptions {
+    pub title: String,
+    pub project_id: Option<String>,
+    pub session_id: Option<String>,      // Launch session to report PID and exit code to
+    pub working_dir: Option<String>,
+    pub env: Vec<(String, String)>,
+    pub args: Vec<String>,               // Extra arguments for `claude`
+    pub shell: Option<(String, Vec<String>)>,  // Run this instead of `claude` itself, e.g. to wrap it in launch hooks
+    pub cols: u16,
+    pub rows: u16,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TerminalInfo {
+    pub id: String,
+    pub title: String,
+    pub project_id: Option<String>,
+    pub session_id: Option<String>,
+    pub pid: Option<u32>,
+    pub cols: u16,
+    pub rows: u16,
+    pub started_at: u64,                 // Unix timestamp
+    pub exited: bool,
+    pub exit_code: Option<i32>,
+}
+
+/// Raw terminal output is not guaranteed to be valid UTF-8, so it travels base64-encoded
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PtyEvent {
+    Output {
+        terminal_id: String,
+        offset: u64,                     // Byte offset of `data` in the terminal's output stream
+        data: String,
+    },
+    Exit {
+        terminal_id: String,
+        exit_code: Option<i32>,
+    },
+}
+
+/// Scrollback plus the stream offset it ends at, for de-duplicating against live events
+#[derive(Debug, Clone, Serialize)]
+pub struct TerminalSnapshot {
+    pub data: String,                    // base64
+    pub end: u64,
+}
+
+struct PtyEntry {
+    info: TerminalInfo,
+    master: Option<Box<dyn MasterPty + Send>>,
+    writer: Option<PtyWriter>,          // Own lock, so a child that stops reading input cannot block the list
+    killer: Box<dyn ChildKiller + Send + Sync>,
+    scrollback: VecDeque<u8>,
+    written: u64,                        // Total bytes of output so far
+}
+
+fn terminals() -> &'static Mutex<HashMap<String, PtyEntry>> {
+    static TERMINALS: OnceLock<Mutex<HashMap<String, PtyEntry>>> = OnceLock::new();
+    TERMINALS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+pub struct PtyTerminals;
+
+impl PtyTerminals {
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn size(cols: u16, rows: u16) -> PtySize {
+        PtySize {
+            rows: rows.max(1),
+            cols: cols.max(1),
+            pixel_width: 0,
+            pixel_height: 0,
+        }
+    }
+
+    /// npm installs `claude` as a .cmd shim on Windows, which needs cmd.exe to run
+    fn claude_command(args: &[String]) -> CommandBuilder {
+        let resolved = DependencyChecker::resolve_binary("claude");
+        let is_script = resolved
+            .as_deref()
+            .and_then(|p| p.extension())
+            .map(|ext| ext.eq_ignore_ascii_case("cmd") || ext.eq_ignore_ascii_case("bat"))
+            .unwrap_or(false);
+
+        let mut command = if is_script {
+            let mut command = CommandBuilder::new("cmd.exe");
+            command.arg("/c");
+            command.arg(resolved.unwrap_or_else(|| PathBuf::from("claude")));
+            command
+        } else {
+            CommandBuilder::new(resolved.unwrap_or_else(|| PathBuf::from("claude")))
+        };
+        command.args(args);
+        command
+    }
+
+    /// Spawn `claude` under a new pseudo-terminal and stream its output through `on_event`
+    pub fn spawn<F>(options: PtySpawnOptions, on_event: F) -> Result<TerminalInfo, String>
+    where
+        F: Fn(PtyEvent) + Send + Sync + 'static,
+    {
+        static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+        let work_dir = match options.working_dir {
+            Some(ref dir) => {
+                let path = PathBuf::from(dir);
+                if !path.is_dir() {
+                    return Err(format!("工作目录不存在: {}", dir));
+                }
+                path
+            }
+            None => dirs::home_dir().ok_or("无法获取用户主目录")?,
+        };
+
+        let pair = native_pty_system()
+            .openpty(Self::size(options.cols, options.rows))
+            .map_err(|e| format!("无法创建伪终端: {}", e))?;
+
+        let mut command = match options.shell {
+            Some((ref program, ref args)) => {
+                let mut command = CommandBuilder::new(program);
+                command.args(args);
+                command
+            }
+            None => Self::claude_command(&options.args),
+        };
+        command.cwd(&work_dir);
+        command.env("PATH", DependencyChecker::search_path());
+        #[cfg(not(windows))]
+        {
+            command.env("TERM", "xterm-256color");
+            command.env("COLORTERM", "truecolor");
+        }
+        for (key, value) in &options.env {
+            command.env(key, value);
+        }
+
+        let mut child = pair
+            .slave
+            .spawn_command(command)
+            .map_err(|e| format!("无法启动 Claude Code: {}", e))?;
+        // The child holds its own handle; keeping ours would prevent EOF on exit
+        drop(pair.slave);
+
+        let reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| format!("无法读取伪终端: {}", e))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| format!("无法写入伪终端: {}", e))?;
+
+        let id = format!("term-{}", NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::SeqCst));
+        let pid = child.process_id();
+        let info = TerminalInfo {
+            id: id.clone(),
+            title: options.title,
+            project_id: options.project_id,
+            session_id: options.session_id.clone(),
+            pid,
+            cols: options.cols,
+            rows: options.rows,
+            started_at: Self::now(),
+            exited: false,
+            exit_code: None,
+        };
+
+        Logger::log(LogLevel::Info, "pty", "terminal started", serde_json::json!({
+            "terminal_id": id,
+            "pid": pid,
+            "working_dir": work_dir.to_string_lossy(),
+            "session_id": options.session_id,
+        }));
+
+        if let (Some(session_id), Some(pid)) = (options.session_id.as_deref(), pid) {
+            let _ = Sessions::report_started(session_id, pid);
+        }
+
+        terminals()
+            .lock()
+            .map_err(|_| "终端列表被锁定".to_string())?
+            .insert(
+                id.clone(),
+                PtyEntry {
+                    info: info.clone(),
+                    master: Some(pair.master),
+                    writer: Some(Arc::new(Mutex::new(writer))),
+                    killer: child.clone_killer(),
+                    scrollback: VecDeque::new(),
+                    written: 0,
+                },
+            );
+
+        let on_event = Arc::new(on_event);
+
+        let (drained, reader_done) = mpsc::channel::<()>();
+        {
+            let id = id.clone();
+            let on_event = on_event.clone();
+            std::thread::spawn(move || {
+                Self::pump(&id, reader, on_event.as_ref());
+                let _ = drained.send(());
+            });
+        }
+
+        let session_id = options.session_id;
+        std::thread::spawn(move || {
+            let exit_code = child.wait().ok().map(|status| status.exit_code() as i32);
+
+            // Dropping the master closes the terminal; the reader then ends unless a grandchild
+            // still holds the slave, so the exit is reported after a bounded wait for the output
+            if let Ok(mut terminals) = terminals().lock() {
+                if let Some(entry) = terminals.get_mut(&id) {
+                    entry.master = None;
+                    entry.writer = None;
+                    entry.info.exited = true;
+                    entry.info.exit_code = exit_code;
+                }
+            }
+            let _ = reader_done.recv_timeout(DRAIN_TIMEOUT);
+
+            Logger::log(LogLevel::Info, "pty", "terminal exited", serde_json::json!({
+                "terminal_id": id,
+                "exit_code": exit_code,
+            }));
+            if let Some(ref session_id) = session_id {
+                let _ = Sessions::report_exited(session_id, exit_code.unwrap_or(-1));
+            }
+            on_event(PtyEvent::Exit { terminal_id: id, exit_code });
+        });
+
+        Ok(info)
+    }
+
+    fn pump<F>(id: &str, mut reader: Box<dyn Read + Send>, on_event: &F)
+    where
+        F: Fn(PtyEvent),
+    {
+        let mut buf = [0u8; 8192];
+        loop {
+            let n = match reader.read(&mut buf) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            let chunk = &buf[..n];
+
+            let offset = match terminals().lock() {
+                Ok(mut terminals) => match terminals.get_mut(id) {
+                    Some(entry) => {
+                        let offset = entry.written;
+                        entry.written += n as u64;
+                        entry.scrollback.extend(chunk);
+                        let excess = entry.scrollback.len().saturating_sub(SCROLLBACK_BYTES);
+                        entry.scrollback.drain(..excess);
+                        offset
+                    }
+                    // Closed by the user; keep draining so the child is not blocked on a full pipe
+                    None => continue,
+                },
+                Err(_) => break,
+            };
+
+            on_event(PtyEvent::Output {
+                terminal_id: id.to_string(),
+                offset,
+                data: general_purpose::STANDARD.encode(chunk),
+            });
+        }
+    }
+
+    /// Forward keyboard input (already encoded by the terminal view) to the child
+    pub fn write(id: &str, data: &str) -> Result<(), String> {
+        // Blocks while the child is not reading, so the list lock is released first
+        let writer = {
+            let terminals = terminals().lock().map_err(|_| "终端列表被锁定".to_string())?;
+            let entry = terminals.get(id).ok_or_else(|| format!("终端不存在: {}", id))?;
+            entry.writer.clone().ok_or("终端已退出")?
+        };
+        let mut writer = writer.lock().map_err(|_| "终端输入被锁定".to_string())?;
+        writer
+            .write_all(data.as_bytes())
+            .and_then(|_| writer.flush())
+            .map_err(|e| format!("无法写入终端: {}", e))
+    }
+
+    pub fn resize(id: &str, cols: u16, rows: u16) -> Result<(), String> {
+        let mut terminals = terminals().lock().map_err(|_| "终端列表被锁定".to_string())?;
+        let entry = terminals.get_mut(id).ok_or_else(|| format!("终端不存在: {}", id))?;
+        if let Some(ref master) = entry.master {
+            master
+                .resize(Self::size(cols, rows))
+                .map_err(|e| format!("无法调整终端大小: {}", e))?;
+        }
+        entry.info.cols = cols;
+        entry.info.rows = rows;
+        Ok(())
+    }
+
+    pub fn snapshot(id: &str) -> Result<TerminalSnapshot, String> {
+        let terminals = terminals().lock().map_err(|_| "终端列表被锁定".to_string())?;
+        let entry = terminals.get(id).ok_or_else(|| format!("终端不存在: {}", id))?;
+        let (front, back) = entry.scrollback.as_slices();
+        Ok(TerminalSnapshot {
+            data: general_purpose::STANDARD.encode([front, back].concat()),
+            end: entry.written,
+        })
+    }
+
+    /// Kill the child if it is still running and forget the terminal
+    pub fn close(id: &str) -> Result<(), String> {
+        let entry = terminals()
+            .lock()
+            .map_err(|_| "终端列表被锁定".to_string())?
+            .remove(id);
+        if let Some(mut entry) = entry {
+            if !entry.info.exited {
+                let _ = entry.killer.kill();
+            }
+        }
+        Ok(())
+    }
+
+    pub fn list() -> Vec<TerminalInfo> {
+        let mut list: Vec<TerminalInfo> = terminals()
+            .lock()
+            .map(|terminals| terminals.values().map(|entry| entry.info.clone()).collect())
+            .unwrap_or_default();
+        list.sort_by_key(|info| info.started_at);
+        list
+    }
+}
@@ -0,0 +1,362 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use super::logger::{LogLevel, Logger};
+
+/// Sessions kept in sessions.json; older finished ones are dropped
+const MAX_SESSIONS: usize = 500;
+
+/// A launch that never reported its PID within this many seconds is considered lost
+const START_TIMEOUT_SECS: u64 = 120;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionState {
+    Starting,                            // Terminal spawned, wrapper has not reported yet
+    Running,
+    Exited,                              // Wrapper reported the exit code of claude
+    Lost,                                // Terminal closed (or never started) without reporting an exit
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LaunchSession {
+    pub id: String,
+    pub project_id: Option<String>,      // None for launches from the legacy config panel
+    pub project_name: Option<String>,
+    pub working_directory: Option<String>,
+    pub pid: Option<u32>,                // PID of the shell wrapping claude inside the terminal
+    pub state: SessionState,
+    pub started_at: u64,                 // Unix timestamp
+    pub ended_at: Option<u64>,
+    pub exit_code: Option<i32>,
+}
+
+impl LaunchSession {
+    pub fn is_running(&self) -> bool {
+        matches!(self.state, SessionState::Starting | SessionState::Running)
+    }
+
+    /// Seconds spent in the session so far (up to now for running sessions)
+    pub fn duration_secs(&self, now: u64) -> u64 {
+        self.ended_at.unwrap_or(now).saturating_sub(self.started_at)
+    }
+}
+
+/// Aggregate for the project list: running badge and total time spent
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectSessionStats {
+    pub running: usize,
+    pub session_count: usize,
+    pub total_secs: u64,
+}
+
+/// On-disk format of sessions.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct SessionsFile {
+    #[serde(default)]
+    sessions: Vec<LaunchSession>,
+}
+
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+pub struct Sessions;
+
+impl Sessions {
+    fn config_dir() -> Result<PathBuf, String> {
+        let config_dir = dirs::config_dir()
+            .ok_or("无法获取配置目录")?
+            .join("ClaudeCodeLauncher");
+
+        if !config_dir.exists() {
+            fs::create_dir_all(&config_dir)
+                .map_err(|e| format!("无法创建配置目录: {}", e))?;
+        }
+
+        Ok(config_dir)
+    }
+
+    fn get_sessions_path() -> Result<PathBuf, String> {
+        Ok(Self::config_dir()?.join("sessions.json"))
+    }
+
+    /// Serializes read-modify-write cycles on sessions.json. The GUI and the command-line
+    /// interface run in separate processes, so this is a file lock; it is released on drop.
+    fn lock() -> Result<fs::File, String> {
+        let path = Self::config_dir()?.join("sessions.lock");
+        let file = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .map_err(|e| format!("无法打开会话锁 {}: {}", path.display(), e))?;
+        file.lock().map_err(|e| format!("会话记录被锁定: {}", e))?;
+        Ok(file)
+    }
+
+    /// Directory the terminal wrapper reports into: `<id>.pid` on start, `<id>.exit` on exit
+    pub fn report_dir() -> Result<PathBuf, String> {
+        let dir = Self::config_dir()?.join("sessions");
+        if !dir.exists() {
+            fs::create_dir_all(&dir)
+                .map_err(|e| format!("无法创建会话目录: {}", e))?;
+        }
+        Ok(dir)
+    }
+
+    pub fn pid_file(id: &str) -> Result<PathBuf, String> {
+        Ok(Self::report_dir()?.join(format!("{}.pid", id)))
+    }
+
+    pub fn exit_file(id: &str) -> Result<PathBuf, String> {
+        Ok(Self::report_dir()?.join(format!("{}.exit", id)))
+    }
+
+    /// Output of the session's launch hooks, appended by the launch script; kept after the session ends
+    pub fn hook_log_file(id: &str) -> Result<PathBuf, String> {
+        Ok(Self::report_dir()?.join(format!("{}.hooks.log", id)))
+    }
+
+    /// Captured hook output of a session (secrets redacted); empty if it ran no hooks
+    pub fn hook_output(id: &str) -> Result<String, String> {
+        match fs::read_to_string(Self::hook_log_file(id)?) {
+            Ok(content) => Ok(Logger::redact(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+            Err(e) => Err(format!("无法读取钩子输出: {}", e)),
+        }
+    }
+
+    fn load() -> SessionsFile {
+        Self::get_sessions_path()
+            .ok()
+            .and_then(|path| fs::read_to_string(path).ok())
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write to a temp file next to sessions.json and rename it over, so readers never see half a file
+    fn save(file: &SessionsFile) -> Result<(), String> {
+        let path = Self::get_sessions_path()?;
+        let json_string = serde_json::to_string_pretty(file)
+            .map_err(|e| format!("无法序列化会话记录: {}", e))?;
+
+        let mut temp = tempfile::NamedTempFile::new_in(Self::config_dir()?)
+            .map_err(|e| format!("无法写入会话记录: {}", e))?;
+        temp.write_all(json_string.as_bytes())
+            .map_err(|e| format!("无法写入会话记录: {}", e))?;
+        temp.persist(&path)
+            .map(|_| ())
+            .map_err(|e| format!("无法写入会话记录: {}", e.error))
+    }
+
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    /// Register a launch before the terminal is spawned, so the wrapper knows where to report
+    pub fn begin(project_id: Option<&str>, project_name: Option<&str>, working_directory: Option<&str>) -> Result<LaunchSession, String> {
+        let _lock = Self::lock()?;
+
+        let started_at = Self::now();
+        let session = LaunchSession {
+            id: format!("{}-{}-{}", started_at, std::process::id(), NEXT_ID.fetch_add(1, Ordering::SeqCst)),
+            project_id: project_id.map(|s| s.to_string()),
+            project_name: project_name.map(|s| s.to_string()),
+            working_directory: working_directory.map(|s| s.to_string()),
+            pid: None,
+            state: SessionState::Starting,
+            started_at,
+            ended_at: None,
+            exit_code: None,
+        };
+
+        let mut file = Self::load();
+        file.sessions.push(session.clone());
+        if file.sessions.len() > MAX_SESSIONS {
+            let excess = file.sessions.len() - MAX_SESSIONS;
+            // Drop the oldest finished sessions first; running ones are never evicted
+            let mut dropped = 0;
+            file.sessions.retain(|s| {
+                if dropped < excess && !s.is_running() {
+                    dropped += 1;
+                    if let Ok(path) = Self::hook_log_file(&s.id) {
+                        let _ = fs::remove_file(path);
+                    }
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+        Self::save(&file)?;
+
+        Logger::log(LogLevel::Info, "session", "session started", serde_json::json!({
+            "session_id": session.id,
+            "project_id": session.project_id,
+            "working_directory": session.working_directory,
+        }));
+        Ok(session)
+    }
+
+    /// Forget a session whose terminal could not be started
+    pub fn abort(id: &str) {
+        let Ok(_lock) = Self::lock() else {
+            return;
+        };
+        let mut file = Self::load();
+        file.sessions.retain(|s| s.id != id);
+        let _ = Self::save(&file);
+        Self::remove_reports(id);
+        if let Ok(path) = Self::hook_log_file(id) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    fn remove_reports(id: &str) {
+        if let Ok(path) = Self::pid_file(id) {
+            let _ = fs::remove_file(path);
+        }
+        if let Ok(path) = Self::exit_file(id) {
+            let _ = fs::remove_file(path);
+        }
+    }
+
+    /// Report from inside the launcher itself, for sessions that run in an in-app terminal
+    pub fn report_started(id: &str, pid: u32) -> Result<(), String> {
+        fs::write(Self::pid_file(id)?, pid.to_string())
+            .map_err(|e| format!("无法写入会话状态: {}", e))
+    }
+
+    pub fn report_exited(id: &str, exit_code: i32) -> Result<(), String> {
+        fs::write(Self::exit_file(id)?, exit_code.to_string())
+            .map_err(|e| format!("无法写入会话状态: {}", e))
+    }
+
+    fn read_report(path: &PathBuf) -> Option<(String, u64)> {
+        let content = fs::read_to_string(path).ok()?;
+        let modified = fs::metadata(path)
+            .and_then(|m| m.modified())
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+            .unwrap_or_else(Self::now);
+        // PowerShell's Out-File may prepend a BOM
+        Some((content.trim_start_matches('\u{feff}').trim().to_string(), modified))
+    }
+
+    #[cfg(windows)]
+    fn is_process_alive(pid: u32) -> bool {
+        use windows::Win32::Foundation::CloseHandle;
+        use windows::Win32::System::Threading::{GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION};
+        const STILL_ACTIVE: u32 = 259;
+
+        unsafe {
+            let Ok(handle) = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, false, pid) else {
+                return false;
+            };
+            let mut code = 0u32;
+            let alive = GetExitCodeProcess(handle, &mut code).is_ok() && code == STILL_ACTIVE;
+            let _ = CloseHandle(handle);
+            alive
+        }
+    }
+
+    #[cfg(not(windows))]
+    fn is_process_alive(pid: u32) -> bool {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+
+    /// Fold wrapper reports and process liveness into a running session
+    fn refresh(session: &mut LaunchSession, now: u64) {
+        if !session.is_running() {
+            return;
+        }
+
+        if session.pid.is_none() {
+            if let Some((pid, _)) = Self::pid_file(&session.id).ok().as_ref().and_then(Self::read_report) {
+                session.pid = pid.parse().ok();
+                session.state = SessionState::Running;
+            }
+        }
+
+        if let Some((code, modified)) = Self::exit_file(&session.id).ok().as_ref().and_then(Self::read_report) {
+            session.state = SessionState::Exited;
+            session.exit_code = code.parse().ok();
+            session.ended_at = Some(modified.max(session.started_at));
+        } else if let Some(pid) = session.pid {
+            if !Self::is_process_alive(pid) {
+                session.state = SessionState::Lost;
+                session.ended_at = Some(now);
+            }
+        } else if now.saturating_sub(session.started_at) > START_TIMEOUT_SECS {
+            session.state = SessionState::Lost;
+            session.ended_at = Some(now);
+        }
+
+        if !session.is_running() {
+            Self::remove_reports(&session.id);
+        }
+    }
+
+    /// All sessions, newest first, optionally limited to one project
+    pub fn list(project_id: Option<&str>) -> Result<Vec<LaunchSession>, String> {
+        let _lock = Self::lock()?;
+
+        let mut file = Self::load();
+        let now = Self::now();
+        let before: Vec<SessionState> = file.sessions.iter().map(|s| s.state).collect();
+        for session in file.sessions.iter_mut() {
+            Self::refresh(session, now);
+        }
+        if file.sessions.iter().map(|s| s.state).ne(before) {
+            Self::save(&file)?;
+        }
+
+        let mut sessions: Vec<LaunchSession> = file
+            .sessions
+            .into_iter()
+            .filter(|s| project_id.is_none() || s.project_id.as_deref() == project_id)
+            .collect();
+        sessions.reverse();
+        Ok(sessions)
+    }
+
+    pub fn get(id: &str) -> Result<LaunchSession, String> {
+        Self::list(None)?
+            .into_iter()
+            .find(|s| s.id == id)
+            .ok_or_else(|| format!("会话不存在: {}", id))
+    }
+
+    /// Running count and total time per project id
+    pub fn project_stats() -> Result<HashMap<String, ProjectSessionStats>, String> {
+        let now = Self::now();
+        let mut stats: HashMap<String, ProjectSessionStats> = HashMap::new();
+
+        for session in Self::list(None)? {
+            let Some(project_id) = session.project_id.clone() else {
+                continue;
+            };
+            let entry = stats.entry(project_id).or_default();
+            entry.session_count += 1;
+            entry.total_secs += session.duration_secs(now);
+            if session.is_running() {
+                entry.running += 1;
+            }
+        }
+
+        Ok(stats)
+    }
+}
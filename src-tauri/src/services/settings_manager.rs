@@ -3,10 +3,13 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use std::fs;
 
+use super::file_watch::FileWatch;
+use super::launcher::LAUNCH_ENV_KEYS;
+
 pub struct SettingsManager;
 
 impl SettingsManager {
-    fn get_settings_path() -> Result<PathBuf, String> {
+    pub fn get_settings_path() -> Result<PathBuf, String> {
         let home = dirs::home_dir().ok_or("无法获取用户主目录")?;
         Ok(home.join(".claude").join("settings.json"))
     }
@@ -16,7 +19,14 @@ impl SettingsManager {
         Ok(home.join(".claude"))
     }
 
-    pub fn save_config(config: HashMap<String, String>) -> Result<(), String> {
+    /// Version of settings.json to pass back to `save_config` / `reset_config`
+    pub fn file_version() -> Result<String, String> {
+        Ok(FileWatch::version(&Self::get_settings_path()?))
+    }
+
+    /// `version` is the `file_version` the caller's view of settings.json was loaded at;
+    /// without it the save is only checked against changes made while it runs
+    pub fn save_config(config: HashMap<String, String>, version: Option<&str>) -> Result<(), String> {
         let claude_dir = Self::get_claude_dir()?;
 
         // 检查目录是否存在
@@ -30,9 +40,11 @@ impl SettingsManager {
         let settings_path = Self::get_settings_path()?;
 
         // 读取现有配置
+        let mut read_version = String::new();
         let mut existing_data: Value = if settings_path.exists() {
             match fs::read_to_string(&settings_path) {
                 Ok(content) => {
+                    read_version = FileWatch::version_of(Some(content.as_bytes()));
                     match serde_json::from_str(&content) {
                         Ok(data) => data,
                         Err(_) => {
@@ -72,13 +84,39 @@ impl SettingsManager {
         let json_string = serde_json::to_string_pretty(&existing_data)
             .map_err(|e| format!("无法序列化JSON: {}", e))?;
 
-        fs::write(&settings_path, json_string)
-            .map_err(|e| format!("无法写入文件: {}", e))?;
+        // Claude Code rewrites this file too; do not overwrite its changes made since we read it
+        FileWatch::write(&settings_path, json_string.as_bytes(), version.unwrap_or(&read_version))?;
 
         Ok(())
     }
 
-    pub fn reset_config() -> Result<(), String> {
+    /// Env keys in settings.json that the launcher writes and `reset_config` removes
+    fn is_launcher_env_key(key: &str) -> bool {
+        key.starts_with("ANTHROPIC_") || LAUNCH_ENV_KEYS.contains(&key)
+    }
+
+    /// List the launcher-written env keys currently in settings.json (for a dry run of `reset_config`)
+    pub fn launcher_env_keys() -> Result<(PathBuf, Vec<String>), String> {
+        let settings_path = Self::get_settings_path()?;
+        if !settings_path.exists() {
+            return Ok((settings_path, Vec::new()));
+        }
+
+        let content = fs::read_to_string(&settings_path)
+            .map_err(|e| format!("无法读取文件: {}", e))?;
+        let data: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("无法解析JSON: {}", e))?;
+
+        let keys = data
+            .get("env")
+            .and_then(|v| v.as_object())
+            .map(|env| env.keys().filter(|k| Self::is_launcher_env_key(k)).cloned().collect())
+            .unwrap_or_default();
+
+        Ok((settings_path, keys))
+    }
+
+    pub fn reset_config(version: Option<&str>) -> Result<(), String> {
         let claude_dir = Self::get_claude_dir()?;
 
         // 检查目录
@@ -96,6 +134,7 @@ impl SettingsManager {
         // 读取配置
         let content = fs::read_to_string(&settings_path)
             .map_err(|e| format!("无法读取文件: {}", e))?;
+        let read_version = FileWatch::version_of(Some(content.as_bytes()));
 
         let mut data: Value = serde_json::from_str(&content)
             .map_err(|e| format!("无法解析JSON: {}", e))?;
@@ -103,7 +142,7 @@ impl SettingsManager {
         // 删除相关环境变量
         if let Some(env) = data.get_mut("env").and_then(|v| v.as_object_mut()) {
             let keys_to_remove: Vec<String> = env.keys()
-                .filter(|k| k.starts_with("ANTHROPIC_") || k.as_str() == "HTTP_PROXY" || k.as_str() == "HTTPS_PROXY")
+                .filter(|k| Self::is_launcher_env_key(k))
                 .cloned()
                 .collect();
 
@@ -120,16 +159,14 @@ impl SettingsManager {
         }
 
         // 如果整个配置为空,删除文件
-        if data.as_object().map_or(true, |o| o.is_empty()) {
-            fs::remove_file(&settings_path)
-                .map_err(|e| format!("无法删除文件: {}", e))?;
+        if data.as_object().is_none_or(|o| o.is_empty()) {
+            FileWatch::remove(&settings_path, version.unwrap_or(&read_version))?;
         } else {
             // 写回文件
             let json_string = serde_json::to_string_pretty(&data)
                 .map_err(|e| format!("无法序列化JSON: {}", e))?;
 
-            fs::write(&settings_path, json_string)
-                .map_err(|e| format!("无法写入文件: {}", e))?;
+            FileWatch::write(&settings_path, json_string.as_bytes(), version.unwrap_or(&read_version))?;
         }
 
         Ok(())
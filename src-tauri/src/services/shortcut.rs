@@ -0,0 +1,178 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+#[cfg(windows)]
+use std::process::Command;
+
+use crate::models::Project;
+
+/// Creates OS shortcuts that start a project through the command line (`launch <id>`),
+/// so a project opens in one click without going through the main window.
+///
+/// - Linux: a freedesktop entry in `~/.local/share/applications` (shows up in the app menu)
+/// - macOS: an executable `.command` script on the desktop, run by Terminal.app
+/// - Windows: a `.lnk` on the desktop; the GUI binary has no console, so it opens a new window
+pub struct Shortcuts;
+
+impl Shortcuts {
+    /// The launcher binary to invoke; inside an AppImage the running binary lives in a temporary mount
+    fn executable() -> Result<PathBuf, String> {
+        if let Some(appimage) = std::env::var_os("APPIMAGE") {
+            return Ok(PathBuf::from(appimage));
+        }
+        std::env::current_exe().map_err(|e| format!("无法获取启动器路径: {}", e))
+    }
+
+    /// File name of a project's shortcut: the name for the user, the id so two projects with
+    /// the same name do not overwrite each other's shortcut. Names may contain characters
+    /// that are not allowed in file names.
+    #[cfg(any(windows, target_os = "macos"))]
+    fn file_stem(project: &Project) -> String {
+        let name = project.name.trim().trim_end_matches('.');
+        let stem = if name.is_empty() {
+            project.id.clone()
+        } else {
+            format!("{} ({})", name, project.id)
+        };
+        stem.chars()
+            .map(|c| if c.is_control() || r#"<>:"/\|?*"#.contains(c) { '_' } else { c })
+            .collect()
+    }
+
+    #[cfg(any(windows, target_os = "macos"))]
+    fn desktop_dir() -> Result<PathBuf, String> {
+        dirs::desktop_dir()
+            .or_else(|| dirs::home_dir().map(|home| home.join("Desktop")))
+            .ok_or("无法获取桌面目录".to_string())
+    }
+
+    /// Create (or recreate) the shortcut of a project and return its path
+    pub fn create(project: &Project) -> Result<String, String> {
+        let exe = Self::executable()?;
+
+        // A previous shortcut may carry an outdated name
+        if let Some(ref old) = project.shortcut_path {
+            let _ = fs::remove_file(old);
+        }
+
+        let path = Self::write(project, &exe)?;
+        Ok(path.to_string_lossy().to_string())
+    }
+
+    /// Remove the shortcut recorded for a project; a shortcut deleted by hand is not an error
+    pub fn remove(project: &Project) -> Result<(), String> {
+        let Some(ref path) = project.shortcut_path else {
+            return Ok(());
+        };
+        match fs::remove_file(path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(format!("无法删除快捷方式 {}: {}", path, e)),
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    fn write(project: &Project, exe: &Path) -> Result<PathBuf, String> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = dirs::data_dir()
+            .ok_or("无法获取用户数据目录")?
+            .join("applications");
+        fs::create_dir_all(&dir).map_err(|e| format!("无法创建目录 {}: {}", dir.display(), e))?;
+        let path = dir.join(format!("claude-launcher-{}.desktop", project.id));
+
+        // Exec quoting per the desktop entry spec; `%` starts a field code and must be doubled
+        let quote = |value: &str| {
+            let escaped: String = value
+                .chars()
+                .flat_map(|c| match c {
+                    '"' | '`' | '$' | '\\' => vec!['\\', c],
+                    '%' => vec!['%', '%'],
+                    _ => vec![c],
+                })
+                .collect();
+            format!("\"{}\"", escaped)
+        };
+        // Values of other keys only need newlines kept out
+        let single_line = |value: &str| value.replace(['\n', '\r'], " ");
+
+        let content = format!(
+            "[Desktop Entry]\n\
+             Type=Application\n\
+             Name={name}\n\
+             Comment=使用 Claude Code 打开 {dir}\n\
+             Exec={exe} launch {id}\n\
+             Path={dir}\n\
+             Icon=utilities-terminal\n\
+             Terminal=true\n\
+             Categories=Development;\n",
+            name = single_line(&format!("Claude: {}", project.name)),
+            dir = single_line(&project.working_directory),
+            exe = quote(&exe.to_string_lossy()),
+            id = quote(&project.id),
+        );
+
+        fs::write(&path, content).map_err(|e| format!("无法写入快捷方式 {}: {}", path.display(), e))?;
+        // Some desktops only trust launcher entries that are executable
+        let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o755));
+        Ok(path)
+    }
+
+    #[cfg(target_os = "macos")]
+    fn write(project: &Project, exe: &Path) -> Result<PathBuf, String> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "'\\''"));
+        let path = Self::desktop_dir()?.join(format!("{}.command", Self::file_stem(project)));
+        let content = format!(
+            "#!/bin/sh\n# Claude Code 启动器: {}\nexec {} launch {}\n",
+            project.name.replace(['\n', '\r'], " "),
+            quote(&exe.to_string_lossy()),
+            quote(&project.id),
+        );
+
+        fs::write(&path, content).map_err(|e| format!("无法写入快捷方式 {}: {}", path.display(), e))?;
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| format!("无法设置快捷方式权限: {}", e))?;
+        Ok(path)
+    }
+
+    #[cfg(windows)]
+    fn write(project: &Project, exe: &Path) -> Result<PathBuf, String> {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+
+        let quote = |value: &str| format!("'{}'", value.replace('\'', "''"));
+        let path = Self::desktop_dir()?.join(format!("{}.lnk", Self::file_stem(project)));
+        let exe = exe.to_string_lossy().to_string();
+
+        // .lnk is a binary format; let the shell's own COM object write it
+        let script = format!(
+            "$s = (New-Object -ComObject WScript.Shell).CreateShortcut({path}); \
+             $s.TargetPath = {exe}; \
+             $s.Arguments = {args}; \
+             $s.WorkingDirectory = {dir}; \
+             $s.IconLocation = {icon}; \
+             $s.Description = {description}; \
+             $s.Save()",
+            path = quote(&path.to_string_lossy()),
+            exe = quote(&exe),
+            args = quote(&format!("launch \"{}\" --new-window", project.id)),
+            dir = quote(&project.working_directory),
+            icon = quote(&format!("{},0", exe)),
+            description = quote(&format!("使用 Claude Code 打开 {}", project.name)),
+        );
+
+        let output = Command::new("powershell.exe")
+            .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+            .creation_flags(CREATE_NO_WINDOW)
+            .output()
+            .map_err(|e| format!("无法运行 PowerShell: {}", e))?;
+        if !output.status.success() {
+            return Err(format!(
+                "无法创建快捷方式: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(path)
+    }
+}
@@ -0,0 +1,314 @@
+use serde::Serialize;
+#[cfg(not(windows))]
+use std::process::Command;
+
+use super::proxy::ProxySettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProxySource {
+    Environment,                         // HTTP_PROXY/HTTPS_PROXY/NO_PROXY of the launcher process
+    Windows,                             // Internet Settings in the registry
+    Macos,                               // `scutil --proxy` (System Settings > Network)
+    Gnome,                               // gsettings org.gnome.system.proxy
+    Kde,                                 // ~/.config/kioslaverc
+}
+
+/// Proxy configuration found in one place on this machine
+#[derive(Debug, Clone, Serialize)]
+pub struct DetectedProxy {
+    pub source: ProxySource,
+    pub http_proxy: Option<String>,
+    pub https_proxy: Option<String>,
+    pub no_proxy: Option<String>,        // Comma-separated, already in NO_PROXY syntax
+    pub pac_url: Option<String>,         // Automatic configuration script; the launcher cannot evaluate it
+}
+
+impl DetectedProxy {
+    fn new(source: ProxySource) -> Self {
+        Self {
+            source,
+            http_proxy: None,
+            https_proxy: None,
+            no_proxy: None,
+            pac_url: None,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.http_proxy.is_none() && self.https_proxy.is_none() && self.pac_url.is_none()
+    }
+
+    pub fn settings(&self) -> ProxySettings {
+        ProxySettings {
+            http_proxy: self.http_proxy.clone(),
+            https_proxy: self.https_proxy.clone(),
+            no_proxy: self.no_proxy.clone(),
+        }
+    }
+}
+
+pub struct SystemProxy;
+
+impl SystemProxy {
+    /// Every proxy configuration found, most specific first (environment variables win)
+    pub fn detect() -> Vec<DetectedProxy> {
+        let mut found = vec![Self::from_env()];
+
+        #[cfg(windows)]
+        found.push(Self::from_windows());
+        #[cfg(target_os = "macos")]
+        found.push(Self::from_macos());
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            found.push(Self::from_gnome());
+            found.push(Self::from_kde());
+        }
+
+        found.into_iter().filter(|p| !p.is_empty()).collect()
+    }
+
+    /// Settings to inherit at launch: the first source that names an actual proxy
+    pub fn effective() -> ProxySettings {
+        Self::detect()
+            .into_iter()
+            .find(|p| p.http_proxy.is_some() || p.https_proxy.is_some())
+            .map(|p| p.settings())
+            .unwrap_or_default()
+    }
+
+    /// `host:port` as stored by the OS settings, turned into a proxy URL
+    fn to_url(host: &str, port: Option<&str>) -> Option<String> {
+        let host = host.trim().trim_matches('\'');
+        if host.is_empty() {
+            return None;
+        }
+        let with_scheme = if host.contains("://") {
+            host.to_string()
+        } else {
+            format!("http://{}", host)
+        };
+        match port.map(str::trim).filter(|p| !p.is_empty() && *p != "0") {
+            Some(port) => Some(format!("{}:{}", with_scheme.trim_end_matches('/'), port)),
+            None => Some(with_scheme),
+        }
+    }
+
+    fn join_no_proxy<I: IntoIterator<Item = String>>(entries: I) -> Option<String> {
+        let entries: Vec<String> = entries
+            .into_iter()
+            .map(|e| e.trim().to_string())
+            .filter(|e| !e.is_empty())
+            .collect();
+        if entries.is_empty() {
+            None
+        } else {
+            Some(entries.join(","))
+        }
+    }
+
+    fn from_env() -> DetectedProxy {
+        let read = |names: [&str; 2]| {
+            names
+                .iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .map(|value| value.trim().to_string())
+                .find(|value| !value.is_empty())
+        };
+        let mut detected = DetectedProxy::new(ProxySource::Environment);
+        detected.http_proxy = read(["HTTP_PROXY", "http_proxy"]);
+        detected.https_proxy = read(["HTTPS_PROXY", "https_proxy"]);
+        detected.no_proxy = read(["NO_PROXY", "no_proxy"]);
+        detected
+    }
+
+    #[cfg(windows)]
+    fn from_windows() -> DetectedProxy {
+        use winreg::enums::*;
+        use winreg::RegKey;
+
+        let mut detected = DetectedProxy::new(ProxySource::Windows);
+        let Ok(settings) = RegKey::predef(HKEY_CURRENT_USER)
+            .open_subkey(r"Software\Microsoft\Windows\CurrentVersion\Internet Settings")
+        else {
+            return detected;
+        };
+
+        detected.pac_url = settings
+            .get_value::<String, _>("AutoConfigURL")
+            .ok()
+            .filter(|s| !s.trim().is_empty());
+
+        if settings.get_value::<u32, _>("ProxyEnable").unwrap_or(0) == 0 {
+            return detected;
+        }
+
+        // Either "host:port" for all protocols or "http=host:port;https=host:port"
+        let server: String = settings.get_value("ProxyServer").unwrap_or_default();
+        if server.contains('=') {
+            for part in server.split(';') {
+                if let Some((scheme, address)) = part.split_once('=') {
+                    match scheme.trim().to_ascii_lowercase().as_str() {
+                        "http" => detected.http_proxy = Self::to_url(address, None),
+                        "https" => detected.https_proxy = Self::to_url(address, None),
+                        _ => {}
+                    }
+                }
+            }
+        } else {
+            detected.http_proxy = Self::to_url(&server, None);
+            detected.https_proxy = detected.http_proxy.clone();
+        }
+
+        // "<local>" bypasses dotless intranet names, which NO_PROXY cannot express
+        let overrides: String = settings.get_value("ProxyOverride").unwrap_or_default();
+        detected.no_proxy = Self::join_no_proxy(overrides.split(';').map(|entry| {
+            if entry.trim() == "<local>" {
+                "localhost,127.0.0.1".to_string()
+            } else {
+                entry.to_string()
+            }
+        }));
+        detected
+    }
+
+    #[cfg(target_os = "macos")]
+    fn from_macos() -> DetectedProxy {
+        let mut detected = DetectedProxy::new(ProxySource::Macos);
+        let Ok(output) = Command::new("scutil").arg("--proxy").output() else {
+            return detected;
+        };
+        let text = String::from_utf8_lossy(&output.stdout);
+
+        let mut values = std::collections::HashMap::new();
+        let mut exceptions = Vec::new();
+        let mut in_exceptions = false;
+        for line in text.lines() {
+            let line = line.trim();
+            if line.starts_with("ExceptionsList") {
+                in_exceptions = true;
+                continue;
+            }
+            if in_exceptions {
+                if line == "}" {
+                    in_exceptions = false;
+                } else if let Some((_, value)) = line.split_once(" : ") {
+                    exceptions.push(value.to_string());
+                }
+                continue;
+            }
+            if let Some((key, value)) = line.split_once(" : ") {
+                values.insert(key.to_string(), value.to_string());
+            }
+        }
+
+        let enabled = |key: &str| values.get(key).map(|v| v == "1").unwrap_or(false);
+        if enabled("HTTPEnable") {
+            if let Some(host) = values.get("HTTPProxy") {
+                detected.http_proxy = Self::to_url(host, values.get("HTTPPort").map(String::as_str));
+            }
+        }
+        if enabled("HTTPSEnable") {
+            if let Some(host) = values.get("HTTPSProxy") {
+                detected.https_proxy = Self::to_url(host, values.get("HTTPSPort").map(String::as_str));
+            }
+        }
+        if enabled("ProxyAutoConfigEnable") {
+            detected.pac_url = values.get("ProxyAutoConfigURLString").cloned();
+        }
+        detected.no_proxy = Self::join_no_proxy(exceptions);
+        detected
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn gsettings(schema: &str, key: &str) -> Option<String> {
+        let output = Command::new("gsettings").args(["get", schema, key]).output().ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Some(value.trim_matches('\'').to_string())
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn from_gnome() -> DetectedProxy {
+        let mut detected = DetectedProxy::new(ProxySource::Gnome);
+        match Self::gsettings("org.gnome.system.proxy", "mode").as_deref() {
+            Some("manual") => {
+                for (schema, target) in [
+                    ("org.gnome.system.proxy.http", &mut detected.http_proxy),
+                    ("org.gnome.system.proxy.https", &mut detected.https_proxy),
+                ] {
+                    if let Some(host) = Self::gsettings(schema, "host") {
+                        *target = Self::to_url(&host, Self::gsettings(schema, "port").as_deref());
+                    }
+                }
+                // ['localhost', '127.0.0.0/8', '::1']
+                if let Some(hosts) = Self::gsettings("org.gnome.system.proxy", "ignore-hosts") {
+                    detected.no_proxy = Self::join_no_proxy(
+                        hosts
+                            .trim_start_matches('[')
+                            .trim_end_matches(']')
+                            .split(',')
+                            .map(|h| h.trim().trim_matches('\'').to_string()),
+                    );
+                }
+            }
+            Some("auto") => {
+                detected.pac_url = Self::gsettings("org.gnome.system.proxy", "autoconfig-url")
+                    .filter(|url| !url.is_empty());
+            }
+            _ => {}
+        }
+        detected
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn from_kde() -> DetectedProxy {
+        let mut detected = DetectedProxy::new(ProxySource::Kde);
+        let Some(path) = dirs::config_dir().map(|dir| dir.join("kioslaverc")) else {
+            return detected;
+        };
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return detected;
+        };
+
+        let mut in_section = false;
+        let mut values = std::collections::HashMap::new();
+        for line in content.lines() {
+            let line = line.trim();
+            if line.starts_with('[') {
+                in_section = line == "[Proxy Settings]";
+                continue;
+            }
+            if in_section {
+                if let Some((key, value)) = line.split_once('=') {
+                    values.insert(key.trim().to_string(), value.trim().to_string());
+                }
+            }
+        }
+
+        // KDE writes "http://host port" or "http://host:port"
+        let kde_url = |key: &str| {
+            values.get(key).and_then(|value| {
+                let mut parts = value.split_whitespace();
+                let host = parts.next()?;
+                Self::to_url(host, parts.next())
+            })
+        };
+        match values.get("ProxyType").map(String::as_str) {
+            Some("1") => {
+                detected.http_proxy = kde_url("httpProxy");
+                detected.https_proxy = kde_url("httpsProxy");
+                detected.no_proxy = values
+                    .get("NoProxyFor")
+                    .and_then(|hosts| Self::join_no_proxy(hosts.split(',').map(str::to_string)));
+            }
+            Some("2") => {
+                detected.pac_url = values.get("Proxy Config Script").cloned().filter(|url| !url.is_empty());
+            }
+            _ => {}
+        }
+        detected
+    }
+}
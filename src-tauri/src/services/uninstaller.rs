@@ -0,0 +1,386 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::models::ProjectConfig;
+use super::config_storage::ConfigStorage;
+use super::dependency_checker::{DependencyChecker, InstallMethod};
+use super::environment::Environment;
+use super::install_job::{InstallJobState, InstallJobs};
+#[cfg(windows)]
+use super::npm_prefix::NpmPrefix;
+use super::npm_registry::CLAUDE_PACKAGE;
+use super::settings_manager::SettingsManager;
+
+/// File name prefix of install scripts written to the temp directory
+pub const INSTALL_SCRIPT_PREFIX: &str = "claude_install";
+
+/// What to remove; every category is opt-in
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CleanupOptions {
+    #[serde(default)]
+    pub claude_package: bool,            // Uninstall Claude Code itself
+    #[serde(default)]
+    pub launcher_data: bool,             // config.json, update cache and logs
+    #[serde(default)]
+    pub settings_env: bool,              // env entries written to ~/.claude/settings.json
+    #[serde(default)]
+    pub user_env_vars: bool,             // Persistent user environment variables and PATH entries
+    #[serde(default)]
+    pub temp_scripts: bool,              // Leftover install scripts in the temp directory
+    #[serde(default)]
+    pub dry_run: bool,                   // Only list what would be removed
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CleanupCategory {
+    ClaudePackage,
+    LauncherData,
+    SettingsEnv,
+    UserEnvVars,
+    TempScripts,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CleanupAction {
+    DeleteFile { path: String },
+    DeleteDir { path: String },
+    RunCommand { program: String, args: Vec<String> },
+    RemoveSettingsEnv { path: String, keys: Vec<String> },
+    RemoveUserEnvVar { name: String },
+    /// The PATH lines the launcher appended to a shell startup file
+    RemoveProfilePath { path: String },
+    /// A directory the launcher added to the user PATH in the registry
+    RemoveUserPathEntry { dir: String },
+    /// Cannot be done automatically; `hint` tells the user what to do
+    Manual { hint: String },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupItem {
+    pub category: CleanupCategory,
+    pub action: CleanupAction,
+    pub done: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupReport {
+    pub dry_run: bool,
+    pub items: Vec<CleanupItem>,
+}
+
+pub struct Uninstaller;
+
+impl Uninstaller {
+    fn item(category: CleanupCategory, action: CleanupAction) -> CleanupItem {
+        CleanupItem {
+            category,
+            action,
+            done: false,
+            error: None,
+        }
+    }
+
+    fn path_string(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    fn npm_uninstall_action() -> CleanupAction {
+        #[cfg(windows)]
+        let (program, mut args) = ("cmd".to_string(), vec!["/c".to_string(), "npm".to_string()]);
+        #[cfg(not(windows))]
+        let (program, mut args) = ("npm".to_string(), Vec::new());
+
+        args.extend(["uninstall", "-g", CLAUDE_PACKAGE].iter().map(|s| s.to_string()));
+        CleanupAction::RunCommand { program, args }
+    }
+
+    /// Remove Claude Code the same way it was installed
+    fn plan_claude_package() -> Vec<CleanupItem> {
+        let status = DependencyChecker::check_claude();
+        let Some(path) = status.path.as_deref().map(PathBuf::from) else {
+            return Vec::new();
+        };
+        let category = CleanupCategory::ClaudePackage;
+
+        match status.install_method.unwrap_or(InstallMethod::Unknown) {
+            InstallMethod::Npm | InstallMethod::VersionManager => {
+                vec![Self::item(category, Self::npm_uninstall_action())]
+            }
+            InstallMethod::Native => {
+                let mut items = vec![Self::item(category, CleanupAction::DeleteFile { path: Self::path_string(&path) })];
+                if let Some(home) = dirs::home_dir() {
+                    for dir in [home.join(".local").join("share").join("claude"), home.join(".claude").join("local")] {
+                        if dir.is_dir() {
+                            items.push(Self::item(category, CleanupAction::DeleteDir { path: Self::path_string(&dir) }));
+                        }
+                    }
+                }
+                items
+            }
+            InstallMethod::Homebrew => vec![Self::item(
+                category,
+                CleanupAction::RunCommand {
+                    program: "brew".to_string(),
+                    args: vec!["uninstall".to_string(), "--cask".to_string(), "claude-code".to_string()],
+                },
+            )],
+            _ => vec![Self::item(
+                category,
+                CleanupAction::Manual {
+                    hint: format!("无法识别 Claude Code 的安装方式,请手动删除: {}", path.display()),
+                },
+            )],
+        }
+    }
+
+    /// Config and log folders named ClaudeCodeLauncher (they coincide on some platforms)
+    fn plan_launcher_data() -> Vec<CleanupItem> {
+        let mut dirs_found: Vec<PathBuf> = [dirs::config_dir(), dirs::data_local_dir()]
+            .into_iter()
+            .flatten()
+            .map(|base| base.join("ClaudeCodeLauncher"))
+            .filter(|dir| dir.is_dir())
+            .collect();
+        dirs_found.dedup();
+
+        dirs_found
+            .into_iter()
+            .map(|dir| Self::item(CleanupCategory::LauncherData, CleanupAction::DeleteDir { path: Self::path_string(&dir) }))
+            .collect()
+    }
+
+    fn plan_settings_env() -> Vec<CleanupItem> {
+        match SettingsManager::launcher_env_keys() {
+            Ok((_, keys)) if keys.is_empty() => Vec::new(),
+            Ok((path, keys)) => vec![Self::item(
+                CleanupCategory::SettingsEnv,
+                CleanupAction::RemoveSettingsEnv { path: Self::path_string(&path), keys },
+            )],
+            Err(e) => vec![CleanupItem {
+                error: Some(e),
+                ..Self::item(CleanupCategory::SettingsEnv, CleanupAction::Manual { hint: "请手动检查 ~/.claude/settings.json".to_string() })
+            }],
+        }
+    }
+
+    fn plan_user_env_vars() -> Vec<CleanupItem> {
+        let configs: Vec<ProjectConfig> = ConfigStorage::get_projects()
+            .map(|projects| projects.into_iter().map(|p| p.config).collect())
+            .unwrap_or_default();
+        let mut items = Self::plan_env_vars(Environment::get_permanent, &configs);
+        items.extend(Self::plan_user_path());
+        items
+    }
+
+    /// The value a launcher persisting `config` gave `key`
+    fn project_env_value<'a>(config: &'a ProjectConfig, key: &str) -> Option<&'a str> {
+        match key {
+            "ANTHROPIC_MODEL" => Some(&config.model),
+            "ANTHROPIC_BASE_URL" => Some(&config.base_url),
+            "ANTHROPIC_AUTH_TOKEN" => Some(&config.token),
+            "HTTP_PROXY" => Some(&config.proxy),
+            "HTTPS_PROXY" if config.https_proxy.is_empty() => Some(&config.proxy),
+            "HTTPS_PROXY" => Some(&config.https_proxy),
+            _ => None,
+        }
+    }
+
+    /// Only variables whose value matches a project config are removed; the others
+    /// were likely set by the user and are listed for manual review instead
+    fn plan_env_vars(persisted: impl Fn(&str) -> Option<String>, configs: &[ProjectConfig]) -> Vec<CleanupItem> {
+        Environment::get_env_keys()
+            .into_iter()
+            .filter_map(|name| {
+                let value = persisted(&name)?;
+                let set_by_launcher = !value.is_empty()
+                    && configs.iter().any(|config| Self::project_env_value(config, &name) == Some(value.as_str()));
+                let action = if set_by_launcher {
+                    CleanupAction::RemoveUserEnvVar { name }
+                } else {
+                    CleanupAction::Manual {
+                        hint: format!("环境变量 {} 与所有项目配置都不一致，可能是用户自行设置的，未自动删除；如需删除请手动处理", name),
+                    }
+                };
+                Some(Self::item(CleanupCategory::UserEnvVars, action))
+            })
+            .collect()
+    }
+
+    /// PATH entries for the user-level npm prefix (see `NpmPrefix::configure_user_prefix`)
+    #[cfg(windows)]
+    fn plan_user_path() -> Vec<CleanupItem> {
+        NpmPrefix::default_user_prefix()
+            .map(|prefix| NpmPrefix::bin_dir(&prefix))
+            .filter(|dir| Environment::user_path_contains(dir))
+            .map(|dir| Self::item(CleanupCategory::UserEnvVars, CleanupAction::RemoveUserPathEntry { dir: Self::path_string(&dir) }))
+            .into_iter()
+            .collect()
+    }
+
+    #[cfg(not(windows))]
+    fn plan_user_path() -> Vec<CleanupItem> {
+        Environment::profiles_with_path_entry()
+            .into_iter()
+            .map(|path| Self::item(CleanupCategory::UserEnvVars, CleanupAction::RemoveProfilePath { path: Self::path_string(&path) }))
+            .collect()
+    }
+
+    fn plan_temp_scripts() -> Vec<CleanupItem> {
+        let Ok(entries) = fs::read_dir(std::env::temp_dir()) else {
+            return Vec::new();
+        };
+
+        let mut scripts: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| {
+                let name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                let ext = path.extension().map(|e| e.to_string_lossy().to_lowercase()).unwrap_or_default();
+                path.is_file() && name.starts_with(INSTALL_SCRIPT_PREFIX) && ["bat", "sh", "ps1"].contains(&ext.as_str())
+            })
+            .collect();
+        scripts.sort();
+
+        scripts
+            .into_iter()
+            .map(|path| Self::item(CleanupCategory::TempScripts, CleanupAction::DeleteFile { path: Self::path_string(&path) }))
+            .collect()
+    }
+
+    /// Everything the given options would remove, without touching anything
+    pub fn plan(options: &CleanupOptions) -> Vec<CleanupItem> {
+        let mut items = Vec::new();
+        if options.claude_package {
+            items.extend(Self::plan_claude_package());
+        }
+        if options.settings_env {
+            items.extend(Self::plan_settings_env());
+        }
+        if options.user_env_vars {
+            items.extend(Self::plan_user_env_vars());
+        }
+        if options.temp_scripts {
+            items.extend(Self::plan_temp_scripts());
+        }
+        // Last, so the other steps can still read the launcher config
+        if options.launcher_data {
+            items.extend(Self::plan_launcher_data());
+        }
+        items
+    }
+
+    fn execute(action: &CleanupAction) -> Result<(), String> {
+        match action {
+            CleanupAction::DeleteFile { path } => fs::remove_file(path)
+                .map_err(|e| format!("无法删除文件 {}: {}", path, e)),
+            CleanupAction::DeleteDir { path } => fs::remove_dir_all(path)
+                .map_err(|e| format!("无法删除目录 {}: {}", path, e)),
+            CleanupAction::RunCommand { program, args } => {
+                let mut command = Command::new(program);
+                command.args(args);
+                #[cfg(windows)]
+                {
+                    use std::os::windows::process::CommandExt;
+                    const CREATE_NO_WINDOW: u32 = 0x08000000;
+                    command.creation_flags(CREATE_NO_WINDOW);
+                }
+                #[cfg(not(windows))]
+                command.env("PATH", DependencyChecker::search_path());
+
+                let output = command
+                    .output()
+                    .map_err(|e| format!("无法执行 {}: {}", program, e))?;
+                if output.status.success() {
+                    Ok(())
+                } else {
+                    Err(format!(
+                        "{} 执行失败 (退出码: {:?}): {}",
+                        program,
+                        output.status.code(),
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    ))
+                }
+            }
+            CleanupAction::RemoveSettingsEnv { .. } => SettingsManager::reset_config(None),
+            CleanupAction::RemoveUserEnvVar { name } => Environment::set_permanent(name, ""),
+            #[cfg(not(windows))]
+            CleanupAction::RemoveProfilePath { path } => Environment::remove_path_entry(Path::new(path)),
+            #[cfg(windows)]
+            CleanupAction::RemoveUserPathEntry { dir } => Environment::remove_from_user_path(Path::new(dir)),
+            // Only planned on the other platform
+            #[cfg(windows)]
+            CleanupAction::RemoveProfilePath { .. } => Err("此操作仅在 macOS/Linux 上可用".to_string()),
+            #[cfg(not(windows))]
+            CleanupAction::RemoveUserPathEntry { .. } => Err("此功能仅在Windows上可用".to_string()),
+            CleanupAction::Manual { hint } => Err(hint.clone()),
+        }
+    }
+
+    /// Run the cleanup, or only list it when `dry_run` is set. Failures are reported per item.
+    pub fn run(options: &CleanupOptions) -> Result<CleanupReport, String> {
+        let mut items = Self::plan(options);
+
+        if !options.dry_run {
+            if InstallJobs::list().iter().any(|job| job.state == InstallJobState::Running) {
+                return Err("有正在运行的安装任务,请等待完成后再清理".to_string());
+            }
+
+            for item in items.iter_mut() {
+                match Self::execute(&item.action) {
+                    Ok(()) => item.done = true,
+                    Err(e) => item.error = Some(e),
+                }
+            }
+        }
+
+        Ok(CleanupReport {
+            dry_run: options.dry_run,
+            items,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn only_env_vars_matching_a_project_are_removed() {
+        let config = ProjectConfig {
+            model: "glm-4".to_string(),
+            proxy: "http://proxy.corp:8080".to_string(),
+            ..ProjectConfig::default()
+        };
+        let persisted: HashMap<&str, &str> = [
+            ("ANTHROPIC_MODEL", "glm-4"),
+            ("HTTP_PROXY", "http://proxy.corp:8080"),
+            ("HTTPS_PROXY", "http://user-proxy:3128"),
+            ("NO_PROXY", "localhost"),
+            ("SSL_CERT_FILE", "/etc/ssl/corp.pem"),
+        ]
+        .into();
+
+        let items = Uninstaller::plan_env_vars(|key| persisted.get(key).map(|v| v.to_string()), &[config]);
+
+        let removed: Vec<&str> = items
+            .iter()
+            .filter_map(|item| match &item.action {
+                CleanupAction::RemoveUserEnvVar { name } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(removed, ["ANTHROPIC_MODEL", "HTTP_PROXY"]);
+
+        // The user's HTTPS_PROXY is only reported; executing the item leaves it alone
+        let manual: Vec<&CleanupItem> = items.iter().filter(|item| matches!(item.action, CleanupAction::Manual { .. })).collect();
+        assert_eq!(manual.len(), 1);
+        let error = Uninstaller::execute(&manual[0].action).unwrap_err();
+        assert!(error.contains("HTTPS_PROXY"), "{}", error);
+    }
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use crate::models::LauncherSettings;
+use super::config_storage::ConfigStorage;
+
+/// A cached "latest version" lookup for one dependency
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedVersion {
+    pub latest_version: String,
+    pub checked_at: u64,                 // Unix timestamp of the successful lookup
+}
+
+/// On-disk format of update_cache.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct UpdateCacheFile {
+    #[serde(default)]
+    entries: HashMap<String, CachedVersion>,
+}
+
+/// Result of resolving a latest version through the cache
+#[derive(Debug, Clone, Default)]
+pub struct LatestVersionLookup {
+    pub latest_version: Option<String>,
+    pub checked_at: Option<u64>,
+    pub stale: bool,                     // Value is older than the TTL, came from offline mode, or the live lookup failed
+}
+
+pub struct UpdateCache;
+
+impl UpdateCache {
+    fn get_cache_path() -> Result<PathBuf, String> {
+        let config_dir = dirs::config_dir()
+            .ok_or("无法获取配置目录")?
+            .join("ClaudeCodeLauncher");
+
+        if !config_dir.exists() {
+            fs::create_dir_all(&config_dir)
+                .map_err(|e| format!("无法创建配置目录: {}", e))?;
+        }
+
+        Ok(config_dir.join("update_cache.json"))
+    }
+
+    fn load(path: &Path) -> UpdateCacheFile {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn save(path: &Path, cache: &UpdateCacheFile) -> Result<(), String> {
+        let json_string = serde_json::to_string_pretty(cache)
+            .map_err(|e| format!("无法序列化更新缓存: {}", e))?;
+        fs::write(path, json_string)
+            .map_err(|e| format!("无法写入更新缓存: {}", e))
+    }
+
+    fn now() -> u64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+    }
+
+    fn put_at(path: &Path, key: &str, latest_version: &str, checked_at: u64) -> Result<(), String> {
+        let mut cache = Self::load(path);
+        cache.entries.insert(
+            key.to_string(),
+            CachedVersion {
+                latest_version: latest_version.to_string(),
+                checked_at,
+            },
+        );
+        Self::save(path, &cache)
+    }
+
+    pub fn clear() -> Result<(), String> {
+        let path = Self::get_cache_path()?;
+        if path.exists() {
+            fs::remove_file(&path)
+                .map_err(|e| format!("无法删除更新缓存: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Resolve the latest version for `key`, honouring the TTL and offline mode.
+    ///
+    /// A fresh cache entry is returned without calling `fetch`. When the live lookup
+    /// fails (or offline mode is on) the last cached value is returned with `stale` set.
+    pub async fn lookup<F, Fut>(key: &str, force: bool, fetch: F) -> LatestVersionLookup
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<String>>,
+    {
+        let settings = ConfigStorage::get_launcher_settings().unwrap_or_default();
+        match Self::get_cache_path() {
+            Ok(path) => Self::lookup_at(&path, &settings, Self::now(), key, force, fetch).await,
+            // Without a cache the lookup is always live
+            Err(_) if settings.offline_mode => LatestVersionLookup::default(),
+            Err(_) => LatestVersionLookup {
+                checked_at: Some(Self::now()),
+                latest_version: fetch().await,
+                stale: false,
+            },
+        }
+    }
+
+    async fn lookup_at<F, Fut>(
+        path: &Path,
+        settings: &LauncherSettings,
+        now: u64,
+        key: &str,
+        force: bool,
+        fetch: F,
+    ) -> LatestVersionLookup
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Option<String>>,
+    {
+        let cached = Self::load(path).entries.get(key).cloned();
+
+        let from_cache = |entry: &CachedVersion, stale: bool| LatestVersionLookup {
+            latest_version: Some(entry.latest_version.clone()),
+            checked_at: Some(entry.checked_at),
+            stale,
+        };
+
+        if settings.offline_mode {
+            // Never confirmed against the source, however recent
+            return cached.as_ref().map(|entry| from_cache(entry, true)).unwrap_or_default();
+        }
+        if let Some(ref entry) = cached {
+            let expired = now.saturating_sub(entry.checked_at) >= settings.update_check_ttl_secs;
+            if !force && !expired {
+                return from_cache(entry, false);
+            }
+        }
+
+        match fetch().await {
+            Some(version) => {
+                let _ = Self::put_at(path, key, &version, now);
+                LatestVersionLookup {
+                    latest_version: Some(version),
+                    checked_at: Some(now),
+                    stale: false,
+                }
+            }
+            None => cached
+                .as_ref()
+                .map(|entry| from_cache(entry, true))
+                .unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TTL: u64 = 60;
+
+    fn settings(offline_mode: bool) -> LauncherSettings {
+        LauncherSettings {
+            update_check_ttl_secs: TTL,
+            offline_mode,
+            ..LauncherSettings::default()
+        }
+    }
+
+    async fn lookup(path: &Path, offline_mode: bool, now: u64, fetched: Option<&str>) -> (LatestVersionLookup, bool) {
+        let called = std::cell::Cell::new(false);
+        let lookup = UpdateCache::lookup_at(path, &settings(offline_mode), now, "claude", false, || {
+            called.set(true);
+            std::future::ready(fetched.map(str::to_string))
+        })
+        .await;
+        (lookup, called.get())
+    }
+
+    #[tokio::test]
+    async fn fresh_entry_is_served_until_the_ttl_expires() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("update_cache.json");
+        UpdateCache::put_at(&path, "claude", "1.0.0", 1000).unwrap();
+
+        let (fresh, called) = lookup(&path, false, 1000 + TTL - 1, Some("2.0.0")).await;
+        assert!(!called);
+        assert_eq!(fresh.latest_version.as_deref(), Some("1.0.0"));
+        assert!(!fresh.stale);
+
+        let (renewed, called) = lookup(&path, false, 1000 + TTL, Some("2.0.0")).await;
+        assert!(called);
+        assert_eq!(renewed.latest_version.as_deref(), Some("2.0.0"));
+        assert_eq!(renewed.checked_at, Some(1000 + TTL));
+        assert!(!renewed.stale);
+        assert_eq!(UpdateCache::load(&path).entries["claude"].latest_version, "2.0.0");
+    }
+
+    #[tokio::test]
+    async fn offline_mode_never_fetches_and_marks_the_cache_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("update_cache.json");
+
+        let (empty, called) = lookup(&path, true, 1000, Some("2.0.0")).await;
+        assert!(!called);
+        assert_eq!(empty.latest_version, None);
+
+        UpdateCache::put_at(&path, "claude", "1.0.0", 1000).unwrap();
+        let (cached, called) = lookup(&path, true, 1001, Some("2.0.0")).await;
+        assert!(!called);
+        assert_eq!(cached.latest_version.as_deref(), Some("1.0.0"));
+        assert!(cached.stale);
+    }
+
+    #[tokio::test]
+    async fn failed_fetch_falls_back_to_the_expired_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("update_cache.json");
+
+        let (nothing, _) = lookup(&path, false, 1000, None).await;
+        assert_eq!(nothing.latest_version, None);
+        assert!(!nothing.stale);
+
+        UpdateCache::put_at(&path, "claude", "1.0.0", 1000).unwrap();
+        let (fallback, called) = lookup(&path, false, 1000 + TTL, None).await;
+        assert!(called);
+        assert_eq!(fallback.latest_version.as_deref(), Some("1.0.0"));
+        assert_eq!(fallback.checked_at, Some(1000));
+        assert!(fallback.stale);
+    }
+}
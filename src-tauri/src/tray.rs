@@ -0,0 +1,129 @@
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager};
+
+use crate::commands;
+use crate::models::Project;
+use crate::services::{ConfigStorage, Logger};
+
+const TRAY_ID: &str = "main";
+
+/// Recently launched projects listed below the pinned ones
+const RECENT_PROJECTS: usize = 5;
+
+const MENU_SHOW: &str = "show";
+const MENU_QUIT: &str = "quit";
+const LAUNCH_PREFIX: &str = "launch:";
+
+/// Pinned projects (same order as the project list) followed by the most recently launched others
+fn menu_projects() -> (Vec<Project>, Vec<Project>) {
+    let projects = ConfigStorage::get_projects().unwrap_or_default();
+
+    let mut pinned: Vec<Project> = projects.iter().filter(|p| p.is_pinned).cloned().collect();
+    pinned.sort_by(|a, b| b.pinned_at.unwrap_or(0).cmp(&a.pinned_at.unwrap_or(0)));
+
+    let mut recent: Vec<Project> = projects
+        .into_iter()
+        .filter(|p| !p.is_pinned && p.last_launched_at.is_some())
+        .collect();
+    recent.sort_by(|a, b| b.last_launched_at.cmp(&a.last_launched_at));
+    recent.truncate(RECENT_PROJECTS);
+
+    (pinned, recent)
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<tauri::Wry>> {
+    let menu = Menu::new(app)?;
+    menu.append(&MenuItem::with_id(app, MENU_SHOW, "显示主窗口", true, None::<&str>)?)?;
+
+    let (pinned, recent) = menu_projects();
+    for (title, projects) in [("置顶项目", pinned), ("最近启动", recent)] {
+        if projects.is_empty() {
+            continue;
+        }
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+        menu.append(&MenuItem::new(app, title, false, None::<&str>)?)?;
+        for project in projects {
+            let id = format!("{}{}", LAUNCH_PREFIX, project.id);
+            menu.append(&MenuItem::with_id(app, id, &project.name, true, None::<&str>)?)?;
+        }
+    }
+
+    menu.append(&PredefinedMenuItem::separator(app)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_QUIT, "退出", true, None::<&str>)?)?;
+    Ok(menu)
+}
+
+pub fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.unminimize();
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+}
+
+fn on_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id: &str = event.id().as_ref();
+    if id == MENU_SHOW {
+        show_main_window(app);
+    } else if id == MENU_QUIT {
+        app.exit(0);
+    } else if let Some(project_id) = id.strip_prefix(LAUNCH_PREFIX) {
+        if let Err(e) = commands::launch_project(app.clone(), project_id.to_string()) {
+            use tauri_plugin_dialog::{DialogExt, MessageDialogKind};
+
+            Logger::error("tray", format!("launch from tray failed: {}", e));
+            app.dialog()
+                .message(e)
+                .title("启动失败")
+                .kind(MessageDialogKind::Error)
+                .show(|_| {});
+        }
+    }
+}
+
+/// Create the tray icon; its menu is rebuilt whenever the config file is written
+pub fn init(app: &AppHandle) -> tauri::Result<()> {
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Claude Code 启动器")
+        .menu(&build_menu(app)?)
+        .show_menu_on_left_click(false)
+        .on_menu_event(on_menu_event)
+        .on_tray_icon_event(|tray, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+    builder.build(app)?;
+
+    let handle = app.clone();
+    ConfigStorage::on_change(move || refresh(&handle));
+    Ok(())
+}
+
+pub fn refresh(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+    match build_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => Logger::warn("tray", format!("failed to rebuild tray menu: {}", e)),
+    }
+}
+
+/// Closing the window only hides it when the user chose to keep the launcher in the tray
+pub fn hide_on_close() -> bool {
+    ConfigStorage::get_launcher_settings()
+        .map(|settings| settings.hide_to_tray_on_close)
+        .unwrap_or(false)
+}
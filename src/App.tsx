@@ -8,6 +8,8 @@ import { ProjectCreatePage } from './pages/ProjectCreatePage';
 import { OnboardingOverlay } from './components/OnboardingOverlay';
 import { OnboardingTrigger } from './components/OnboardingTrigger';
 import { UpdateNotification } from './components/UpdateNotification';
+import { TerminalTabs } from './components/TerminalTabs';
+import { DeepLinkDialog } from './components/DeepLinkDialog';
 import { useUpdateChecker } from './hooks/useUpdateChecker';
 import { onboardingApi } from './api';
 import './index.css';
@@ -181,6 +183,12 @@ function AppContent() {
           <Route path="/project/:id/edit" element={<ProjectEditPage />} />
         </Routes>
 
+        {/* 应用内终端标签页 */}
+        <TerminalTabs />
+
+        {/* 外部链接确认 */}
+        <DeepLinkDialog />
+
         {/* Onboarding overlay - only show on home page */}
         {showOnboarding && location.pathname === '/' && (
           <OnboardingOverlay onComplete={handleOnboardingComplete} />
@@ -1,28 +1,50 @@
 import { invoke } from '@tauri-apps/api/core';
-import type { DependencyStatus, AppConfig } from './types';
-import type { Project, ProjectConfig, ProjectOrderItem, PinnedOrderItem } from './types/project';
+import type { DependencyStatus, AppConfig, LauncherSettings, LauncherSettingsSnapshot, RegistryConfig, InstallJobInfo, PackageVersions, OfflineManifest, VerifiedPackage, NpmPrefixStatus, CleanupOptions, CleanupReport, InstallMethod, ClaudeInstallations, TerminalInfo, TerminalSnapshot, LogEntry, LogFilter, CaBundleInfo, DeepLinkRequest } from './types';
+import type { Project, ProjectConfig, ProjectSnapshot, ProjectOrderItem, PinnedOrderItem, LaunchSession, ProjectSessionStats, ProxyInfo, ConnectivityResult, DetectedProxy } from './types/project';
 
 export const api = {
   // 依赖检测
   checkNodejs: () => invoke<DependencyStatus>('check_nodejs'),
   checkClaude: () => invoke<DependencyStatus>('check_claude'),
   checkGitbash: () => invoke<DependencyStatus>('check_gitbash'),
-  checkNodejsWithUpdate: () => invoke<DependencyStatus>('check_nodejs_with_update'),
-  checkClaudeWithUpdate: () => invoke<DependencyStatus>('check_claude_with_update'),
-  checkGitbashWithUpdate: () => invoke<DependencyStatus>('check_gitbash_with_update'),
+  checkNodejsWithUpdate: (force?: boolean) =>
+    invoke<DependencyStatus>('check_nodejs_with_update', { force }),
+  checkClaudeWithUpdate: (force?: boolean) =>
+    invoke<DependencyStatus>('check_claude_with_update', { force }),
+  checkGitbashWithUpdate: (force?: boolean) =>
+    invoke<DependencyStatus>('check_gitbash_with_update', { force }),
+  clearUpdateCache: () => invoke<void>('clear_update_cache'),
   refreshSystemPath: () => invoke('refresh_system_path'),
 
   // 安装/更新
-  installNodejs: () => invoke('install_nodejs'),
-  updateNodejs: () => invoke('update_nodejs'),
-  installClaude: () => invoke('install_claude'),
-  updateClaude: () => invoke('update_claude'),
-  installGitbash: () => invoke('install_gitbash'),
-  updateGitbash: () => invoke('update_gitbash'),
+  installNodejs: () => invoke<InstallJobInfo>('install_nodejs'),
+  updateNodejs: () => invoke<InstallJobInfo>('update_nodejs'),
+  installClaude: (method?: InstallMethod) => invoke<InstallJobInfo>('install_claude', { method }),
+  updateClaude: () => invoke<InstallJobInfo>('update_claude'),
+  installGitbash: () => invoke<InstallJobInfo>('install_gitbash'),
+  updateGitbash: () => invoke<InstallJobInfo>('update_gitbash'),
+  listClaudeVersions: () => invoke<PackageVersions>('list_claude_versions'),
+  installClaudeVersion: (version: string) =>
+    invoke<InstallJobInfo>('install_claude_version', { version }),
+  getClaudePreviousVersion: () => invoke<string | null>('get_claude_previous_version'),
+  rollbackClaude: () => invoke<InstallJobInfo>('rollback_claude'),
+  getClaudeInstallations: () => invoke<ClaudeInstallations>('get_claude_installations'),
+  migrateClaudeToNative: () => invoke<InstallJobInfo>('migrate_claude_to_native'),
+  inspectOfflinePackage: (path: string, version?: string) =>
+    invoke<VerifiedPackage>('inspect_offline_package', { path, version }),
+  installClaudeOffline: (path: string, version?: string) =>
+    invoke<InstallJobInfo>('install_claude_offline', { path, version }),
+  prepareOfflineBundle: (destDir: string, version?: string) =>
+    invoke<OfflineManifest>('prepare_offline_bundle', { destDir, version }),
+  getNpmPrefixStatus: () => invoke<NpmPrefixStatus>('get_npm_prefix_status'),
+  configureNpmUserPrefix: () => invoke<NpmPrefixStatus>('configure_npm_user_prefix'),
+  cleanup: (options: CleanupOptions) => invoke<CleanupReport>('cleanup', { options }),
+  cancelInstallJob: (jobId: string) => invoke<void>('cancel_install_job', { jobId }),
+  listInstallJobs: () => invoke<InstallJobInfo[]>('list_install_jobs'),
 
   // 启动
   launchClaudeCode: (config: Record<string, string>) =>
-    invoke('launch_claude_code', { config }),
+    invoke<LaunchSession>('launch_claude_code', { config }),
 
   // 命令生成
   generatePowershellCommand: (config: Record<string, string>) =>
@@ -36,9 +58,10 @@ export const api = {
   getPlatform: () => invoke<string>('get_platform'),
 
   // 设置管理
-  saveToSettings: (config: Record<string, string>) =>
-    invoke('save_to_settings', { config }),
-  resetSettings: () => invoke('reset_settings'),
+  getSettingsVersion: () => invoke<string>('get_settings_file_version'),
+  saveToSettings: (config: Record<string, string>, version?: string) =>
+    invoke('save_to_settings', { config, version }),
+  resetSettings: (version?: string) => invoke('reset_settings', { version }),
   openSettingsFile: () => invoke('open_settings_file'),
 
   // 应用配置 (legacy API for backwards compatibility)
@@ -55,6 +78,9 @@ export const projectApi = {
   // Get a single project by ID
   get: (id: string) => invoke<Project>('get_project', { id }),
 
+  // Get a project for editing, with the version its update is checked against
+  getSnapshot: (id: string) => invoke<ProjectSnapshot>('get_project_snapshot', { id }),
+
   // Create a new project
   create: (name: string, workingDirectory: string, config: ProjectConfig) =>
     invoke<Project>('create_project', {
@@ -69,7 +95,8 @@ export const projectApi = {
     name?: string,
     workingDirectory?: string,
     config?: ProjectConfig,
-    isPinned?: boolean
+    isPinned?: boolean,
+    version?: string
   ) =>
     invoke<Project>('update_project', {
       id,
@@ -77,13 +104,14 @@ export const projectApi = {
       workingDirectory,
       config,
       isPinned,
+      version,
     }),
 
   // Delete a project
   delete: (id: string) => invoke<void>('delete_project', { id }),
 
   // Launch a project
-  launch: (id: string) => invoke<void>('launch_project', { id }),
+  launch: (id: string) => invoke<LaunchSession>('launch_project', { id }),
 
   // Generate commands for a project
   generatePowershellCommand: (id: string) =>
@@ -104,11 +132,66 @@ export const projectApi = {
   // Toggle project pinned status
   togglePinned: (id: string, isPinned: boolean) =>
     invoke<Project>('toggle_project_pinned', { id, isPinned }),
+
+  // Desktop shortcut / launcher entry
+  createShortcut: (id: string) => invoke<Project>('create_project_shortcut', { id }),
+  removeShortcut: (id: string) => invoke<Project>('remove_project_shortcut', { id }),
+
+  // Parse a proxy URL; rejects unsupported schemes and malformed addresses
+  validateProxy: (url: string) => invoke<ProxyInfo>('validate_proxy_url', { url }),
+
+  // Proxy settings from environment variables and the OS network settings
+  detectSystemProxy: () => invoke<DetectedProxy[]>('detect_system_proxy'),
+
+  // Reach the API endpoint through the (unsaved) config's proxy settings
+  testConnectivity: (config: ProjectConfig) =>
+    invoke<ConnectivityResult>('test_proxy_connectivity', { config }),
+};
+
+// Launch session API
+export const sessionApi = {
+  list: (projectId?: string) => invoke<LaunchSession[]>('list_sessions', { projectId }),
+  get: (id: string) => invoke<LaunchSession>('get_session', { id }),
+  getHookOutput: (id: string) => invoke<string>('get_session_hook_output', { id }),
+  getProjectStats: () =>
+    invoke<Record<string, ProjectSessionStats>>('get_project_session_stats'),
+};
+
+// In-app terminal API
+export const terminalApi = {
+  list: () => invoke<TerminalInfo[]>('list_terminals'),
+  write: (terminalId: string, data: string) =>
+    invoke<void>('write_terminal', { terminalId, data }),
+  resize: (terminalId: string, cols: number, rows: number) =>
+    invoke<void>('resize_terminal', { terminalId, cols, rows }),
+  snapshot: (terminalId: string) =>
+    invoke<TerminalSnapshot>('get_terminal_snapshot', { terminalId }),
+  close: (terminalId: string) => invoke<void>('close_terminal', { terminalId }),
+};
+
+// Deep link API
+export const deepLinkApi = {
+  getPending: () => invoke<DeepLinkRequest[]>('get_pending_deep_links'),
+  confirm: (id: string) => invoke<LaunchSession>('confirm_deep_link', { id }),
+  dismiss: (id: string) => invoke<void>('dismiss_deep_link', { id }),
+};
+
+// CA certificate bundle API
+export const caBundleApi = {
+  inspect: (path: string) => invoke<CaBundleInfo>('inspect_ca_bundle', { path }),
+  selectFile: () => invoke<string | null>('select_ca_bundle_file'),
+};
+
+// Log API
+export const logApi = {
+  get: (filter?: LogFilter) => invoke<LogEntry[]>('get_logs', { filter }),
+  getDirectory: () => invoke<string>('get_log_directory'),
 };
 
 // Dialog API
 export const dialogApi = {
   selectDirectory: () => invoke<string | null>('select_directory'),
+  selectOfflinePackage: () => invoke<string | null>('select_offline_package'),
 };
 
 // System API
@@ -116,6 +199,15 @@ export const systemApi = {
   getHomeDirectory: () => invoke<string>('get_home_directory'),
 };
 
+// Launcher settings API
+export const settingsApi = {
+  get: () => invoke<LauncherSettings>('get_launcher_settings'),
+  getSnapshot: () => invoke<LauncherSettingsSnapshot>('get_launcher_settings_snapshot'),
+  save: (settings: LauncherSettings, version?: string) =>
+    invoke<void>('save_launcher_settings', { settings, version }),
+  getNpmRegistry: () => invoke<RegistryConfig>('get_npm_registry'),
+};
+
 // Onboarding API
 export const onboardingApi = {
   getStatus: () => invoke<boolean>('get_onboarding_status'),
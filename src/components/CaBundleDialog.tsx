@@ -0,0 +1,87 @@
+import React, { useEffect, useState } from 'react';
+import { caBundleApi, settingsApi } from '../api';
+import type { CaBundleConfig, LauncherSettingsSnapshot } from '../types';
+import { CaBundlePicker } from './CaBundlePicker';
+
+interface CaBundleDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+// 全局根证书: 未单独配置证书的项目以及启动器自身的网络请求都会使用
+export const CaBundleDialog: React.FC<CaBundleDialogProps> = ({ isOpen, onClose }) => {
+  const [caBundle, setCaBundle] = useState<CaBundleConfig>({ path: '', set_ssl_cert_file: false });
+  const [error, setError] = useState('');
+  const [saving, setSaving] = useState(false);
+  const [snapshot, setSnapshot] = useState<LauncherSettingsSnapshot | null>(null);
+
+  useEffect(() => {
+    if (!isOpen) {
+      return;
+    }
+    setError('');
+    setSnapshot(null);
+    settingsApi.getSnapshot()
+      .then((loaded) => {
+        setSnapshot(loaded);
+        setCaBundle(loaded.settings.ca_bundle);
+      })
+      .catch((err) => console.error('读取设置失败:', err));
+  }, [isOpen]);
+
+  if (!isOpen) return null;
+
+  const handleSave = async () => {
+    if (!snapshot) return;
+    setSaving(true);
+    setError('');
+    try {
+      if (caBundle.path.trim()) {
+        await caBundleApi.inspect(caBundle.path);
+      }
+      // 回传打开时的版本: 设置已被其他窗口修改时拒绝保存,而不是覆盖
+      await settingsApi.save({ ...snapshot.settings, ca_bundle: caBundle }, snapshot.version);
+      onClose();
+    } catch (err: any) {
+      setError(err?.toString() || '保存失败');
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      {/* 背景遮罩 */}
+      <div className="absolute inset-0 bg-black/60" onClick={onClose} />
+
+      {/* 对话框 */}
+      <div className="relative bg-[#2a2a2a] border border-[#565B5E] rounded-lg shadow-xl max-w-2xl w-full mx-4 p-6">
+        <h3 className="text-[16px] font-bold text-[#DCE4EE] mb-1">全局根证书</h3>
+        <div className="text-[11px] text-[#999999] mb-3">
+          公司代理解密 HTTPS 流量时，需要信任其根证书。未单独设置证书的项目、npm 安装与更新检查都会使用这里的证书。
+        </div>
+
+        <CaBundlePicker value={caBundle} onChange={setCaBundle} />
+        {error && <p className="text-red-500 text-[11px] mt-2">{error}</p>}
+
+        <div className="flex justify-end gap-3 mt-4">
+          <button
+            type="button"
+            onClick={onClose}
+            className="px-4 py-2 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+          >
+            取消
+          </button>
+          <button
+            type="button"
+            onClick={handleSave}
+            disabled={saving || !snapshot}
+            className="px-4 py-2 text-[12px] bg-[#3b82f6] hover:bg-[#2563eb] text-white rounded disabled:opacity-50"
+          >
+            {saving ? '保存中...' : '保存'}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,110 @@
+import React, { useEffect, useState } from 'react';
+import { caBundleApi } from '../api';
+import type { CaBundleConfig, CaBundleInfo } from '../types';
+
+interface CaBundlePickerProps {
+  value: CaBundleConfig;
+  onChange: (value: CaBundleConfig) => void;
+  placeholder?: string;
+}
+
+export const CaBundlePicker: React.FC<CaBundlePickerProps> = ({ value, onChange, placeholder }) => {
+  const [info, setInfo] = useState<CaBundleInfo | null>(null);
+  const [error, setError] = useState('');
+
+  // 路径变化后重新解析证书，便于确认选对了文件
+  useEffect(() => {
+    const path = value.path.trim();
+    if (!path) {
+      setInfo(null);
+      setError('');
+      return;
+    }
+    let cancelled = false;
+    caBundleApi.inspect(path)
+      .then((result) => {
+        if (!cancelled) {
+          setInfo(result);
+          setError('');
+        }
+      })
+      .catch((err) => {
+        if (!cancelled) {
+          setInfo(null);
+          setError(String(err));
+        }
+      });
+    return () => {
+      cancelled = true;
+    };
+  }, [value.path]);
+
+  const handleSelect = async () => {
+    try {
+      const selected = await caBundleApi.selectFile();
+      if (selected) {
+        onChange({ ...value, path: selected });
+      }
+    } catch (err) {
+      console.error('选择证书文件失败:', err);
+    }
+  };
+
+  return (
+    <div className="space-y-2">
+      <div className="flex gap-2">
+        <input
+          type="text"
+          value={value.path}
+          onChange={(e) => onChange({ ...value, path: e.target.value })}
+          placeholder={placeholder || 'PEM 格式的根证书文件路径'}
+          className="flex-1 px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
+        />
+        <button
+          type="button"
+          onClick={handleSelect}
+          className="px-3 py-1 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+        >
+          选择
+        </button>
+        {value.path && (
+          <button
+            type="button"
+            onClick={() => onChange({ path: '', set_ssl_cert_file: false })}
+            className="px-3 py-1 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+          >
+            清除
+          </button>
+        )}
+      </div>
+
+      <label className="flex items-center gap-2 cursor-pointer">
+        <input
+          type="checkbox"
+          checked={value.set_ssl_cert_file}
+          disabled={!value.path}
+          onChange={(e) => onChange({ ...value, set_ssl_cert_file: e.target.checked })}
+          className="w-4 h-4"
+        />
+        <span className="text-[12px]">同时设置 SSL_CERT_FILE (会替换默认信任库，仅在文件包含完整证书链时启用)</span>
+      </label>
+
+      {error && <p className="text-red-500 text-[11px]">{error}</p>}
+      {info && (
+        <div className="bg-[#2a2a2a] border border-[#565B5E] rounded p-2 space-y-1 max-h-40 overflow-auto">
+          {info.certificates.map((cert, index) => (
+            <div key={index} className="text-[11px]">
+              <div className={`break-all ${cert.expired ? 'text-red-400' : 'text-[#DCE4EE]'}`}>
+                {cert.subject}
+                {cert.is_ca && <span className="ml-1 text-[#999999]">(CA)</span>}
+              </div>
+              <div className={cert.expired ? 'text-red-400' : 'text-[#999999]'}>
+                {cert.expired ? '已过期' : '有效期至'} {cert.not_after}
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
+    </div>
+  );
+};
@@ -0,0 +1,134 @@
+import React, { useEffect, useState } from 'react';
+import { api } from '../api';
+import type { CleanupAction, CleanupCategory, CleanupOptions, CleanupReport } from '../types';
+
+interface CleanupDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+const CATEGORY_LABELS: Record<CleanupCategory, string> = {
+  claude_package: 'Claude Code 程序',
+  settings_env: 'settings.json 中由启动器写入的环境变量',
+  user_env_vars: '用户环境变量与 PATH 设置',
+  temp_scripts: '临时安装脚本',
+  launcher_data: '启动器配置、缓存与日志',
+};
+
+const describeAction = (action: CleanupAction): string => {
+  switch (action.kind) {
+    case 'delete_file':
+      return `删除文件 ${action.path}`;
+    case 'delete_dir':
+      return `删除目录 ${action.path}`;
+    case 'run_command':
+      return `执行 ${action.program} ${action.args.join(' ')}`;
+    case 'remove_settings_env':
+      return `从 ${action.path} 移除 ${action.keys.join(', ')}`;
+    case 'remove_user_env_var':
+      return `删除环境变量 ${action.name}`;
+    case 'remove_profile_path':
+      return `从 ${action.path} 移除启动器添加的 PATH 设置`;
+    case 'remove_user_path_entry':
+      return `从用户 PATH 中移除 ${action.dir}`;
+    case 'manual':
+      return action.hint;
+  }
+};
+
+export const CleanupDialog: React.FC<CleanupDialogProps> = ({ isOpen, onClose }) => {
+  const [options, setOptions] = useState<CleanupOptions>({
+    claude_package: false,
+    launcher_data: false,
+    settings_env: true,
+    user_env_vars: true,
+    temp_scripts: true,
+    dry_run: true,
+  });
+  const [report, setReport] = useState<CleanupReport | null>(null);
+  const [running, setRunning] = useState(false);
+
+  // 选项变化后重新预览
+  useEffect(() => {
+    if (!isOpen) {
+      return;
+    }
+    setReport(null);
+    api.cleanup({ ...options, dry_run: true })
+      .then(setReport)
+      .catch((error) => console.error('预览失败:', error));
+  }, [isOpen, options]);
+
+  if (!isOpen) return null;
+
+  const toggle = (category: CleanupCategory) => {
+    setOptions((prev) => ({ ...prev, [category]: !prev[category] }));
+  };
+
+  const handleRun = async () => {
+    setRunning(true);
+    try {
+      setReport(await api.cleanup({ ...options, dry_run: false }));
+    } catch (error: any) {
+      alert(`清理失败: ${error}`);
+    } finally {
+      setRunning(false);
+    }
+  };
+
+  const nothingSelected = (Object.keys(CATEGORY_LABELS) as CleanupCategory[]).every((c) => !options[c]);
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      {/* 背景遮罩 */}
+      <div className="absolute inset-0 bg-black/60" onClick={onClose} />
+
+      {/* 对话框 */}
+      <div className="relative bg-[#2a2a2a] border border-[#565B5E] rounded-lg shadow-xl max-w-2xl w-full mx-4 p-6">
+        <h3 className="text-[16px] font-bold text-[#DCE4EE] mb-3">卸载与清理</h3>
+
+        <div className="flex flex-col gap-1 mb-3">
+          {(Object.keys(CATEGORY_LABELS) as CleanupCategory[]).map((category) => (
+            <label key={category} className="flex items-center gap-2 text-[12px] text-[#DCE4EE]">
+              <input type="checkbox" checked={options[category]} onChange={() => toggle(category)} />
+              {CATEGORY_LABELS[category]}
+            </label>
+          ))}
+        </div>
+
+        <div className="text-[12px] text-[#999999] mb-1">
+          {report && !report.dry_run ? '清理结果:' : '将执行以下操作:'}
+        </div>
+        <div className="max-h-60 overflow-auto bg-[#1e1e1e] rounded p-2 mb-4 text-[11px] text-[#d4d4d4]">
+          {!report && <div>⏳</div>}
+          {report && report.items.length === 0 && <div>没有需要清理的内容</div>}
+          {report?.items.map((item, index) => (
+            <div key={index} className={item.error ? 'text-error' : item.done ? 'text-success' : ''}>
+              {item.done ? '✓ ' : item.error ? '✗ ' : '• '}
+              [{CATEGORY_LABELS[item.category]}] {describeAction(item.action)}
+              {item.error && item.action.kind !== 'manual' ? ` — ${item.error}` : ''}
+            </div>
+          ))}
+        </div>
+
+        <div className="flex justify-end gap-3">
+          <button
+            type="button"
+            onClick={onClose}
+            className="px-4 py-2 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+          >
+            关闭
+          </button>
+          <button
+            type="button"
+            onClick={handleRun}
+            disabled={running || nothingSelected || !report || report.items.length === 0 || !report.dry_run}
+            className="px-4 py-2 text-[12px] bg-red-600 hover:bg-red-700 text-white rounded disabled:opacity-50"
+          >
+            {running ? '清理中...' : '执行清理'}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -0,0 +1,107 @@
+import React, { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { deepLinkApi } from '../api';
+import type { DeepLinkRequest } from '../types';
+
+// 外部链接不会直接启动任何东西，需在此确认
+export const DeepLinkDialog: React.FC = () => {
+  const [queue, setQueue] = useState<DeepLinkRequest[]>([]);
+  const [busy, setBusy] = useState(false);
+
+  useEffect(() => {
+    // 应用因链接而启动时，请求可能早于界面加载到达
+    deepLinkApi.getPending()
+      .then((pending) => setQueue((current) => [
+        ...current,
+        ...pending.filter((request) => !current.some((r) => r.id === request.id)),
+      ]))
+      .catch((err) => console.error('读取链接请求失败:', err));
+
+    const unlisten = listen<DeepLinkRequest>('deep-link', (event) => {
+      setQueue((current) =>
+        current.some((r) => r.id === event.payload.id) ? current : [...current, event.payload]
+      );
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  const request = queue[0];
+  if (!request) return null;
+
+  const next = () => {
+    setQueue((current) => current.slice(1));
+  };
+
+  const handleCancel = async () => {
+    if (request.action) {
+      await deepLinkApi.dismiss(request.id).catch(() => {});
+    }
+    next();
+  };
+
+  const handleConfirm = async () => {
+    setBusy(true);
+    try {
+      await deepLinkApi.confirm(request.id);
+      next();
+    } catch (err: any) {
+      // 请求已被后端取走，失败后只能显示原因
+      const reason = err?.toString() || '启动失败';
+      setQueue((current) => [{ ...request, action: null, error: reason }, ...current.slice(1)]);
+    } finally {
+      setBusy(false);
+    }
+  };
+
+  const action = request.action;
+  let message = '';
+  if (action?.kind === 'launch') {
+    message = `外部链接请求启动项目「${action.project_name}」\n工作目录: ${action.working_directory}`;
+  } else if (action?.kind === 'open') {
+    message = action.project_name
+      ? `外部链接请求在以下目录启动 Claude Code (使用项目「${action.project_name}」):\n${action.path}`
+      : `外部链接请求在以下目录启动 Claude Code:\n${action.path}\n\n将以默认设置为该目录创建新项目。`;
+  }
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      {/* 背景遮罩 */}
+      <div className="absolute inset-0 bg-black/60" />
+
+      {/* 对话框 */}
+      <div className="relative bg-[#2a2a2a] border border-[#565B5E] rounded-lg shadow-xl max-w-md w-full mx-4 p-6">
+        <h3 className="text-[16px] font-bold text-[#DCE4EE] mb-3">
+          {action ? '打开外部链接' : '无法打开链接'}
+        </h3>
+        {action ? (
+          <p className="text-[14px] text-[#999999] mb-3 whitespace-pre-line break-all">{message}</p>
+        ) : (
+          <p className="text-[14px] text-red-400 mb-3 whitespace-pre-line break-all">{request.error}</p>
+        )}
+        <div className="text-[11px] text-[#999999] mb-4 break-all">{request.url}</div>
+
+        <div className="flex justify-end gap-3">
+          <button
+            type="button"
+            onClick={handleCancel}
+            className="px-4 py-2 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+          >
+            {action ? '取消' : '关闭'}
+          </button>
+          {action && (
+            <button
+              type="button"
+              onClick={handleConfirm}
+              disabled={busy}
+              className="px-4 py-2 text-[12px] bg-[#3b82f6] hover:bg-[#2563eb] text-white rounded disabled:opacity-50"
+            >
+              {busy ? '启动中...' : '启动'}
+            </button>
+          )}
+        </div>
+      </div>
+    </div>
+  );
+};
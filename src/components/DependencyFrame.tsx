@@ -1,6 +1,56 @@
-import { useEffect, useState } from 'react';
-import { api } from '../api';
-import type { DependencyStatus } from '../types';
+import { useEffect, useRef, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { api, dialogApi, settingsApi } from '../api';
+import { CleanupDialog } from './CleanupDialog';
+import type {
+  ClaudeInstallations,
+  DependencyError,
+  DependencyStatus,
+  InstallAction,
+  InstallFinishedEvent,
+  InstallJobInfo,
+  InstallOutputEvent,
+  InstallTarget,
+  LauncherSettingsSnapshot,
+  PublishedVersion,
+} from '../types';
+
+// 安装日志面板最多保留的行数
+const MAX_LOG_LINES = 500;
+
+// 版本下拉框最多显示的版本数
+const MAX_VERSION_OPTIONS = 50;
+
+const TARGET_LABELS: Record<InstallTarget, string> = {
+  nodejs: 'Node.js',
+  claude: 'Claude Code',
+  gitbash: 'Git',
+};
+
+const ACTION_LABELS: Record<InstallAction, string> = {
+  install: '安装',
+  update: '更新',
+  switch_version: '切换版本',
+  migrate: '迁移',
+};
+
+const describeError = (error: DependencyError | null): string | undefined => {
+  if (!error) {
+    return undefined;
+  }
+  switch (error.kind) {
+    case 'not_found':
+      return `未在 PATH 中找到 ${error.command}`;
+    case 'command_failed':
+      return `${error.command} 执行失败 (退出码: ${error.exit_code ?? '未知'})${error.stderr ? `\n${error.stderr}` : ''}`;
+    case 'spawn_failed':
+      return `无法启动 ${error.command}: ${error.message}`;
+    case 'version_unparseable':
+      return `无法解析版本号: ${error.raw_output}`;
+    case 'below_minimum':
+      return `版本 ${error.version} 低于最低要求 ${error.minimum}`;
+  }
+};
 
 export const DependencyFrame = () => {
   const [nodejsStatus, setNodejsStatus] = useState<DependencyStatus | null>(null);
@@ -9,6 +59,82 @@ export const DependencyFrame = () => {
   const [nodejsLoading, setNodejsLoading] = useState(false);
   const [claudeLoading, setClaudeLoading] = useState(false);
   const [gitbashLoading, setGitbashLoading] = useState(false);
+  const [activeJob, setActiveJob] = useState<InstallJobInfo | null>(null);
+  const [jobLog, setJobLog] = useState<string[]>([]);
+  const activeJobRef = useRef<string | null>(null);
+  const [showVersions, setShowVersions] = useState(false);
+  const [claudeVersions, setClaudeVersions] = useState<PublishedVersion[]>([]);
+  const [selectedVersion, setSelectedVersion] = useState('');
+  const [previousVersion, setPreviousVersion] = useState<string | null>(null);
+  const [pinnedVersion, setPinnedVersion] = useState<string | null>(null);
+  const [settingsSnapshot, setSettingsSnapshot] = useState<LauncherSettingsSnapshot | null>(null);
+  const [preparingBundle, setPreparingBundle] = useState(false);
+  const [showCleanup, setShowCleanup] = useState(false);
+  const [claudeInstallations, setClaudeInstallations] = useState<ClaudeInstallations | null>(null);
+  const logRef = useRef<HTMLPreElement | null>(null);
+
+  const setLoading = (target: InstallTarget, loading: boolean) => {
+    const setters: Record<InstallTarget, (value: boolean) => void> = {
+      nodejs: setNodejsLoading,
+      claude: setClaudeLoading,
+      gitbash: setGitbashLoading,
+    };
+    setters[target](loading);
+  };
+
+  const setStatus = (target: InstallTarget, status: DependencyStatus) => {
+    const setters: Record<InstallTarget, (value: DependencyStatus) => void> = {
+      nodejs: setNodejsStatus,
+      claude: setClaudeStatus,
+      gitbash: setGitbashStatus,
+    };
+    setters[target](status);
+  };
+
+  // 订阅安装任务的输出与完成事件
+  useEffect(() => {
+    const unlistenOutput = listen<InstallOutputEvent>('install-output', (event) => {
+      if (event.payload.job_id !== activeJobRef.current) {
+        return;
+      }
+      setJobLog((lines) => [...lines, event.payload.line].slice(-MAX_LOG_LINES));
+    });
+
+    const unlistenFinished = listen<InstallFinishedEvent>('install-finished', (event) => {
+      const { job, status } = event.payload;
+      setLoading(job.target, false);
+      setStatus(job.target, status);
+      sessionStorage.removeItem('dependencyStatus');
+      if (job.id === activeJobRef.current) {
+        setActiveJob(job);
+      }
+      if (job.target === 'claude') {
+        api.getClaudePreviousVersion().then(setPreviousVersion).catch(() => {});
+      }
+    });
+
+    // 恢复页面刷新前仍在运行的任务
+    api.listInstallJobs().then((jobs) => {
+      const running = jobs.find((job) => job.state === 'running');
+      if (running) {
+        activeJobRef.current = running.id;
+        setActiveJob(running);
+        setJobLog(running.output_tail);
+        setLoading(running.target, true);
+      }
+    }).catch(() => {});
+
+    return () => {
+      unlistenOutput.then((unlisten) => unlisten());
+      unlistenFinished.then((unlisten) => unlisten());
+    };
+  }, []);
+
+  useEffect(() => {
+    if (logRef.current) {
+      logRef.current.scrollTop = logRef.current.scrollHeight;
+    }
+  }, [jobLog]);
 
   // 自动检测(启动时)，使用 sessionStorage 缓存避免重复检测
   useEffect(() => {
@@ -80,88 +206,156 @@ export const DependencyFrame = () => {
     }
   };
 
-  const handleInstallNodejs = async () => {
-    setNodejsLoading(true);
+  // 全局 npm 目录需要管理员权限时,先询问是否改用用户级目录
+  const ensureWritableNpmPrefix = async (): Promise<boolean> => {
+    const prefix = await api.getNpmPrefixStatus();
+    if (!prefix.prefix || prefix.writable) {
+      return true;
+    }
+    if (!confirm(`npm 全局目录 ${prefix.prefix} 需要管理员权限。\n是否改为安装到用户目录 ${prefix.user_prefix}?\n(${prefix.user_bin_dir} 会被加入 PATH)`)) {
+      return false;
+    }
+    await api.configureNpmUserPrefix();
+    return true;
+  };
+
+  // 原生安装器装在用户目录,不涉及 npm 全局目录
+  const startJob = async (
+    target: InstallTarget,
+    action: InstallAction,
+    run: () => Promise<InstallJobInfo>,
+    usesNpm: boolean = target === 'claude',
+  ) => {
+    setLoading(target, true);
     try {
-      await api.installNodejs();
-      // 安装窗口会自动打开,等待完成后重新检测
-      setTimeout(() => {
-        checkInstallationOnly();
-        setNodejsLoading(false);
-      }, 2000);
+      if (usesNpm && !(await ensureWritableNpmPrefix())) {
+        setLoading(target, false);
+        return;
+      }
+      const job = await run();
+      activeJobRef.current = job.id;
+      setActiveJob(job);
+      setJobLog([]);
     } catch (error: any) {
-      alert(`安装失败: ${error}`);
-      setNodejsLoading(false);
+      alert(`${ACTION_LABELS[action]}失败: ${error}`);
+      setLoading(target, false);
     }
   };
 
-  const handleUpdateNodejs = async () => {
-    setNodejsLoading(true);
+  const handleCancelJob = async () => {
+    if (!activeJob || activeJob.state !== 'running') {
+      return;
+    }
     try {
-      await api.updateNodejs();
-      setTimeout(() => {
-        checkInstallationOnly();
-        setNodejsLoading(false);
-      }, 2000);
+      await api.cancelInstallJob(activeJob.id);
     } catch (error: any) {
-      alert(`更新失败: ${error}`);
-      setNodejsLoading(false);
+      console.error('取消失败:', error);
     }
   };
 
-  const handleInstallClaude = async () => {
-    setClaudeLoading(true);
+  const handleInstallNodejs = () => startJob('nodejs', 'install', api.installNodejs);
+  const handleUpdateNodejs = () => startJob('nodejs', 'update', api.updateNodejs);
+  const claudeIsNative = claudeStatus?.install_method === 'native';
+  const handleInstallClaude = () => startJob('claude', 'install', () => api.installClaude('npm'));
+  const handleInstallClaudeNative = () => startJob('claude', 'install', () => api.installClaude('native'), false);
+  const handleUpdateClaude = () => startJob('claude', 'update', api.updateClaude, !claudeIsNative);
+  const handleInstallGitbash = () => startJob('gitbash', 'install', api.installGitbash);
+  const handleUpdateGitbash = () => startJob('gitbash', 'update', api.updateGitbash);
+
+  const toggleVersionPanel = async () => {
+    if (showVersions) {
+      setShowVersions(false);
+      return;
+    }
+    setShowVersions(true);
     try {
-      await api.installClaude();
-      setTimeout(() => {
-        checkInstallationOnly();
-        setClaudeLoading(false);
-      }, 2000);
+      const [previous, snapshot, installations] = await Promise.all([
+        api.getClaudePreviousVersion(),
+        settingsApi.getSnapshot(),
+        api.getClaudeInstallations(),
+      ]);
+      const settings = snapshot.settings;
+      setSettingsSnapshot(snapshot);
+      setPreviousVersion(previous);
+      setClaudeInstallations(installations);
+      setPinnedVersion(settings.claude_pinned_version);
+      setSelectedVersion(settings.claude_pinned_version ?? '');
+
+      // 无网络时版本列表为空,仍可使用离线安装
+      const published = await api.listClaudeVersions().catch((error) => {
+        console.error('获取版本列表失败:', error);
+        return null;
+      });
+      if (published) {
+        setClaudeVersions(published.versions.filter((v) => !v.deprecated).slice(0, MAX_VERSION_OPTIONS));
+        setSelectedVersion(settings.claude_pinned_version ?? published.dist_tags.latest ?? '');
+      }
     } catch (error: any) {
-      alert(`安装失败: ${error}`);
-      setClaudeLoading(false);
+      alert(`加载版本信息失败: ${error}`);
+      setShowVersions(false);
     }
   };
 
-  const handleUpdateClaude = async () => {
-    setClaudeLoading(true);
+  const handleInstallClaudeVersion = () => {
+    if (selectedVersion) {
+      startJob('claude', 'switch_version', () => api.installClaudeVersion(selectedVersion), !claudeIsNative);
+    }
+  };
+
+  const handleInstallClaudeOffline = async () => {
     try {
-      await api.updateClaude();
-      setTimeout(() => {
-        checkInstallationOnly();
-        setClaudeLoading(false);
-      }, 2000);
+      const path = await dialogApi.selectOfflinePackage();
+      if (!path) {
+        return;
+      }
+      const pkg = await api.inspectOfflinePackage(path);
+      if (!confirm(`安装 Claude Code ${pkg.version}?\n${pkg.tarball}\n校验: ${pkg.verified_with}`)) {
+        return;
+      }
+      startJob('claude', 'switch_version', () => api.installClaudeOffline(pkg.tarball));
     } catch (error: any) {
-      alert(`更新失败: ${error}`);
-      setClaudeLoading(false);
+      alert(`离线安装失败: ${error}`);
     }
   };
 
-  const handleInstallGitbash = async () => {
-    setGitbashLoading(true);
+  const handlePrepareOfflineBundle = async () => {
     try {
-      await api.installGitbash();
-      setTimeout(() => {
-        checkInstallationOnly();
-        setGitbashLoading(false);
-      }, 2000);
+      const dir = await dialogApi.selectDirectory();
+      if (!dir) {
+        return;
+      }
+      setPreparingBundle(true);
+      const manifest = await api.prepareOfflineBundle(dir, selectedVersion || undefined);
+      const versions = manifest.packages.map((p) => p.version).join(', ');
+      alert(`离线包已保存到 ${dir}\n包含版本: ${versions}`);
     } catch (error: any) {
-      alert(`安装失败: ${error}`);
-      setGitbashLoading(false);
+      alert(`准备离线包失败: ${error}`);
+    } finally {
+      setPreparingBundle(false);
+    }
+  };
+
+  const handleRollbackClaude = () => startJob('claude', 'switch_version', api.rollbackClaude, !claudeIsNative);
+
+  const handleMigrateClaude = () => {
+    if (!confirm('将安装 Claude Code 原生版本 (~/.local/bin),验证可用后卸载 npm 全局安装的版本。是否继续?')) {
+      return;
     }
+    setClaudeInstallations(null);
+    startJob('claude', 'migrate', api.migrateClaudeToNative, false);
   };
 
-  const handleUpdateGitbash = async () => {
-    setGitbashLoading(true);
+  const handleTogglePin = async () => {
+    if (!settingsSnapshot) return;
     try {
-      await api.updateGitbash();
-      setTimeout(() => {
-        checkInstallationOnly();
-        setGitbashLoading(false);
-      }, 2000);
+      const next = pinnedVersion ? null : selectedVersion || null;
+      // 回传读取时的版本: 设置已被其他窗口修改时拒绝保存,而不是覆盖
+      await settingsApi.save({ ...settingsSnapshot.settings, claude_pinned_version: next }, settingsSnapshot.version);
+      setSettingsSnapshot(await settingsApi.getSnapshot());
+      setPinnedVersion(next);
+      setClaudeStatus(await api.checkClaudeWithUpdate());
     } catch (error: any) {
-      alert(`更新失败: ${error}`);
-      setGitbashLoading(false);
+      alert(`保存失败: ${error}`);
     }
   };
 
@@ -171,18 +365,144 @@ export const DependencyFrame = () => {
     }
 
     if (!status.installed) {
-      return <span className="text-error text-[10px]">✗</span>;
+      return (
+        <span className="text-error text-[10px]" title={describeError(status.error)}>
+          ✗
+        </span>
+      );
+    }
+
+    if (status.error?.kind === 'below_minimum') {
+      return (
+        <span className="text-error text-[10px]" title={describeError(status.error)}>
+          ✗ {status.version}
+        </span>
+      );
     }
 
+    const checkedTitle = status.last_checked_at
+      ? `最新版本检查于 ${new Date(status.last_checked_at * 1000).toLocaleString()}${status.latest_version_stale ? ' (缓存,可能已过期)' : ''}`
+      : undefined;
+
     if (status.update_available && status.latest_version) {
       return (
-        <span className="text-warning text-[10px]">
+        <span className="text-warning text-[10px]" title={checkedTitle}>
           ⚠ {status.version} → {status.latest_version}
+          {status.latest_version_stale && ' *'}
         </span>
       );
     }
 
-    return <span className="text-success text-[10px]">✓ {status.version}</span>;
+    return (
+      <span className="text-success text-[10px]" title={checkedTitle}>
+        ✓ {status.version}
+        {status.pinned_version && ` (固定 ${status.pinned_version})`}
+      </span>
+    );
+  };
+
+  const renderVersionPanel = () => (
+    <div className="mt-2 flex items-center gap-2 text-[10px]">
+      <span>Claude Code 版本:</span>
+      <select
+        value={selectedVersion}
+        onChange={(e) => setSelectedVersion(e.target.value)}
+        className="px-2 py-1 text-[10px] border rounded"
+      >
+        {claudeVersions.map((v) => (
+          <option key={v.version} value={v.version}>
+            {v.version}
+            {v.published_at ? ` (${new Date(v.published_at).toLocaleDateString()})` : ''}
+          </option>
+        ))}
+      </select>
+      <button
+        onClick={handleInstallClaudeVersion}
+        disabled={!selectedVersion || claudeLoading}
+        className="px-3 py-1 text-[10px] bg-primary hover:bg-primary-hover rounded text-white"
+      >
+        安装此版本
+      </button>
+      <button
+        onClick={handleRollbackClaude}
+        disabled={!previousVersion || claudeLoading}
+        title={previousVersion ? `回滚到 ${previousVersion}` : '没有可回滚的版本'}
+        className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+      >
+        回滚{previousVersion ? ` (${previousVersion})` : ''}
+      </button>
+      <button
+        onClick={handleTogglePin}
+        disabled={!pinnedVersion && !selectedVersion}
+        className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+      >
+        {pinnedVersion ? `取消固定 (${pinnedVersion})` : '固定此版本'}
+      </button>
+      <button
+        onClick={handleInstallClaudeOffline}
+        disabled={claudeLoading}
+        className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+      >
+        离线安装...
+      </button>
+      <button
+        onClick={handlePrepareOfflineBundle}
+        disabled={preparingBundle}
+        title="下载所选版本及其元数据到文件夹,供无网络的机器离线安装"
+        className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+      >
+        {preparingBundle ? '下载中...' : '准备离线包...'}
+      </button>
+      {claudeInstallations?.can_migrate && (
+        <button
+          onClick={handleMigrateClaude}
+          disabled={claudeLoading}
+          title={`npm 安装: ${claudeInstallations.npm_path}`}
+          className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+        >
+          迁移到原生安装
+        </button>
+      )}
+    </div>
+  );
+
+  const renderJobPanel = (job: InstallJobInfo) => {
+    const title = `${TARGET_LABELS[job.target]} ${ACTION_LABELS[job.action]}`;
+    const stateText: Record<InstallJobInfo['state'], string> = {
+      running: '进行中...',
+      succeeded: '✓ 完成',
+      failed: `✗ 失败${job.exit_code !== null ? ` (退出码: ${job.exit_code})` : ''}`,
+      cancelled: '已取消',
+    };
+    const stateClass = job.state === 'succeeded' ? 'text-success' : job.state === 'running' ? 'text-[#999999]' : 'text-error';
+
+    return (
+      <div className="mt-2">
+        <div className="flex items-center gap-2 text-[10px]">
+          <span className="font-bold">{title}</span>
+          <span className={stateClass} title={job.error ?? undefined}>{stateText[job.state]}</span>
+          <div className="flex-1" />
+          {job.state === 'running' ? (
+            <button
+              onClick={handleCancelJob}
+              className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+            >
+              取消
+            </button>
+          ) : (
+            <button
+              onClick={() => setActiveJob(null)}
+              className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+            >
+              关闭
+            </button>
+          )}
+        </div>
+        <pre ref={logRef} className="mt-1 max-h-40 overflow-auto bg-[#1e1e1e] text-[#d4d4d4] text-[10px] p-2 rounded whitespace-pre-wrap">
+          {jobLog.join('\n')}
+        </pre>
+      </div>
+    );
   };
 
   const renderNodejsButton = () => {
@@ -224,13 +544,25 @@ export const DependencyFrame = () => {
 
     if (!claudeStatus.installed) {
       return (
-        <button
-          onClick={handleInstallClaude}
-          disabled={claudeLoading}
-          className="px-3 py-1 text-[10px] bg-primary hover:bg-primary-hover rounded text-white"
-        >
-          {claudeLoading ? '安装中...' : '安装'}
-        </button>
+        <>
+          <button
+            onClick={handleInstallClaude}
+            disabled={claudeLoading}
+            title="通过 npm 全局安装 (需要 Node.js)"
+            className="px-3 py-1 text-[10px] bg-primary hover:bg-primary-hover rounded text-white"
+          >
+            {claudeLoading ? '安装中...' : '安装'}
+          </button>
+          {!claudeLoading && (
+            <button
+              onClick={handleInstallClaudeNative}
+              title="使用 Claude Code 原生安装器安装到 ~/.local/bin (无需 Node.js,可自动更新)"
+              className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+            >
+              原生安装
+            </button>
+          )}
+        </>
       );
     }
 
@@ -303,6 +635,14 @@ export const DependencyFrame = () => {
             <span className="text-[10px]">Claude Code:</span>
             {renderStatus(claudeStatus)}
             {renderClaudeButton()}
+            {claudeStatus && !claudeLoading && (
+              <button
+                onClick={toggleVersionPanel}
+                className="px-3 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+              >
+                版本
+              </button>
+            )}
           </div>
 
           <div className="flex-1" />
@@ -313,8 +653,26 @@ export const DependencyFrame = () => {
           >
             检查更新
           </button>
+
+          <button
+            onClick={() => setShowCleanup(true)}
+            className="px-4 py-1 text-[10px] bg-[#666666] hover:bg-[#555555] text-white rounded"
+          >
+            卸载/清理
+          </button>
         </div>
+
+        {showVersions && renderVersionPanel()}
+        {activeJob && renderJobPanel(activeJob)}
       </div>
+
+      <CleanupDialog
+        isOpen={showCleanup}
+        onClose={() => {
+          setShowCleanup(false);
+          checkWithUpdates();
+        }}
+      />
     </div>
   );
 };
@@ -0,0 +1,95 @@
+import React from 'react';
+import type { LaunchHook } from '../types/project';
+
+interface HookListEditorProps {
+  hooks: LaunchHook[];
+  onChange: (hooks: LaunchHook[]) => void;
+  placeholder: string;
+  abortLabel: string;
+}
+
+const DEFAULT_TIMEOUT_SECS = 60;
+
+export const HookListEditor: React.FC<HookListEditorProps> = ({ hooks, onChange, placeholder, abortLabel }) => {
+  const update = (index: number, patch: Partial<LaunchHook>) => {
+    onChange(hooks.map((hook, i) => (i === index ? { ...hook, ...patch } : hook)));
+  };
+
+  const move = (index: number, offset: number) => {
+    const target = index + offset;
+    if (target < 0 || target >= hooks.length) return;
+    const next = [...hooks];
+    [next[index], next[target]] = [next[target], next[index]];
+    onChange(next);
+  };
+
+  const handleAdd = () => {
+    onChange([...hooks, { command: '', timeout_secs: DEFAULT_TIMEOUT_SECS, abort_on_failure: true }]);
+  };
+
+  return (
+    <div className="space-y-2">
+      {hooks.map((hook, index) => (
+        <div key={index} className="flex items-center gap-2">
+          <span className="text-[11px] text-[#999999] w-4 flex-shrink-0">{index + 1}.</span>
+          <input
+            type="text"
+            value={hook.command}
+            onChange={(e) => update(index, { command: e.target.value })}
+            placeholder={placeholder}
+            className="flex-1 px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px] font-mono"
+          />
+          <input
+            type="number"
+            min={1}
+            max={3600}
+            value={hook.timeout_secs}
+            onChange={(e) => update(index, { timeout_secs: Number(e.target.value) || DEFAULT_TIMEOUT_SECS })}
+            title="超时 (秒)"
+            className="w-16 px-2 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
+          />
+          <span className="text-[11px] text-[#999999] flex-shrink-0">秒</span>
+          <label className="flex items-center gap-1 cursor-pointer flex-shrink-0" title={abortLabel}>
+            <input
+              type="checkbox"
+              checked={hook.abort_on_failure}
+              onChange={(e) => update(index, { abort_on_failure: e.target.checked })}
+              className="w-4 h-4"
+            />
+            <span className="text-[11px]">失败中止</span>
+          </label>
+          <button
+            type="button"
+            onClick={() => move(index, -1)}
+            disabled={index === 0}
+            className="px-2 py-1 text-[11px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded disabled:opacity-50"
+          >
+            ↑
+          </button>
+          <button
+            type="button"
+            onClick={() => move(index, 1)}
+            disabled={index === hooks.length - 1}
+            className="px-2 py-1 text-[11px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded disabled:opacity-50"
+          >
+            ↓
+          </button>
+          <button
+            type="button"
+            onClick={() => onChange(hooks.filter((_, i) => i !== index))}
+            className="px-2 py-1 text-[11px] bg-[#565B5E] hover:bg-red-600 text-white rounded"
+          >
+            删除
+          </button>
+        </div>
+      ))}
+      <button
+        type="button"
+        onClick={handleAdd}
+        className="px-3 py-1 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+      >
+        + 添加命令
+      </button>
+    </div>
+  );
+};
@@ -0,0 +1,129 @@
+import React, { useEffect, useState } from 'react';
+import { writeText } from '@tauri-apps/plugin-clipboard-manager';
+import { logApi } from '../api';
+import type { LogEntry, LogLevel } from '../types';
+
+interface LogsDialogProps {
+  isOpen: boolean;
+  onClose: () => void;
+}
+
+// 只展示最近的日志,避免大文件拖慢界面
+const MAX_ENTRIES = 500;
+
+const LEVEL_LABELS: Record<LogLevel, string> = {
+  debug: '调试',
+  info: '信息',
+  warn: '警告',
+  error: '错误',
+};
+
+const LEVEL_COLORS: Record<LogLevel, string> = {
+  debug: 'text-[#999999]',
+  info: 'text-[#d4d4d4]',
+  warn: 'text-yellow-400',
+  error: 'text-red-400',
+};
+
+const formatEntry = (entry: LogEntry): string => {
+  const fields = entry.fields && Object.keys(entry.fields).length > 0 ? ' ' + JSON.stringify(entry.fields) : '';
+  return `${entry.timestamp} [${entry.level.toUpperCase()}] ${entry.source}: ${entry.message}${fields}`;
+};
+
+export const LogsDialog: React.FC<LogsDialogProps> = ({ isOpen, onClose }) => {
+  const [entries, setEntries] = useState<LogEntry[]>([]);
+  const [minLevel, setMinLevel] = useState<LogLevel>('info');
+  const [contains, setContains] = useState('');
+  const [logDir, setLogDir] = useState('');
+  const [copied, setCopied] = useState(false);
+
+  useEffect(() => {
+    if (!isOpen) {
+      return;
+    }
+    logApi.get({ min_level: minLevel, contains: contains || undefined, limit: MAX_ENTRIES })
+      .then(setEntries)
+      .catch((error) => console.error('读取日志失败:', error));
+  }, [isOpen, minLevel, contains]);
+
+  useEffect(() => {
+    if (isOpen) {
+      logApi.getDirectory().then(setLogDir).catch(() => {});
+    }
+  }, [isOpen]);
+
+  if (!isOpen) return null;
+
+  const handleCopy = async () => {
+    try {
+      await writeText(entries.map(formatEntry).join('\n'));
+      setCopied(true);
+      setTimeout(() => setCopied(false), 2000);
+    } catch (error: any) {
+      alert(`复制失败: ${error}`);
+    }
+  };
+
+  return (
+    <div className="fixed inset-0 z-50 flex items-center justify-center">
+      {/* 背景遮罩 */}
+      <div className="absolute inset-0 bg-black/60" onClick={onClose} />
+
+      {/* 对话框 */}
+      <div className="relative bg-[#2a2a2a] border border-[#565B5E] rounded-lg shadow-xl max-w-4xl w-full mx-4 p-6">
+        <h3 className="text-[16px] font-bold text-[#DCE4EE] mb-1">启动器日志</h3>
+        <div className="text-[11px] text-[#999999] mb-3">
+          令牌与代理密码已自动隐藏,可直接复制附在问题反馈中。{logDir && `日志目录: ${logDir}`}
+        </div>
+
+        <div className="flex items-center gap-3 mb-3">
+          <select
+            value={minLevel}
+            onChange={(e) => setMinLevel(e.target.value as LogLevel)}
+            className="bg-[#1e1e1e] border border-[#565B5E] rounded px-2 py-1 text-[12px] text-[#DCE4EE]"
+          >
+            {(Object.keys(LEVEL_LABELS) as LogLevel[]).map((level) => (
+              <option key={level} value={level}>
+                {LEVEL_LABELS[level]}及以上
+              </option>
+            ))}
+          </select>
+          <input
+            type="text"
+            value={contains}
+            onChange={(e) => setContains(e.target.value)}
+            placeholder="搜索..."
+            className="flex-1 bg-[#1e1e1e] border border-[#565B5E] rounded px-2 py-1 text-[12px] text-[#DCE4EE]"
+          />
+        </div>
+
+        <div className="h-96 overflow-auto bg-[#1e1e1e] rounded p-2 mb-4 text-[11px] font-mono">
+          {entries.length === 0 && <div className="text-[#999999]">没有日志</div>}
+          {entries.map((entry, index) => (
+            <div key={index} className={`whitespace-pre-wrap break-all ${LEVEL_COLORS[entry.level]}`}>
+              {formatEntry(entry)}
+            </div>
+          ))}
+        </div>
+
+        <div className="flex justify-end gap-3">
+          <button
+            type="button"
+            onClick={onClose}
+            className="px-4 py-2 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+          >
+            关闭
+          </button>
+          <button
+            type="button"
+            onClick={handleCopy}
+            disabled={entries.length === 0}
+            className="px-4 py-2 text-[12px] bg-[#3b82f6] hover:bg-[#2563eb] text-white rounded disabled:opacity-50"
+          >
+            {copied ? '已复制' : '复制日志'}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+};
@@ -1,11 +1,12 @@
 import { useState } from 'react';
 import { writeText } from '@tauri-apps/plugin-clipboard-manager';
-import type { Project } from '../types/project';
+import type { Project, ProjectSessionStats } from '../types/project';
 import { projectApi } from '../api';
 
 interface ProjectCardProps {
   project: Project;
   platform: string;
+  sessionStats?: ProjectSessionStats;
   onLaunch: (id: string) => void;
   onEdit: (id: string) => void;
   isDragging?: boolean;
@@ -14,6 +15,7 @@ interface ProjectCardProps {
 export const ProjectCard: React.FC<ProjectCardProps> = ({
   project,
   platform,
+  sessionStats,
   onLaunch,
   onEdit,
   isDragging = false,
@@ -31,6 +33,12 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
     return path;
   };
 
+  const formatDuration = (secs: number) => {
+    const hours = Math.floor(secs / 3600);
+    const minutes = Math.floor((secs % 3600) / 60);
+    return hours > 0 ? `${hours}小时${minutes}分` : `${minutes}分钟`;
+  };
+
   const getModeLabel = () => {
     return project.config.mode === 'claude' ? 'Claude原版' : '自定义模型';
   };
@@ -70,6 +78,11 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
           <h3 className="text-[14px] font-medium text-white truncate">
             {project.name}
           </h3>
+          {sessionStats && sessionStats.running > 0 && (
+            <span className="px-2 py-0.5 text-[10px] bg-[#16a34a] text-white rounded whitespace-nowrap" title={`${sessionStats.running} 个会话运行中`}>
+              运行中{sessionStats.running > 1 ? ` ×${sessionStats.running}` : ''}
+            </span>
+          )}
           {project.is_pinned && (
             <span className="px-2 py-0.5 text-[10px] bg-[#f59e0b] text-white rounded whitespace-nowrap" title="已置顶">
               置顶
@@ -103,6 +116,7 @@ export const ProjectCard: React.FC<ProjectCardProps> = ({
           ? `上次启动: ${new Date(project.last_launched_at * 1000).toLocaleString()}`
           : `创建时间: ${new Date(project.created_at * 1000).toLocaleString()}`
         }
+        {sessionStats && sessionStats.session_count > 0 && ` · 累计使用: ${formatDuration(sessionStats.total_secs)}`}
       </p>
 
       {/* Buttons row */}
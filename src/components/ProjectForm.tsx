@@ -1,6 +1,17 @@
 import { useState, useEffect } from 'react';
 import { DirectoryPicker } from './DirectoryPicker';
-import type { ProjectConfig } from '../types/project';
+import { CaBundlePicker } from './CaBundlePicker';
+import { HookListEditor } from './HookListEditor';
+import { caBundleApi, projectApi } from '../api';
+import type { ConnectivityResult, DetectedProxy, LaunchHook, ProjectConfig, ProxySource } from '../types/project';
+
+const PROXY_SOURCE_LABELS: Record<ProxySource, string> = {
+  environment: '环境变量',
+  windows: 'Windows Internet 设置',
+  macos: 'macOS 网络设置',
+  gnome: 'GNOME 代理设置',
+  kde: 'KDE 代理设置',
+};
 
 interface ProjectFormProps {
   initialName?: string;
@@ -42,6 +53,11 @@ export const ProjectForm: React.FC<ProjectFormProps> = ({
     if (initialConfig) {
       if (initialConfig.mode) setMode(initialConfig.mode);
       if (initialConfig.proxy) setProxy(initialConfig.proxy);
+      if (initialConfig.https_proxy) setHttpsProxy(initialConfig.https_proxy);
+      if (initialConfig.no_proxy) setNoProxy(initialConfig.no_proxy);
+      if (initialConfig.inherit_system_proxy !== undefined) setInheritSystemProxy(initialConfig.inherit_system_proxy);
+      if (initialConfig.proxy_username) setProxyUsername(initialConfig.proxy_username);
+      if (initialConfig.proxy_password) setProxyPassword(initialConfig.proxy_password);
       if (initialConfig.model) setModel(initialConfig.model);
       if (initialConfig.base_url) setBaseUrl(initialConfig.base_url);
       if (initialConfig.token) setToken(initialConfig.token);
@@ -54,14 +70,26 @@ export const ProjectForm: React.FC<ProjectFormProps> = ({
     setIsPinned(initialIsPinned);
   }, [initialIsPinned]);
   const [proxy, setProxy] = useState(initialConfig?.proxy || '');
+  const [httpsProxy, setHttpsProxy] = useState(initialConfig?.https_proxy || '');
+  const [noProxy, setNoProxy] = useState(initialConfig?.no_proxy || '');
+  const [inheritSystemProxy, setInheritSystemProxy] = useState(initialConfig?.inherit_system_proxy ?? false);
+  const [proxyUsername, setProxyUsername] = useState(initialConfig?.proxy_username || '');
+  const [proxyPassword, setProxyPassword] = useState(initialConfig?.proxy_password || '');
+  const [showProxyPassword, setShowProxyPassword] = useState(false);
+  const [preLaunchHooks, setPreLaunchHooks] = useState<LaunchHook[]>(initialConfig?.pre_launch_hooks || []);
+  const [postExitHooks, setPostExitHooks] = useState<LaunchHook[]>(initialConfig?.post_exit_hooks || []);
+  const [caBundle, setCaBundle] = useState(initialConfig?.ca_bundle || { path: '', set_ssl_cert_file: false });
   const [model, setModel] = useState(initialConfig?.model || '');
   const [baseUrl, setBaseUrl] = useState(initialConfig?.base_url || 'http://litellm.uattest.weoa.com');
   const [token, setToken] = useState(initialConfig?.token || '');
   const [skipPermissions, setSkipPermissions] = useState(initialConfig?.skip_permissions ?? false);
   const [showToken, setShowToken] = useState(false);
   const [errors, setErrors] = useState<Record<string, string>>({});
+  const [testing, setTesting] = useState(false);
+  const [testResult, setTestResult] = useState<ConnectivityResult | null>(null);
+  const [detectedProxies, setDetectedProxies] = useState<DetectedProxy[] | null>(null);
 
-  const validate = (): boolean => {
+  const validate = async (): Promise<boolean> => {
     const newErrors: Record<string, string> = {};
 
     if (!name.trim()) {
@@ -72,8 +100,35 @@ export const ProjectForm: React.FC<ProjectFormProps> = ({
       newErrors.workingDirectory = '请选择工作目录';
     }
 
-    if (mode === 'claude' && proxy && !proxy.startsWith('http://') && !proxy.startsWith('https://')) {
-      newErrors.proxy = '代理地址必须以 http:// 或 https:// 开头';
+    // 地址中直接写了 user:password@ 时拆分到单独的认证字段
+    const proxyFields: [string, string, (url: string) => void][] = [
+      ['proxy', proxy, setProxy],
+      ['httpsProxy', httpsProxy, setHttpsProxy],
+    ];
+    for (const [key, value, setValue] of proxyFields) {
+      if (!value.trim()) continue;
+      try {
+        const info = await projectApi.validateProxy(value);
+        setValue(info.url);
+        if (info.username || info.password) {
+          setProxyUsername(info.username);
+          setProxyPassword(info.password);
+        }
+      } catch (err: any) {
+        newErrors[key] = err?.toString() || '代理地址格式错误';
+      }
+    }
+
+    if (caBundle.path.trim()) {
+      try {
+        await caBundleApi.inspect(caBundle.path);
+      } catch (err: any) {
+        newErrors.caBundle = err?.toString() || '证书文件无效';
+      }
+    }
+
+    if ([...preLaunchHooks, ...postExitHooks].some((hook) => /[\r\n]/.test(hook.command))) {
+      newErrors.hooks = '钩子命令不能包含换行';
     }
 
     if (mode === 'custom' && baseUrl && !baseUrl.startsWith('http://') && !baseUrl.startsWith('https://')) {
@@ -84,23 +139,68 @@ export const ProjectForm: React.FC<ProjectFormProps> = ({
     return Object.keys(newErrors).length === 0;
   };
 
-  const handleSubmit = (e: React.FormEvent) => {
+  // 后端会校验代理地址与 NO_PROXY 列表，并拆分地址中的认证信息
+  const buildConfig = (): ProjectConfig => ({
+    mode,
+    proxy: proxy.trim(),
+    https_proxy: httpsProxy.trim(),
+    no_proxy: noProxy,
+    inherit_system_proxy: inheritSystemProxy,
+    proxy_username: proxyUsername,
+    proxy_password: proxyPassword,
+    model,
+    base_url: baseUrl,
+    token,
+    skip_permissions: skipPermissions,
+    ca_bundle: caBundle,
+    pre_launch_hooks: preLaunchHooks,
+    post_exit_hooks: postExitHooks,
+  });
+
+  const handleDetectProxy = async () => {
+    try {
+      setDetectedProxies(await projectApi.detectSystemProxy());
+    } catch (err: any) {
+      console.error('检测系统代理失败:', err);
+      setDetectedProxies([]);
+    }
+  };
+
+  const handleUseDetected = (detected: DetectedProxy) => {
+    const http = detected.http_proxy || detected.https_proxy || '';
+    setProxy(http);
+    setHttpsProxy(detected.https_proxy && detected.https_proxy !== http ? detected.https_proxy : '');
+    if (detected.no_proxy) setNoProxy(detected.no_proxy);
+    setDetectedProxies(null);
+  };
+
+  const handleTestConnectivity = async () => {
+    setTesting(true);
+    setTestResult(null);
+    try {
+      setTestResult(await projectApi.testConnectivity(buildConfig()));
+    } catch (err: any) {
+      setTestResult({
+        target: '',
+        proxy: null,
+        ok: false,
+        status: null,
+        latency_ms: 0,
+        error: err?.toString() || '测试失败',
+      });
+    } finally {
+      setTesting(false);
+    }
+  };
+
+  const handleSubmit = async (e: React.FormEvent) => {
     e.preventDefault();
 
-    if (!validate()) {
+    if (!(await validate())) {
       return;
     }
 
-    const config: ProjectConfig = {
-      mode,
-      proxy,
-      model,
-      base_url: baseUrl,
-      token,
-      skip_permissions: skipPermissions,
-    };
-
-    onSubmit(name.trim(), workingDirectory.trim(), config, isPinned);
+    onSubmit(name.trim(), workingDirectory.trim(), buildConfig(), isPinned);
   };
 
   return (
@@ -172,24 +272,6 @@ export const ProjectForm: React.FC<ProjectFormProps> = ({
         </div>
       </div>
 
-      {/* Claude 原版模式 */}
-      {mode === 'claude' && (
-        <div>
-          <label className="block text-[12px] mb-1">代理地址 (可选)</label>
-          <input
-            type="text"
-            value={proxy}
-            onChange={(e) => setProxy(e.target.value)}
-            placeholder="例: http://127.0.0.1:7890"
-            className="w-full px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
-          />
-          {errors.proxy && <p className="text-[10px] text-red-500 mt-1">{errors.proxy}</p>}
-          <p className="text-[10px] text-[#999999] mt-1">
-            原版 Claude 服务需要翻墙，可在此处配置代理地址
-          </p>
-        </div>
-      )}
-
       {/* 自定义模型模式 */}
       {mode === 'custom' && (
         <div className="space-y-3">
@@ -241,6 +323,172 @@ export const ProjectForm: React.FC<ProjectFormProps> = ({
         </div>
       )}
 
+      {/* 网络代理 (两种模式通用) */}
+      <div className="space-y-2">
+        <label className="block text-[12px] mb-1">网络代理 (可选)</label>
+        <div>
+          <input
+            type="text"
+            value={proxy}
+            onChange={(e) => setProxy(e.target.value)}
+            placeholder="HTTP 代理，例: http://127.0.0.1:7890"
+            className="w-full px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
+          />
+          {errors.proxy && <p className="text-[10px] text-red-500 mt-1">{errors.proxy}</p>}
+        </div>
+        <div>
+          <input
+            type="text"
+            value={httpsProxy}
+            onChange={(e) => setHttpsProxy(e.target.value)}
+            placeholder="HTTPS 代理，留空则与 HTTP 代理相同"
+            className="w-full px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
+          />
+          {errors.httpsProxy && <p className="text-[10px] text-red-500 mt-1">{errors.httpsProxy}</p>}
+        </div>
+
+        {/* 代理认证 (HTTP/HTTPS 共用) */}
+        {(proxy.trim() || httpsProxy.trim()) && (
+          <div className="flex items-center gap-2">
+            <input
+              type="text"
+              value={proxyUsername}
+              onChange={(e) => setProxyUsername(e.target.value)}
+              placeholder="代理用户名 (可选)"
+              className="flex-1 px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
+            />
+            <input
+              type={showProxyPassword ? 'text' : 'password'}
+              value={proxyPassword}
+              onChange={(e) => setProxyPassword(e.target.value)}
+              placeholder="代理密码 (可选)"
+              className="flex-1 px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
+            />
+            <button
+              type="button"
+              onClick={() => setShowProxyPassword(!showProxyPassword)}
+              className="px-3 py-2 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+            >
+              {showProxyPassword ? '隐藏' : '显示'}
+            </button>
+          </div>
+        )}
+
+        <div>
+          <input
+            type="text"
+            value={noProxy}
+            onChange={(e) => setNoProxy(e.target.value)}
+            placeholder="NO_PROXY，不走代理的主机，例: localhost,.corp.example.com,10.0.0.0/8"
+            className="w-full px-3 py-2 bg-[#343638] border border-[#565B5E] rounded text-[12px]"
+          />
+        </div>
+
+        <label className="flex items-center gap-2 cursor-pointer">
+          <input
+            type="checkbox"
+            checked={inheritSystemProxy}
+            onChange={(e) => setInheritSystemProxy(e.target.checked)}
+            className="w-4 h-4"
+          />
+          <span className="text-[12px]">未填写的项使用系统代理设置</span>
+        </label>
+
+        <div className="flex items-center gap-2">
+          <button
+            type="button"
+            onClick={handleDetectProxy}
+            className="px-3 py-1 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+          >
+            检测系统代理
+          </button>
+          <button
+            type="button"
+            onClick={handleTestConnectivity}
+            disabled={testing}
+            className="px-3 py-1 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded disabled:opacity-50"
+          >
+            {testing ? '测试中...' : '测试连接'}
+          </button>
+          {testResult && (
+            <span className={`text-[11px] ${testResult.ok ? 'text-[#16a34a]' : 'text-red-500'}`}>
+              {testResult.ok
+                ? `✓ ${testResult.target} 可访问 (HTTP ${testResult.status}, ${testResult.latency_ms}ms${testResult.proxy ? `, 经由 ${testResult.proxy}` : ', 直连'})`
+                : `✗ ${testResult.error}`}
+            </span>
+          )}
+        </div>
+
+        {/* 检测到的系统代理 */}
+        {detectedProxies && (
+          <div className="bg-[#2a2a2a] border border-[#565B5E] rounded p-2 space-y-1">
+            {detectedProxies.length === 0 && (
+              <div className="text-[11px] text-[#999999]">未检测到系统代理</div>
+            )}
+            {detectedProxies.map((detected) => (
+              <div key={detected.source} className="flex items-center justify-between gap-2 text-[11px]">
+                <div className="min-w-0">
+                  <div className="text-[#DCE4EE]">{PROXY_SOURCE_LABELS[detected.source]}</div>
+                  {detected.http_proxy && <div className="text-[#999999] break-all">HTTP: {detected.http_proxy}</div>}
+                  {detected.https_proxy && <div className="text-[#999999] break-all">HTTPS: {detected.https_proxy}</div>}
+                  {detected.no_proxy && <div className="text-[#999999] break-all">NO_PROXY: {detected.no_proxy}</div>}
+                  {detected.pac_url && (
+                    <div className="text-yellow-400 break-all">
+                      自动配置脚本 (PAC): {detected.pac_url}，请从脚本中确认实际代理地址后手动填写
+                    </div>
+                  )}
+                </div>
+                {(detected.http_proxy || detected.https_proxy) && (
+                  <button
+                    type="button"
+                    onClick={() => handleUseDetected(detected)}
+                    className="px-2 py-1 text-[11px] bg-[#3b82f6] hover:bg-[#2563eb] text-white rounded flex-shrink-0"
+                  >
+                    填入
+                  </button>
+                )}
+              </div>
+            ))}
+          </div>
+        )}
+
+        <p className="text-[10px] text-[#999999]">
+          原版 Claude 服务需要翻墙；内网模型网关可加入 NO_PROXY 直连。代理密码保存在系统密钥链中，仅在启动时注入，复制的命令和日志中会显示为 ****
+        </p>
+      </div>
+
+      {/* 自定义根证书 (公司代理做 TLS 解密时需要) */}
+      <div className="space-y-2">
+        <label className="block text-[12px] mb-1">根证书 (可选)</label>
+        <CaBundlePicker value={caBundle} onChange={setCaBundle} placeholder="留空则使用全局证书设置" />
+        <p className="text-[10px] text-[#999999]">
+          以 NODE_EXTRA_CA_CERTS 注入启动环境，遇到 SELF_SIGNED_CERT_IN_CHAIN 错误时配置公司代理的根证书
+        </p>
+        {errors.caBundle && <p className="text-[10px] text-red-500 mt-1">{errors.caBundle}</p>}
+      </div>
+
+      {/* 启动钩子 */}
+      <div className="space-y-2">
+        <label className="block text-[12px] mb-1">启动前命令 (可选)</label>
+        <HookListEditor
+          hooks={preLaunchHooks}
+          onChange={setPreLaunchHooks}
+          placeholder="例: git pull"
+          abortLabel="命令失败或超时时不启动 Claude Code"
+        />
+        <label className="block text-[12px] mb-1 mt-3">退出后命令 (可选)</label>
+        <HookListEditor
+          hooks={postExitHooks}
+          onChange={setPostExitHooks}
+          placeholder="例: npm run format"
+          abortLabel="命令失败或超时时跳过后面的命令"
+        />
+        <p className="text-[10px] text-[#999999]">
+          按顺序在项目目录中执行，使用与 Claude Code 相同的环境变量；输出会显示在终端中，并可在项目详情中查看
+        </p>
+        {errors.hooks && <p className="text-[10px] text-red-500 mt-1">{errors.hooks}</p>}
+      </div>
+
       {/* 启动模式 */}
       <div>
         <label className="block text-[12px] mb-2">启动模式</label>
@@ -1,11 +1,12 @@
 import { useSortable } from '@dnd-kit/sortable';
 import { CSS } from '@dnd-kit/utilities';
 import { ProjectCard } from './ProjectCard';
-import type { Project } from '../types/project';
+import type { Project, ProjectSessionStats } from '../types/project';
 
 interface SortableProjectCardProps {
   project: Project;
   platform: string;
+  sessionStats?: ProjectSessionStats;
   onLaunch: (id: string) => void;
   onEdit: (id: string) => void;
 }
@@ -13,6 +14,7 @@ interface SortableProjectCardProps {
 export const SortableProjectCard: React.FC<SortableProjectCardProps> = ({
   project,
   platform,
+  sessionStats,
   onLaunch,
   onEdit,
 }) => {
@@ -36,6 +38,7 @@ export const SortableProjectCard: React.FC<SortableProjectCardProps> = ({
       <ProjectCard
         project={project}
         platform={platform}
+        sessionStats={sessionStats}
         onLaunch={onLaunch}
         onEdit={onEdit}
         isDragging={isDragging}
@@ -0,0 +1,113 @@
+import { useEffect, useState } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { terminalApi } from '../api';
+import { TerminalView } from './TerminalView';
+import type { TerminalExitEvent, TerminalInfo } from '../types';
+
+// 应用内终端:每次启动一个标签页,关闭标签页会结束对应的 claude 进程
+export const TerminalTabs: React.FC = () => {
+  const [terminals, setTerminals] = useState<TerminalInfo[]>([]);
+  const [activeId, setActiveId] = useState<string | null>(null);
+  const [collapsed, setCollapsed] = useState(false);
+
+  useEffect(() => {
+    terminalApi
+      .list()
+      .then((list) => {
+        setTerminals(list);
+        if (list.length > 0) {
+          setActiveId(list[list.length - 1].id);
+        }
+      })
+      .catch((error) => console.error('获取终端列表失败:', error));
+
+    const unlistenOpened = listen<TerminalInfo>('terminal-opened', (event) => {
+      setTerminals((prev) => [...prev.filter((t) => t.id !== event.payload.id), event.payload]);
+      setActiveId(event.payload.id);
+      setCollapsed(false);
+    });
+    const unlistenExit = listen<TerminalExitEvent>('terminal-exit', (event) => {
+      setTerminals((prev) =>
+        prev.map((t) =>
+          t.id === event.payload.terminal_id
+            ? { ...t, exited: true, exit_code: event.payload.exit_code }
+            : t
+        )
+      );
+    });
+
+    return () => {
+      unlistenOpened.then((fn) => fn());
+      unlistenExit.then((fn) => fn());
+    };
+  }, []);
+
+  if (terminals.length === 0) return null;
+
+  const handleClose = async (terminal: TerminalInfo) => {
+    if (!terminal.exited && !confirm(`Claude Code 仍在运行,确定关闭「${terminal.title}」?`)) {
+      return;
+    }
+    try {
+      await terminalApi.close(terminal.id);
+    } catch (error: any) {
+      console.error('关闭终端失败:', error);
+    }
+    const remaining = terminals.filter((t) => t.id !== terminal.id);
+    setTerminals(remaining);
+    if (activeId === terminal.id) {
+      setActiveId(remaining.length > 0 ? remaining[remaining.length - 1].id : null);
+    }
+  };
+
+  return (
+    <div
+      className="fixed left-0 right-0 bottom-0 z-40 bg-[#1e1e1e] border-t border-[#565B5E] flex flex-col"
+      style={{ height: collapsed ? 'auto' : '55vh' }}
+    >
+      {/* 标签栏 */}
+      <div className="flex items-center bg-[#2a2a2a] overflow-x-auto">
+        {terminals.map((terminal) => (
+          <div
+            key={terminal.id}
+            onClick={() => {
+              setActiveId(terminal.id);
+              setCollapsed(false);
+            }}
+            className={`flex items-center gap-2 px-3 py-1 text-[11px] cursor-pointer border-r border-[#3a3a3a] whitespace-nowrap ${
+              terminal.id === activeId ? 'bg-[#1e1e1e] text-white' : 'text-[#999999] hover:text-white'
+            }`}
+          >
+            <span className={terminal.exited ? 'text-[#666666]' : 'text-[#16a34a]'}>●</span>
+            {terminal.title}
+            {terminal.exited && ` (退出码 ${terminal.exit_code ?? '?'})`}
+            <button
+              onClick={(e) => {
+                e.stopPropagation();
+                handleClose(terminal);
+              }}
+              className="text-[#999999] hover:text-white"
+              title="关闭"
+            >
+              ×
+            </button>
+          </div>
+        ))}
+        <div className="flex-1" />
+        <button
+          onClick={() => setCollapsed(!collapsed)}
+          className="px-3 py-1 text-[11px] text-[#999999] hover:text-white"
+        >
+          {collapsed ? '展开' : '收起'}
+        </button>
+      </div>
+
+      {/* 终端内容:保持挂载,切换标签时不丢失状态 */}
+      <div className="flex-1 min-h-0 p-1" style={{ display: collapsed ? 'none' : 'block' }}>
+        {terminals.map((terminal) => (
+          <TerminalView key={terminal.id} terminalId={terminal.id} active={terminal.id === activeId} />
+        ))}
+      </div>
+    </div>
+  );
+};
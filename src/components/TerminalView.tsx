@@ -0,0 +1,111 @@
+import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+import { Terminal } from '@xterm/xterm';
+import { FitAddon } from '@xterm/addon-fit';
+import '@xterm/xterm/css/xterm.css';
+import { terminalApi } from '../api';
+import type { TerminalOutputEvent } from '../types';
+
+interface TerminalViewProps {
+  terminalId: string;
+  active: boolean;
+}
+
+const decodeBase64 = (data: string): Uint8Array => {
+  const binary = atob(data);
+  const bytes = new Uint8Array(binary.length);
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i);
+  }
+  return bytes;
+};
+
+export const TerminalView: React.FC<TerminalViewProps> = ({ terminalId, active }) => {
+  const containerRef = useRef<HTMLDivElement>(null);
+  const termRef = useRef<Terminal | null>(null);
+  const fitRef = useRef<FitAddon | null>(null);
+
+  useEffect(() => {
+    if (!containerRef.current) return;
+
+    const term = new Terminal({
+      cursorBlink: true,
+      fontSize: 12,
+      fontFamily: 'Menlo, Consolas, "DejaVu Sans Mono", monospace',
+      theme: { background: '#1e1e1e' },
+      scrollback: 5000,
+    });
+    const fit = new FitAddon();
+    term.loadAddon(fit);
+    term.open(containerRef.current);
+    termRef.current = term;
+    fitRef.current = fit;
+
+    // 先订阅输出事件,再用快照补齐已有内容;快照之前的事件按 offset 丢弃
+    let snapshotEnd: number | null = null;
+    const pending: TerminalOutputEvent[] = [];
+    const writeEvent = (event: TerminalOutputEvent) => {
+      if (snapshotEnd !== null && event.offset >= snapshotEnd) {
+        term.write(decodeBase64(event.data));
+      }
+    };
+
+    const unlistenOutput = listen<TerminalOutputEvent>('terminal-output', (event) => {
+      if (event.payload.terminal_id !== terminalId) return;
+      if (snapshotEnd === null) {
+        pending.push(event.payload);
+      } else {
+        writeEvent(event.payload);
+      }
+    });
+
+    terminalApi
+      .snapshot(terminalId)
+      .then((snapshot) => {
+        term.write(decodeBase64(snapshot.data));
+        snapshotEnd = snapshot.end;
+        pending.forEach(writeEvent);
+      })
+      .catch((error) => console.error('加载终端内容失败:', error));
+
+    const input = term.onData((data) => {
+      terminalApi.write(terminalId, data).catch(() => {});
+    });
+    const resize = term.onResize(({ cols, rows }) => {
+      terminalApi.resize(terminalId, cols, rows).catch(() => {});
+    });
+
+    const observer = new ResizeObserver(() => {
+      if (containerRef.current && containerRef.current.offsetParent !== null) {
+        fit.fit();
+      }
+    });
+    observer.observe(containerRef.current);
+
+    return () => {
+      observer.disconnect();
+      input.dispose();
+      resize.dispose();
+      unlistenOutput.then((fn) => fn());
+      term.dispose();
+      termRef.current = null;
+      fitRef.current = null;
+    };
+  }, [terminalId]);
+
+  // 隐藏的标签页无法测量尺寸,切换回来时重新适配
+  useEffect(() => {
+    if (active) {
+      fitRef.current?.fit();
+      termRef.current?.focus();
+    }
+  }, [active]);
+
+  return (
+    <div
+      ref={containerRef}
+      className="w-full h-full"
+      style={{ display: active ? 'block' : 'none' }}
+    />
+  );
+};
@@ -0,0 +1,19 @@
+import { useEffect, useRef } from 'react';
+import { listen } from '@tauri-apps/api/event';
+
+export type ConfigFile = 'config' | 'settings';
+
+// config.json 或 ~/.claude/settings.json 被启动器以外的程序修改时调用 onChange
+export function useConfigChanged(onChange: (file: ConfigFile) => void) {
+  const callbackRef = useRef(onChange);
+  callbackRef.current = onChange;
+
+  useEffect(() => {
+    const unlisten = listen<{ file: ConfigFile }>('config-changed', (event) => {
+      callbackRef.current(event.payload.file);
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+}
@@ -1,7 +1,8 @@
 import { useEffect, useState } from 'react';
 import { useNavigate, useParams } from 'react-router-dom';
-import { projectApi, api } from '../api';
-import type { Project } from '../types/project';
+import { projectApi, api, sessionApi } from '../api';
+import type { LaunchHook, Project } from '../types/project';
+import { useConfigChanged } from '../hooks/useConfigChanged';
 
 export const ProjectDetailPage: React.FC = () => {
   const navigate = useNavigate();
@@ -11,6 +12,7 @@ export const ProjectDetailPage: React.FC = () => {
   const [error, setError] = useState<string | null>(null);
   const [copySuccess, setCopySuccess] = useState(false);
   const [platform, setPlatform] = useState<'windows' | 'macos' | 'linux' | 'unknown'>('windows');
+  const [hookOutput, setHookOutput] = useState('');
 
   useEffect(() => {
     if (id) {
@@ -19,6 +21,12 @@ export const ProjectDetailPage: React.FC = () => {
     }
   }, [id]);
 
+  useConfigChanged((file) => {
+    if (file === 'config' && id) {
+      loadProject(id);
+    }
+  });
+
   const loadPlatform = async () => {
     try {
       const p = await api.getPlatform();
@@ -34,6 +42,7 @@ export const ProjectDetailPage: React.FC = () => {
       setError(null);
       const data = await projectApi.get(projectId);
       setProject(data);
+      loadHookOutput(projectId);
     } catch (err: any) {
       setError(err?.toString() || '加载项目失败');
     } finally {
@@ -41,6 +50,16 @@ export const ProjectDetailPage: React.FC = () => {
     }
   };
 
+  // 最近一次启动的钩子输出 (由启动脚本写入)
+  const loadHookOutput = async (projectId: string) => {
+    try {
+      const [latest] = await sessionApi.list(projectId);
+      setHookOutput(latest ? await sessionApi.getHookOutput(latest.id) : '');
+    } catch (error) {
+      console.error('读取钩子输出失败:', error);
+    }
+  };
+
   const handleLaunch = async () => {
     if (!project) return;
 
@@ -89,6 +108,24 @@ export const ProjectDetailPage: React.FC = () => {
     }
   };
 
+  const handleCreateShortcut = async () => {
+    if (!project) return;
+    try {
+      setProject(await projectApi.createShortcut(project.id));
+    } catch (err: any) {
+      alert(`创建快捷方式失败: ${err}`);
+    }
+  };
+
+  const handleRemoveShortcut = async () => {
+    if (!project) return;
+    try {
+      setProject(await projectApi.removeShortcut(project.id));
+    } catch (err: any) {
+      alert(`删除快捷方式失败: ${err}`);
+    }
+  };
+
   const handleEdit = () => {
     if (project) {
       navigate(`/project/${project.id}/edit`);
@@ -156,10 +193,34 @@ export const ProjectDetailPage: React.FC = () => {
                 </span>
               </div>
 
-              {project.config.mode === 'claude' && project.config.proxy && (
+              {project.config.proxy && (
                 <div className="flex items-center gap-2">
                   <span className="text-[12px] text-[#999999] w-24 flex-shrink-0">代理地址:</span>
-                  <span className="text-[12px]">{project.config.proxy}</span>
+                  <span className="text-[12px]">
+                    {project.config.proxy}
+                    {project.config.proxy_username && ` (用户: ${project.config.proxy_username})`}
+                  </span>
+                </div>
+              )}
+
+              {project.config.https_proxy && (
+                <div className="flex items-center gap-2">
+                  <span className="text-[12px] text-[#999999] w-24 flex-shrink-0">HTTPS 代理:</span>
+                  <span className="text-[12px]">{project.config.https_proxy}</span>
+                </div>
+              )}
+
+              {project.config.no_proxy && (
+                <div className="flex items-center gap-2">
+                  <span className="text-[12px] text-[#999999] w-24 flex-shrink-0">NO_PROXY:</span>
+                  <span className="text-[12px] break-all">{project.config.no_proxy}</span>
+                </div>
+              )}
+
+              {project.config.inherit_system_proxy && (
+                <div className="flex items-center gap-2">
+                  <span className="text-[12px] text-[#999999] w-24 flex-shrink-0">系统代理:</span>
+                  <span className="text-[12px]">未填写的项使用系统设置</span>
                 </div>
               )}
 
@@ -193,6 +254,27 @@ export const ProjectDetailPage: React.FC = () => {
                 </span>
               </div>
 
+              {([
+                ['启动前命令:', project.config.pre_launch_hooks],
+                ['退出后命令:', project.config.post_exit_hooks],
+              ] as [string, LaunchHook[]][]).map(([label, hooks]) =>
+                hooks.length > 0 && (
+                  <div key={label} className="flex items-start gap-2">
+                    <span className="text-[12px] text-[#999999] w-24 flex-shrink-0">{label}</span>
+                    <div className="text-[12px] space-y-1 min-w-0">
+                      {hooks.map((hook, index) => (
+                        <div key={index} className="font-mono break-all">
+                          {hook.command}
+                          <span className="ml-2 font-sans text-[10px] text-[#999999]">
+                            {hook.timeout_secs}秒{hook.abort_on_failure && ' · 失败中止'}
+                          </span>
+                        </div>
+                      ))}
+                    </div>
+                  </div>
+                )
+              )}
+
               {project.last_launched_at && (
                 <div className="flex items-center gap-2">
                   <span className="text-[12px] text-[#999999] w-24 flex-shrink-0">上次启动:</span>
@@ -237,6 +319,50 @@ export const ProjectDetailPage: React.FC = () => {
               )}
             </div>
 
+            {/* 桌面快捷方式 */}
+            <div className="flex items-center gap-4 mb-4">
+              <span className="text-[12px] text-[#999999]">
+                {platform === 'linux' ? '应用菜单:' : '桌面快捷方式:'}
+              </span>
+              <button
+                onClick={handleCreateShortcut}
+                className="text-[12px] text-[#3b82f6] hover:text-[#2563eb] hover:underline cursor-pointer"
+              >
+                {project.shortcut_path ? '重新创建' : '创建'}
+              </button>
+              {project.shortcut_path && (
+                <>
+                  <button
+                    onClick={handleRemoveShortcut}
+                    className="text-[12px] text-[#3b82f6] hover:text-[#2563eb] hover:underline cursor-pointer"
+                  >
+                    删除
+                  </button>
+                  <span className="text-[10px] text-[#999999] truncate" title={project.shortcut_path}>
+                    {project.shortcut_path}
+                  </span>
+                </>
+              )}
+            </div>
+
+            {/* 最近一次启动的钩子输出 */}
+            {hookOutput && (
+              <div className="mb-4">
+                <div className="flex items-center justify-between mb-1">
+                  <span className="text-[12px] text-[#999999]">最近一次启动的命令输出:</span>
+                  <button
+                    onClick={() => loadHookOutput(project.id)}
+                    className="text-[12px] text-[#3b82f6] hover:text-[#2563eb] hover:underline cursor-pointer"
+                  >
+                    刷新
+                  </button>
+                </div>
+                <pre className="max-h-48 overflow-auto bg-[#1e1e1e] rounded p-2 text-[11px] font-mono whitespace-pre-wrap break-all">
+                  {hookOutput}
+                </pre>
+              </div>
+            )}
+
             {/* 操作按钮 */}
             <div className="flex items-center gap-3">
               <button
@@ -4,6 +4,7 @@ import { projectApi } from '../api';
 import { ProjectForm } from '../components/ProjectForm';
 import { ConfirmDialog } from '../components/ConfirmDialog';
 import { useDragContext } from '../App';
+import { useConfigChanged } from '../hooks/useConfigChanged';
 import type { Project, ProjectConfig } from '../types/project';
 
 export const ProjectEditPage: React.FC = () => {
@@ -11,11 +12,13 @@ export const ProjectEditPage: React.FC = () => {
   const { id } = useParams<{ id: string }>();
   const { registerDragHandler, unregisterDragHandler } = useDragContext();
   const [project, setProject] = useState<Project | null>(null);
+  const [version, setVersion] = useState<string | undefined>(undefined);
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [saving, setSaving] = useState(false);
   const [showDeleteConfirm, setShowDeleteConfirm] = useState(false);
   const [droppedWorkingDirectory, setDroppedWorkingDirectory] = useState<string | null>(null);
+  const [externallyChanged, setExternallyChanged] = useState(false);
 
   // 用 ref 存储 handler 逻辑，确保引用稳定
   const dragHandlerRef = useRef<((path: string) => boolean) | null>(null);
@@ -26,6 +29,13 @@ export const ProjectEditPage: React.FC = () => {
     }
   }, [id]);
 
+  // 编辑中不自动重新加载，以免丢失表单内容；提示用户选择
+  useConfigChanged((file) => {
+    if (file === 'config') {
+      setExternallyChanged(true);
+    }
+  });
+
   // 更新 handler 逻辑（不改变引用）
   useEffect(() => {
     dragHandlerRef.current = (path: string): boolean => {
@@ -49,8 +59,10 @@ export const ProjectEditPage: React.FC = () => {
     try {
       setLoading(true);
       setError(null);
-      const data = await projectApi.get(projectId);
-      setProject(data);
+      setExternallyChanged(false);
+      const snapshot = await projectApi.getSnapshot(projectId);
+      setProject(snapshot.project);
+      setVersion(snapshot.version);
     } catch (err: any) {
       setError(err?.toString() || '加载项目失败');
     } finally {
@@ -68,7 +80,8 @@ export const ProjectEditPage: React.FC = () => {
         project.is_default ? undefined : name,
         project.is_default ? undefined : workingDirectory,
         config,
-        project.is_default ? undefined : isPinned
+        project.is_default ? undefined : isPinned,
+        version
       );
       alert('项目配置已更新');
       navigate('/');
@@ -147,6 +160,19 @@ export const ProjectEditPage: React.FC = () => {
               )}
             </div>
 
+            {externallyChanged && (
+              <div className="flex items-center justify-between gap-3 mb-4 px-3 py-2 bg-[#3a2f1a] border border-yellow-600 rounded text-[12px] text-yellow-300">
+                <span>配置文件已被其他程序修改；若修改涉及该项目，保存会被拒绝，请重新加载后再编辑。</span>
+                <button
+                  type="button"
+                  onClick={() => loadProject(project.id)}
+                  className="px-3 py-1 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded flex-shrink-0"
+                >
+                  重新加载
+                </button>
+              </div>
+            )}
+
             {/* 表单 */}
             <ProjectForm
               initialName={project.name}
@@ -16,11 +16,18 @@ import {
   sortableKeyboardCoordinates,
   verticalListSortingStrategy,
 } from '@dnd-kit/sortable';
-import { projectApi, api } from '../api';
+import { projectApi, api, sessionApi, settingsApi } from '../api';
 import { DependencyFrame } from '../components/DependencyFrame';
+import { LogsDialog } from '../components/LogsDialog';
+import { CaBundleDialog } from '../components/CaBundleDialog';
 import { ProjectCard } from '../components/ProjectCard';
 import { SortableProjectCard } from '../components/SortableProjectCard';
-import type { Project } from '../types/project';
+import { useConfigChanged } from '../hooks/useConfigChanged';
+import type { Project, ProjectSessionStats } from '../types/project';
+import type { LauncherSettings, LauncherSettingsSnapshot } from '../types';
+
+// How often session state is refreshed while the list is open
+const SESSION_POLL_INTERVAL_MS = 10000;
 
 // Sort projects according to the priority rules
 function sortProjects(projects: Project[]): Project[] {
@@ -48,6 +55,12 @@ export const ProjectListPage: React.FC = () => {
   const [loading, setLoading] = useState(true);
   const [error, setError] = useState<string | null>(null);
   const [activeId, setActiveId] = useState<string | null>(null);
+  const [sessionStats, setSessionStats] = useState<Record<string, ProjectSessionStats>>({});
+  const [inAppTerminal, setInAppTerminal] = useState(false);
+  const [hideToTray, setHideToTray] = useState(false);
+  const [settingsSnapshot, setSettingsSnapshot] = useState<LauncherSettingsSnapshot | null>(null);
+  const [showLogs, setShowLogs] = useState(false);
+  const [showCaBundle, setShowCaBundle] = useState(false);
 
   const sensors = useSensors(
     useSensor(PointerSensor, {
@@ -83,8 +96,66 @@ export const ProjectListPage: React.FC = () => {
   useEffect(() => {
     loadProjects();
     loadPlatform();
+    loadSessionStats();
+    loadTerminalSetting();
+
+    // 终端里的会话结束后更新"运行中"标记
+    const timer = setInterval(loadSessionStats, SESSION_POLL_INTERVAL_MS);
+    return () => clearInterval(timer);
   }, []);
 
+  // 配置文件被外部修改后重新加载，避免显示和保存旧数据
+  useConfigChanged((file) => {
+    if (file === 'config') {
+      loadProjects();
+      loadTerminalSetting();
+    }
+  });
+
+  const loadSessionStats = async () => {
+    try {
+      setSessionStats(await sessionApi.getProjectStats());
+    } catch (err) {
+      console.error('Failed to load session stats:', err);
+    }
+  };
+
+  const loadTerminalSetting = async () => {
+    try {
+      const snapshot = await settingsApi.getSnapshot();
+      setSettingsSnapshot(snapshot);
+      setInAppTerminal(snapshot.settings.in_app_terminal);
+      setHideToTray(snapshot.settings.hide_to_tray_on_close);
+    } catch (err) {
+      console.error('Failed to load launcher settings:', err);
+    }
+  };
+
+  // 回传读取时的版本: 设置已被其他窗口修改时拒绝保存,而不是覆盖
+  const saveSettings = async (changes: Partial<LauncherSettings>) => {
+    if (!settingsSnapshot) return;
+    await settingsApi.save({ ...settingsSnapshot.settings, ...changes }, settingsSnapshot.version);
+    setSettingsSnapshot(await settingsApi.getSnapshot());
+  };
+
+  const handleToggleInAppTerminal = async (enabled: boolean) => {
+    try {
+      await saveSettings({ in_app_terminal: enabled });
+      setInAppTerminal(enabled);
+    } catch (err: any) {
+      alert('保存设置失败: ' + (err?.toString() || '未知错误'));
+    }
+  };
+
+  const handleToggleHideToTray = async (enabled: boolean) => {
+    try {
+      await saveSettings({ hide_to_tray_on_close: enabled });
+      setHideToTray(enabled);
+    } catch (err: any) {
+      alert('保存设置失败: ' + (err?.toString() || '未知错误'));
+    }
+  };
+
   const loadPlatform = async () => {
     try {
       const p = await api.getPlatform();
@@ -111,6 +182,7 @@ export const ProjectListPage: React.FC = () => {
     try {
       await projectApi.launch(id);
       loadProjects();
+      loadSessionStats();
     } catch (err: any) {
       alert(`启动失败: ${err}`);
     }
@@ -238,6 +310,41 @@ export const ProjectListPage: React.FC = () => {
             {/* 标题栏 */}
             <div className="flex items-center justify-between mb-4">
               <h2 className="text-base font-bold">项目列表</h2>
+              <div className="flex items-center gap-3">
+              {/* Linux 上始终使用应用内终端 */}
+              {platform !== 'linux' && (
+                <label className="flex items-center gap-1 text-[11px] text-[#999999] cursor-pointer">
+                  <input
+                    type="checkbox"
+                    checked={inAppTerminal}
+                    onChange={(e) => handleToggleInAppTerminal(e.target.checked)}
+                  />
+                  应用内终端
+                </label>
+              )}
+              <label
+                className="flex items-center gap-1 text-[11px] text-[#999999] cursor-pointer"
+                title="关闭窗口后启动器留在系统托盘，可从托盘菜单快速启动项目"
+              >
+                <input
+                  type="checkbox"
+                  checked={hideToTray}
+                  onChange={(e) => handleToggleHideToTray(e.target.checked)}
+                />
+                关闭到托盘
+              </label>
+              <button
+                onClick={() => setShowCaBundle(true)}
+                className="px-3 py-2 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+              >
+                证书
+              </button>
+              <button
+                onClick={() => setShowLogs(true)}
+                className="px-3 py-2 text-[12px] bg-[#565B5E] hover:bg-[#7A8488] text-white rounded"
+              >
+                日志
+              </button>
               <button
                 onClick={handleCreate}
                 className="px-4 py-2 text-[12px] bg-[#3b82f6] hover:bg-[#2563eb] text-white rounded"
@@ -245,6 +352,7 @@ export const ProjectListPage: React.FC = () => {
               >
                 + 新建项目
               </button>
+              </div>
             </div>
 
             {/* 加载状态 */}
@@ -288,6 +396,7 @@ export const ProjectListPage: React.FC = () => {
                           <ProjectCard
                             project={defaultProject}
                             platform={platform}
+                            sessionStats={sessionStats[defaultProject.id]}
                             onLaunch={handleLaunch}
                             onEdit={handleEdit}
                           />
@@ -305,6 +414,7 @@ export const ProjectListPage: React.FC = () => {
                               key={project.id}
                               project={project}
                               platform={platform}
+                              sessionStats={sessionStats[project.id]}
                               onLaunch={handleLaunch}
                               onEdit={handleEdit}
                             />
@@ -323,6 +433,7 @@ export const ProjectListPage: React.FC = () => {
                               key={project.id}
                               project={project}
                               platform={platform}
+                              sessionStats={sessionStats[project.id]}
                               onLaunch={handleLaunch}
                               onEdit={handleEdit}
                             />
@@ -350,6 +461,9 @@ export const ProjectListPage: React.FC = () => {
           </div>
         </div>
       </div>
+
+      <LogsDialog isOpen={showLogs} onClose={() => setShowLogs(false)} />
+      <CaBundleDialog isOpen={showCaBundle} onClose={() => setShowCaBundle(false)} />
     </div>
   );
 };
@@ -1,12 +1,188 @@
+export type InstallMethod =
+  | 'npm'
+  | 'native'
+  | 'homebrew'
+  | 'version_manager'
+  | 'package_manager'
+  | 'system_installer'
+  | 'unknown';
+
+export type DependencyError =
+  | { kind: 'not_found'; command: string }
+  | { kind: 'command_failed'; command: string; exit_code: number | null; stderr: string }
+  | { kind: 'spawn_failed'; command: string; message: string }
+  | { kind: 'version_unparseable'; raw_output: string }
+  | { kind: 'below_minimum'; version: string; minimum: string };
+
 export interface DependencyStatus {
   installed: boolean;
   version: string | null;
   meets_requirement: boolean;
   latest_version: string | null;
   update_available: boolean;
+  error: DependencyError | null;
+  path: string | null;
+  install_method: InstallMethod | null;
+  last_checked_at: number | null;
+  latest_version_stale: boolean;
+  pinned_version: string | null;
+}
+
+export interface LauncherSettings {
+  npm_registry: string;
+  update_check_ttl_secs: number;
+  offline_mode: boolean;
+  claude_pinned_version: string | null;
+  in_app_terminal: boolean;
+  ca_bundle: CaBundleConfig;
+  hide_to_tray_on_close: boolean; // 关闭窗口时隐藏到托盘
+}
+
+export interface LauncherSettingsSnapshot {
+  settings: LauncherSettings;
+  version: string; // 保存时回传，设置在读取后被修改则拒绝保存
+}
+
+export interface CaBundleConfig {
+  path: string; // 为空表示未设置
+  set_ssl_cert_file: boolean; // 同时设置 SSL_CERT_FILE (会替换默认信任库)
+}
+
+export interface CertificateInfo {
+  subject: string;
+  issuer: string;
+  not_before: string;
+  not_after: string;
+  expired: boolean;
+  is_ca: boolean;
+}
+
+export interface CaBundleInfo {
+  path: string;
+  certificates: CertificateInfo[];
+}
+
+export interface RegistryConfig {
+  url: string;
+  source: 'settings' | 'environment' | 'npmrc' | 'default';
+  has_auth_token: boolean;
+}
+
+export type InstallTarget = 'nodejs' | 'claude' | 'gitbash';
+
+export type InstallAction = 'install' | 'update' | 'switch_version' | 'migrate';
+
+export interface ClaudeInstallations {
+  active_method: InstallMethod | null;
+  active_path: string | null;
+  npm_path: string | null;
+  native_path: string | null;
+  can_migrate: boolean;
+}
+
+export interface PublishedVersion {
+  version: string;
+  published_at: string | null;
+  deprecated: boolean;
+}
+
+export interface PackageVersions {
+  dist_tags: Record<string, string>;
+  versions: PublishedVersion[];
+}
+
+export interface NpmPrefixStatus {
+  prefix: string | null;
+  writable: boolean;
+  user_prefix: string;
+  using_user_prefix: boolean;
+  user_bin_dir: string;
+  user_bin_on_path: boolean;
+}
+
+export interface CleanupOptions {
+  claude_package: boolean;
+  launcher_data: boolean;
+  settings_env: boolean;
+  user_env_vars: boolean;
+  temp_scripts: boolean;
+  dry_run: boolean;
+}
+
+export type CleanupCategory = 'claude_package' | 'launcher_data' | 'settings_env' | 'user_env_vars' | 'temp_scripts';
+
+export type CleanupAction =
+  | { kind: 'delete_file'; path: string }
+  | { kind: 'delete_dir'; path: string }
+  | { kind: 'run_command'; program: string; args: string[] }
+  | { kind: 'remove_settings_env'; path: string; keys: string[] }
+  | { kind: 'remove_user_env_var'; name: string }
+  | { kind: 'remove_profile_path'; path: string }
+  | { kind: 'remove_user_path_entry'; dir: string }
+  | { kind: 'manual'; hint: string };
+
+export interface CleanupItem {
+  category: CleanupCategory;
+  action: CleanupAction;
+  done: boolean;
   error: string | null;
 }
 
+export interface CleanupReport {
+  dry_run: boolean;
+  items: CleanupItem[];
+}
+
+export interface OfflinePackage {
+  name: string;
+  version: string;
+  file: string;
+  shasum: string;
+  integrity: string | null;
+}
+
+export interface OfflineManifest {
+  format_version: number;
+  created_at: number;
+  registry: string;
+  packages: OfflinePackage[];
+}
+
+export interface VerifiedPackage {
+  name: string;
+  version: string;
+  tarball: string;
+  verified_with: string;
+}
+
+export type InstallJobState = 'running' | 'succeeded' | 'failed' | 'cancelled';
+
+export interface InstallJobInfo {
+  id: string;
+  target: InstallTarget;
+  action: InstallAction;
+  state: InstallJobState;
+  started_at: number;
+  finished_at: number | null;
+  exit_code: number | null;
+  error: string | null;
+  script_sha256: string | null;
+  output_tail: string[];
+}
+
+export interface InstallOutputEvent {
+  kind: 'output';
+  job_id: string;
+  stream: 'stdout' | 'stderr';
+  line: string;
+}
+
+export interface InstallFinishedEvent {
+  kind: 'finished';
+  job: InstallJobInfo;
+  status: DependencyStatus;
+}
+
 export interface AppConfig {
   mode: 'claude' | 'custom';
   proxy: string;
@@ -14,6 +190,7 @@ export interface AppConfig {
   base_url: string;
   token: string;
   skip_permissions: boolean;
+  file_version?: string; // 读取时默认项目的版本，保存时回传
 }
 
 export const DEFAULT_CONFIG: AppConfig = {
@@ -30,3 +207,64 @@ export const MODEL_OPTIONS = [
   'qwen3-235b-a22b',
   'qwen3-coder-480b-a35b',
 ];
+
+export interface TerminalInfo {
+  id: string;
+  title: string;
+  project_id: string | null;
+  session_id: string | null;
+  pid: number | null;
+  cols: number;
+  rows: number;
+  started_at: number;
+  exited: boolean;
+  exit_code: number | null;
+}
+
+export interface TerminalOutputEvent {
+  kind: 'output';
+  terminal_id: string;
+  offset: number;
+  data: string; // base64
+}
+
+export interface TerminalExitEvent {
+  kind: 'exit';
+  terminal_id: string;
+  exit_code: number | null;
+}
+
+export interface TerminalSnapshot {
+  data: string; // base64
+  end: number;
+}
+
+export type LogLevel = 'debug' | 'info' | 'warn' | 'error';
+
+export interface LogEntry {
+  timestamp: string; // RFC 3339
+  level: LogLevel;
+  source: string;
+  message: string;
+  fields?: Record<string, unknown>;
+}
+
+export interface LogFilter {
+  min_level?: LogLevel;
+  source?: string;
+  contains?: string;
+  since?: string;
+  limit?: number;
+}
+
+// 外部链接 (claude-launcher://) 请求
+export type DeepLinkAction =
+  | { kind: 'launch'; project_id: string; project_name: string; working_directory: string }
+  | { kind: 'open'; path: string; project_id: string | null; project_name: string | null };
+
+export interface DeepLinkRequest {
+  id: string;
+  url: string;
+  action: DeepLinkAction | null; // 链接无效时为 null
+  error: string | null;
+}
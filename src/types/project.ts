@@ -1,10 +1,26 @@
+import type { CaBundleConfig } from '../types';
+
+export interface LaunchHook {
+  command: string;
+  timeout_secs: number;
+  abort_on_failure: boolean; // 启动前: 失败时不启动 Claude Code; 退出后: 跳过其余钩子
+}
+
 export interface ProjectConfig {
   mode: 'claude' | 'custom';
-  proxy: string;
+  proxy: string; // HTTP 代理，不含认证信息
+  https_proxy: string; // 为空时与 proxy 相同
+  no_proxy: string; // 逗号分隔
+  inherit_system_proxy: boolean;
+  proxy_username: string;
+  proxy_password: string;
   model: string;
   base_url: string;
   token: string;
   skip_permissions: boolean;
+  ca_bundle: CaBundleConfig; // path 为空时使用全局证书
+  pre_launch_hooks: LaunchHook[];
+  post_exit_hooks: LaunchHook[];
 }
 
 export interface Project {
@@ -19,6 +35,61 @@ export interface Project {
   is_pinned: boolean;
   pinned_at?: number;
   sort_order: number;
+  shortcut_path?: string | null; // 已创建的桌面快捷方式
+}
+
+// 编辑页加载的项目及其版本；保存时带回版本，项目在此期间被修改则拒绝保存
+export interface ProjectSnapshot {
+  project: Project;
+  version: string;
+}
+
+export type SessionState = 'starting' | 'running' | 'exited' | 'lost';
+
+export interface LaunchSession {
+  id: string;
+  project_id: string | null;
+  project_name: string | null;
+  working_directory: string | null;
+  pid: number | null;
+  state: SessionState;
+  started_at: number;
+  ended_at: number | null;
+  exit_code: number | null;
+}
+
+export interface ProjectSessionStats {
+  running: number;
+  session_count: number;
+  total_secs: number;
+}
+
+export interface ProxyInfo {
+  scheme: string;
+  host: string;
+  port: number | null;
+  url: string; // 去掉认证信息后的地址
+  username: string;
+  password: string;
+}
+
+export type ProxySource = 'environment' | 'windows' | 'macos' | 'gnome' | 'kde';
+
+export interface DetectedProxy {
+  source: ProxySource;
+  http_proxy: string | null;
+  https_proxy: string | null;
+  no_proxy: string | null;
+  pac_url: string | null; // 自动配置脚本，启动器无法解析
+}
+
+export interface ConnectivityResult {
+  target: string;
+  proxy: string | null; // 密码已隐藏；直连时为 null
+  ok: boolean;
+  status: number | null;
+  latency_ms: number;
+  error: string | null;
 }
 
 export interface CreateProjectInput {
@@ -47,8 +118,16 @@ export interface PinnedOrderItem {
 export const DEFAULT_PROJECT_CONFIG: ProjectConfig = {
   mode: 'claude',
   proxy: '',
+  https_proxy: '',
+  no_proxy: '',
+  inherit_system_proxy: false,
+  proxy_username: '',
+  proxy_password: '',
   model: 'qwen3-coder-480b-a35b',
   base_url: 'http://litellm.uattest.weoa.com',
   token: '',
   skip_permissions: true,
+  ca_bundle: { path: '', set_ssl_cert_file: false },
+  pre_launch_hooks: [],
+  post_exit_hooks: [],
 };